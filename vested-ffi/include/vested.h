@@ -0,0 +1,77 @@
+#ifndef VESTED_H
+#define VESTED_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct VestedGrant VestedGrant;
+
+typedef struct {
+    int32_t year;
+    uint32_t month;
+    uint32_t day;
+} VestedDate;
+
+typedef struct {
+    VestedDate date;
+    int64_t vested_this_period;
+    int64_t cumulative_vested_amount;
+    /* Amount vested by the date before rounding to whole shares. */
+    double precise_cumulative_amount;
+} VestedPeriod;
+
+/* An array of periods, with a null pointer whenever it's empty, e.g. if the
+ * schedule couldn't be calculated. */
+typedef struct {
+    VestedPeriod *periods;
+    size_t len;
+} VestedSchedule;
+
+typedef enum {
+    VESTED_STATUS_OK = 0,
+    VESTED_STATUS_NULL_POINTER = 1,
+    VESTED_STATUS_INVALID_DATE = 2,
+    VESTED_STATUS_INVALID_INTERVAL = 3,
+    VESTED_STATUS_INVALID_GRANT = 4,
+    /* The calculation panicked, which is a bug in the library. */
+    VESTED_STATUS_PANIC = 5,
+} VestedStatus;
+
+typedef enum {
+    VESTED_INTERVAL_WEEKLY = 0,
+    VESTED_INTERVAL_BI_WEEKLY = 1,
+    VESTED_INTERVAL_MONTHLY = 2,
+    VESTED_INTERVAL_QUARTERLY = 3,
+    VESTED_INTERVAL_ANNUAL = 4,
+} VestedInterval;
+
+/* Creates a grant and stores it in `out`. Release it with vested_grant_free. */
+VestedStatus vested_grant_new(int64_t amount, VestedDate grant_date, int32_t interval,
+                              int32_t length_months, int32_t cliff_months,
+                              float cliff_percentage, VestedGrant **out);
+
+/* Stops the grant vesting on the date, forfeiting the unvested shares. */
+VestedStatus vested_grant_terminate(VestedGrant *grant, VestedDate date);
+
+/* Returns the shares vested on the date, or -1 if the grant is null, the date
+ * doesn't exist or the calculation panicked. */
+int64_t vested_grant_vested_shares(const VestedGrant *grant, VestedDate date);
+
+/* Calculates the vesting schedule. Release it with vested_schedule_free. It's
+ * empty with a null pointer if the grant is null, the calculation panicked or there
+ * are no periods. */
+VestedSchedule vested_grant_schedule(const VestedGrant *grant);
+
+void vested_schedule_free(VestedSchedule schedule);
+
+void vested_grant_free(VestedGrant *grant);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
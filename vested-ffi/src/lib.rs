@@ -0,0 +1,339 @@
+//! C bindings for the vested calculator, declared in `include/vested.h`.
+//!
+//! Grants are opaque pointers created with `vested_grant_new` and released with
+//! `vested_grant_free`. Schedules are returned as arrays owned by the caller until
+//! passed to `vested_schedule_free`. Panics are caught at the boundary instead of
+//! unwinding into C.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use chrono::Datelike;
+use vested::{Grant, LocalDate, VestingInterval};
+
+/// Opaque handle to a grant.
+pub struct VestedGrant {
+    grant: Grant,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VestedDate {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VestedPeriod {
+    pub date: VestedDate,
+    pub vested_this_period: i64,
+    pub cumulative_vested_amount: i64,
+    /// Amount vested by the date before rounding to whole shares.
+    pub precise_cumulative_amount: f64,
+}
+
+/// An array of periods, with a null pointer whenever it's empty, e.g. if the schedule
+/// couldn't be calculated.
+#[repr(C)]
+pub struct VestedSchedule {
+    pub periods: *mut VestedPeriod,
+    pub len: usize,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VestedStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidDate = 2,
+    InvalidInterval = 3,
+    /// The terms don't produce a meaningful schedule, e.g. a cliff past the length.
+    InvalidGrant = 4,
+    /// The calculation panicked, which is a bug in the library.
+    Panic = 5,
+}
+
+impl VestedSchedule {
+    fn empty() -> VestedSchedule {
+        VestedSchedule {
+            periods: ptr::null_mut(),
+            len: 0,
+        }
+    }
+}
+
+impl VestedDate {
+    fn to_local_date(self) -> Option<LocalDate> {
+        LocalDate::from_ymd_opt(self.year, self.month, self.day)
+    }
+
+    fn from_local_date(date: LocalDate) -> VestedDate {
+        let date = date.naive_date();
+
+        VestedDate {
+            year: date.year(),
+            month: date.month(),
+            day: date.day(),
+        }
+    }
+}
+
+/// Runs the body of an exported function, returning `on_panic` if it panics, since
+/// unwinding into C is undefined behavior.
+fn catch_panic<T>(on_panic: T, body: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or(on_panic)
+}
+
+/// Converts an interval passed from C, which is an integer rather than a Rust enum so
+/// that out of range values can be rejected.
+fn to_interval(interval: i32) -> Option<VestingInterval> {
+    match interval {
+        0 => Some(VestingInterval::Weekly),
+        1 => Some(VestingInterval::BiWeekly),
+        2 => Some(VestingInterval::Monthly),
+        3 => Some(VestingInterval::Quarterly),
+        4 => Some(VestingInterval::Annual),
+        _ => None,
+    }
+}
+
+/// Creates a grant and stores it in `out`. The interval is one of the
+/// `VESTED_INTERVAL_*` constants in the header, weekly (0) through annual (4).
+///
+/// # Safety
+///
+/// `out` must be null or valid for writing a pointer. On success the grant must be
+/// released with `vested_grant_free`.
+#[no_mangle]
+pub unsafe extern "C" fn vested_grant_new(
+    amount: i64,
+    grant_date: VestedDate,
+    interval: i32,
+    length_months: i32,
+    cliff_months: i32,
+    cliff_percentage: f32,
+    out: *mut *mut VestedGrant,
+) -> VestedStatus {
+    catch_panic(VestedStatus::Panic, || {
+        if out.is_null() {
+            return VestedStatus::NullPointer;
+        }
+
+        let Some(grant_date) = grant_date.to_local_date() else {
+            return VestedStatus::InvalidDate;
+        };
+        let Some(interval) = to_interval(interval) else {
+            return VestedStatus::InvalidInterval;
+        };
+
+        let grant = Grant::builder()
+            .amount(amount)
+            .grant_date(grant_date)
+            .interval(interval)
+            .length_months(length_months)
+            .cliff_months(cliff_months)
+            .cliff_percentage(cliff_percentage)
+            .build();
+
+        match grant {
+            Ok(grant) => {
+                *out = Box::into_raw(Box::new(VestedGrant { grant }));
+                VestedStatus::Ok
+            }
+            Err(_) => VestedStatus::InvalidGrant,
+        }
+    })
+}
+
+/// Stops the grant vesting on the date, forfeiting the unvested shares.
+///
+/// # Safety
+///
+/// `grant` must be null or a grant from `vested_grant_new` that hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn vested_grant_terminate(
+    grant: *mut VestedGrant,
+    date: VestedDate,
+) -> VestedStatus {
+    catch_panic(VestedStatus::Panic, || {
+        let Some(grant) = grant.as_mut() else {
+            return VestedStatus::NullPointer;
+        };
+        let Some(date) = date.to_local_date() else {
+            return VestedStatus::InvalidDate;
+        };
+
+        grant.grant.terminate(date);
+        VestedStatus::Ok
+    })
+}
+
+/// Returns the shares vested on the date, or -1 if the grant is null, the date
+/// doesn't exist or the calculation panicked.
+///
+/// # Safety
+///
+/// `grant` must be null or a grant from `vested_grant_new` that hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn vested_grant_vested_shares(
+    grant: *const VestedGrant,
+    date: VestedDate,
+) -> i64 {
+    catch_panic(-1, || match (grant.as_ref(), date.to_local_date()) {
+        (Some(grant), Some(date)) => grant.grant.calculate_vested_shares(date),
+        _ => -1,
+    })
+}
+
+/// Calculates the grant's vesting schedule. The schedule must be released with
+/// `vested_schedule_free`. It's empty with a null pointer if the grant is null, the
+/// calculation panicked or there are no periods.
+///
+/// # Safety
+///
+/// `grant` must be null or a grant from `vested_grant_new` that hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn vested_grant_schedule(grant: *const VestedGrant) -> VestedSchedule {
+    catch_panic(VestedSchedule::empty(), || {
+        let Some(grant) = grant.as_ref() else {
+            return VestedSchedule::empty();
+        };
+
+        let periods: Box<[VestedPeriod]> = grant
+            .grant
+            .calculate_vesting_schedule()
+            .periods
+            .into_iter()
+            .map(|period| VestedPeriod {
+                date: VestedDate::from_local_date(period.date),
+                vested_this_period: period.vested_this_period,
+                cumulative_vested_amount: period.cumulative_vested_amount,
+                precise_cumulative_amount: period.precise_cumulative_amount,
+            })
+            .collect();
+        let len = periods.len();
+
+        // An empty boxed slice has a dangling pointer rather than a null one, which C
+        // callers can't tell apart from a real array.
+        if len == 0 {
+            return VestedSchedule::empty();
+        }
+
+        VestedSchedule {
+            periods: Box::into_raw(periods) as *mut VestedPeriod,
+            len,
+        }
+    })
+}
+
+/// Releases a schedule.
+///
+/// # Safety
+///
+/// `schedule` must come from `vested_grant_schedule` and not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn vested_schedule_free(schedule: VestedSchedule) {
+    catch_panic((), || {
+        if !schedule.periods.is_null() {
+            drop(Box::from_raw(ptr::slice_from_raw_parts_mut(
+                schedule.periods,
+                schedule.len,
+            )));
+        }
+    })
+}
+
+/// Releases a grant.
+///
+/// # Safety
+///
+/// `grant` must be null or a grant from `vested_grant_new` that hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn vested_grant_free(grant: *mut VestedGrant) {
+    catch_panic((), || {
+        if !grant.is_null() {
+            drop(Box::from_raw(grant));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+    use std::slice;
+
+    use super::*;
+
+    #[test]
+    fn it_can_calculate_schedules_through_the_c_api() {
+        let grant_date = VestedDate {
+            year: 2021,
+            month: 1,
+            day: 15,
+        };
+
+        unsafe {
+            let mut grant = ptr::null_mut();
+
+            assert_eq!(
+                vested_grant_new(300, grant_date, 2, 3, 0, 0.0, &mut grant),
+                VestedStatus::Ok
+            );
+            assert_eq!(
+                vested_grant_new(300, grant_date, 9, 3, 0, 0.0, &mut grant),
+                VestedStatus::InvalidInterval
+            );
+
+            let schedule = vested_grant_schedule(grant);
+            let periods = slice::from_raw_parts(schedule.periods, schedule.len);
+
+            assert_eq!(periods.len(), 4);
+            assert_eq!(
+                periods[1],
+                VestedPeriod {
+                    date: VestedDate {
+                        year: 2021,
+                        month: 2,
+                        day: 15,
+                    },
+                    vested_this_period: 100,
+                    cumulative_vested_amount: 100,
+                    precise_cumulative_amount: 100.0,
+                }
+            );
+            assert_eq!(
+                vested_grant_vested_shares(
+                    grant,
+                    VestedDate {
+                        year: 2021,
+                        month: 3,
+                        day: 20,
+                    }
+                ),
+                200
+            );
+
+            vested_schedule_free(schedule);
+            vested_grant_free(grant);
+
+            let schedule = vested_grant_schedule(ptr::null());
+
+            assert!(schedule.periods.is_null());
+            assert_eq!(schedule.len, 0);
+        }
+    }
+
+    #[test]
+    fn it_catches_panics_at_the_boundary() {
+        assert_eq!(
+            catch_panic(VestedStatus::Panic, || VestedStatus::InvalidGrant),
+            VestedStatus::InvalidGrant
+        );
+        assert_eq!(
+            catch_panic(VestedStatus::Panic, || -> VestedStatus { panic!("bug") }),
+            VestedStatus::Panic
+        );
+    }
+}
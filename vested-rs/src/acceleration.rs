@@ -0,0 +1,192 @@
+use chronoutil::RelativeDuration;
+
+use crate::{Grant, LocalDate, VestingPeriod, VestingSchedule};
+
+/// Vesting acceleration on a change of control.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AccelerationPolicy {
+    None,
+    /// The given fraction (0.0 - 1.0) of the unvested shares vests on the change of
+    /// control itself.
+    SingleTrigger(f32),
+    /// The shares that would have vested over the given number of months after the
+    /// change of control vest on it.
+    SingleTriggerMonths(i32),
+    /// The given fraction (0.0 - 1.0) of the unvested shares vests only if the grant
+    /// is also terminated on or after the change of control.
+    DoubleTrigger(f32),
+}
+
+impl Grant {
+    /// Returns the date acceleration takes effect, or `None` if the policy isn't
+    /// triggered.
+    fn acceleration_date(
+        &self,
+        event_date: LocalDate,
+        policy: AccelerationPolicy,
+    ) -> Option<LocalDate> {
+        match policy {
+            AccelerationPolicy::None => None,
+            AccelerationPolicy::SingleTrigger(_) | AccelerationPolicy::SingleTriggerMonths(_) => {
+                Some(event_date)
+            }
+            AccelerationPolicy::DoubleTrigger(_) => self
+                .termination_date
+                .filter(|termination_date| *termination_date >= event_date),
+        }
+    }
+
+    /// Calculates the vested amount on the given date when the grant is accelerated by
+    /// a change of control on `event_date`. The shares left unvested keep vesting on
+    /// the original schedule, reduced pro rata, or on their original dates when whole
+    /// months are accelerated.
+    pub fn calculate_accelerated_vested_amount(
+        &self,
+        event_date: LocalDate,
+        policy: AccelerationPolicy,
+        date: LocalDate,
+    ) -> f32 {
+        let vested_amount = self.calculate_vested_amount(date);
+
+        match self.acceleration_date(event_date, policy) {
+            Some(acceleration_date) if date >= acceleration_date => match policy {
+                AccelerationPolicy::SingleTrigger(percentage)
+                | AccelerationPolicy::DoubleTrigger(percentage) => {
+                    vested_amount
+                        + (self.amount as f32 - vested_amount) * percentage.clamp(0.0, 1.0)
+                }
+                AccelerationPolicy::SingleTriggerMonths(months) => {
+                    vested_amount.max(crate::vested_amount(
+                        &self.vesting_schedule,
+                        self.amount,
+                        self.vesting_commencement_date(),
+                        acceleration_date + RelativeDuration::months(months),
+                    ))
+                }
+                AccelerationPolicy::None => vested_amount,
+            },
+            _ => vested_amount,
+        }
+    }
+
+    /// Recomputes the vesting schedule with the acceleration triggered by a change of
+    /// control on `event_date`, adding a period on the acceleration date itself.
+    pub fn apply_acceleration(
+        &self,
+        event_date: LocalDate,
+        policy: AccelerationPolicy,
+    ) -> VestingSchedule {
+        let mut vesting_schedule = self.calculate_vesting_schedule();
+
+        let acceleration_date = match self.acceleration_date(event_date, policy) {
+            Some(acceleration_date) => acceleration_date,
+            None => return vesting_schedule,
+        };
+
+        let precise_amount =
+            |date| self.calculate_accelerated_vested_amount(event_date, policy, date) as f64;
+        let accelerated_amount = |date| precise_amount(date).floor() as i64;
+
+        for period in vesting_schedule.periods.iter_mut() {
+            period.cumulative_vested_amount = accelerated_amount(period.date);
+            period.precise_cumulative_amount = precise_amount(period.date);
+        }
+
+        if acceleration_date <= vesting_schedule.to_date
+            && !vesting_schedule
+                .periods
+                .iter()
+                .any(|period| period.date == acceleration_date)
+        {
+            let index = vesting_schedule
+                .periods
+                .partition_point(|period| period.date < acceleration_date);
+
+            vesting_schedule.periods.insert(
+                index,
+                VestingPeriod {
+                    date: acceleration_date,
+                    cumulative_vested_amount: accelerated_amount(acceleration_date),
+                    vested_this_period: 0,
+                    precise_cumulative_amount: precise_amount(acceleration_date),
+                },
+            );
+        }
+
+        VestingPeriod::update_vested_this_period(&mut vesting_schedule.periods);
+
+        if let Some(termination_date) = self.termination_date {
+            vesting_schedule.forfeited_amount = self.amount - accelerated_amount(termination_date);
+        }
+
+        vesting_schedule
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AccelerationPolicy, LocalDate};
+    use crate::{Grant, VestingPeriod, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_accelerate_vesting_on_a_change_of_control() {
+        let mut grant = Grant::for_test(
+            4_800,
+            LocalDate::from_ymd(2020, 1, 1),
+            VestingScheduleConfiguration::monthly(0, 0.0, 48),
+        );
+
+        let change_of_control = LocalDate::from_ymd(2021, 1, 15);
+
+        /*
+         * 1200 shares vested by the change of control, half of the remaining 3600 vest on it.
+         */
+
+        let vesting_schedule =
+            grant.apply_acceleration(change_of_control, AccelerationPolicy::SingleTrigger(0.5));
+
+        assert_eq!(vesting_schedule.periods.len(), 50);
+        assert_eq!(
+            vesting_schedule.periods[13],
+            VestingPeriod {
+                date: change_of_control,
+                cumulative_vested_amount: 3000,
+                vested_this_period: 1800,
+                precise_cumulative_amount: 3000.0,
+            }
+        );
+        assert_eq!(
+            vesting_schedule
+                .periods
+                .last()
+                .unwrap()
+                .cumulative_vested_amount,
+            4800
+        );
+
+        assert_eq!(
+            grant.calculate_accelerated_vested_amount(
+                change_of_control,
+                AccelerationPolicy::DoubleTrigger(1.0),
+                LocalDate::from_ymd(2022, 1, 1)
+            ),
+            2400.0
+        );
+
+        grant.terminate(LocalDate::from_ymd(2021, 6, 1));
+
+        let vesting_schedule =
+            grant.apply_acceleration(change_of_control, AccelerationPolicy::DoubleTrigger(1.0));
+
+        assert_eq!(
+            vesting_schedule.periods.last(),
+            Some(&VestingPeriod {
+                date: LocalDate::from_ymd(2021, 6, 1),
+                cumulative_vested_amount: 4800,
+                vested_this_period: 3200,
+                precise_cumulative_amount: 4800.0,
+            })
+        );
+        assert_eq!(vesting_schedule.forfeited_amount, 0);
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+
+use chrono::Datelike;
+
+use crate::{months_between, Grant, LocalDate, VestEvent};
+
+#[derive(Debug, PartialEq)]
+pub struct VestingAnalytics {
+    /// Average number of shares vested per month over the window.
+    pub average_monthly_vest_rate: f32,
+    /// Largest amount vesting on a single date, combined across all grants.
+    pub largest_single_vest: Option<VestEvent>,
+    /// Fraction of the shares vested in the window that land in each calendar quarter (Q1 - Q4).
+    pub quarterly_concentration: [f32; 4],
+}
+
+/// Analyzes how shares vest across the given grants between two dates (inclusive).
+pub fn analyze_vesting(
+    grants: &[Grant],
+    from_date: LocalDate,
+    to_date: LocalDate,
+) -> VestingAnalytics {
+    let mut amounts_by_date: BTreeMap<LocalDate, i64> = BTreeMap::new();
+
+    for event in grants.iter().flat_map(|grant| grant.vest_events()) {
+        if event.date >= from_date && event.date <= to_date {
+            *amounts_by_date.entry(event.date).or_insert(0) += event.amount;
+        }
+    }
+
+    let total_vested: i64 = amounts_by_date.values().sum();
+    let months = months_between(from_date, to_date).max(1);
+
+    let mut largest_single_vest: Option<VestEvent> = None;
+    let mut quarterly_amounts = [0; 4];
+
+    for (date, amount) in amounts_by_date {
+        quarterly_amounts[date.month0() as usize / 3] += amount;
+
+        if largest_single_vest
+            .as_ref()
+            .is_none_or(|largest| amount > largest.amount)
+        {
+            largest_single_vest = Some(VestEvent { date, amount });
+        }
+    }
+
+    let quarterly_concentration = quarterly_amounts.map(|amount| {
+        if total_vested > 0 {
+            amount as f32 / total_vested as f32
+        } else {
+            0.0
+        }
+    });
+
+    VestingAnalytics {
+        average_monthly_vest_rate: total_vested as f32 / months as f32,
+        largest_single_vest,
+        quarterly_concentration,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::{analyze_vesting, LocalDate};
+    use crate::{Grant, VestEvent, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_analyze_vesting_across_grants() {
+        let grants = vec![
+            Grant::for_test(
+                1_200,
+                LocalDate::from_ymd(2020, 1, 15),
+                VestingScheduleConfiguration::monthly(0, 0.0, 4),
+            ),
+            Grant::for_test(
+                400,
+                LocalDate::from_ymd(2020, 2, 15),
+                VestingScheduleConfiguration::monthly(0, 0.0, 2),
+            ),
+        ];
+
+        /*
+         * - 2020/2/15: 300 shares vest
+         * - 2020/3/15: 500 shares vest
+         * - 2020/4/15: 500 shares vest
+         * - 2020/5/15: 300 shares vest
+         */
+
+        let analytics = analyze_vesting(
+            &grants,
+            LocalDate::from_ymd(2020, 1, 1),
+            LocalDate::from_ymd(2020, 12, 31),
+        );
+
+        assert_relative_eq!(
+            analytics.average_monthly_vest_rate,
+            145.45,
+            max_relative = 0.005
+        );
+        assert_eq!(
+            analytics.largest_single_vest,
+            Some(VestEvent {
+                date: LocalDate::from_ymd(2020, 3, 15),
+                amount: 500,
+            })
+        );
+        assert_eq!(analytics.quarterly_concentration, [0.5, 0.5, 0.0, 0.0]);
+    }
+}
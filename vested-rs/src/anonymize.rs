@@ -0,0 +1,94 @@
+use crate::simulation::Rng;
+use crate::{Grant, Holder};
+
+/// Smallest and largest factor grant amounts are scaled by.
+const SCALE_RANGE: (f64, f64) = (0.5, 2.0);
+
+/// Copies the holders for sharing outside the company, e.g. in a bug report: names are
+/// replaced with `Holder 1`, `Holder 2` and so on in shuffled order, and every grant
+/// amount is scaled by the same random factor between 0.5 and 2. Dates and schedules
+/// are kept, so vesting keeps its shape. The same seed always gives the same result.
+pub fn anonymize(holders: &[Holder], seed: u64) -> Vec<Holder> {
+    let mut rng = Rng(seed);
+    let factor = SCALE_RANGE.0 + rng.next_uniform() * (SCALE_RANGE.1 - SCALE_RANGE.0);
+
+    let mut order: Vec<usize> = (0..holders.len()).collect();
+
+    // Fisher-Yates shuffle, so the order doesn't give away e.g. alphabetical names.
+    for i in (1..order.len()).rev() {
+        let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+        order.swap(i, j);
+    }
+
+    order
+        .into_iter()
+        .enumerate()
+        .map(|(index, holder_index)| Holder {
+            name: format!("Holder {}", index + 1),
+            grants: holders[holder_index]
+                .grants
+                .iter()
+                .map(|grant| Grant {
+                    amount: ((grant.amount as f64 * factor).round() as i64).max(1),
+                    ..grant.clone()
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::anonymize;
+    use crate::{Grant, Holder, LocalDate, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_anonymize_holders() {
+        let holder = |name: &str, amount| Holder {
+            name: name.to_string(),
+            grants: vec![Grant::for_test(
+                amount,
+                LocalDate::from_ymd(2021, 1, 15),
+                VestingScheduleConfiguration::monthly(12, 0.25, 48),
+            )],
+        };
+        let holders = vec![holder("Jane Doe", 4_800), holder("John Roe", 9_600)];
+
+        let anonymized = anonymize(&holders, 42);
+        let mut names: Vec<&str> = anonymized.iter().map(|holder| &holder.name[..]).collect();
+        names.sort();
+
+        assert_eq!(names, ["Holder 1", "Holder 2"]);
+
+        let mut amounts: Vec<i64> = anonymized
+            .iter()
+            .map(|holder| holder.grants[0].amount)
+            .collect();
+        amounts.sort();
+
+        assert!((amounts[1] - amounts[0] * 2).abs() <= 1);
+        assert_ne!(amounts[0], 4_800);
+
+        /*
+         * A quarter is vested after the cliff, give or take rounding, whatever the
+         * amount.
+         */
+
+        let date = LocalDate::from_ymd(2022, 1, 15);
+        for holder in &anonymized {
+            let grant = &holder.grants[0];
+            assert!((grant.calculate_vested_shares(date) - grant.amount / 4).abs() <= 1);
+        }
+
+        assert_eq!(
+            anonymize(&holders, 42)
+                .iter()
+                .map(|holder| holder.grants[0].amount)
+                .collect::<Vec<_>>(),
+            anonymized
+                .iter()
+                .map(|holder| holder.grants[0].amount)
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use crate::narrative::{long_date, shares};
+use crate::{Grant, LocalDate, Money, Price};
+
+/// A common question about a grant, e.g. asked of a chat bot.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Question {
+    /// How many shares have vested by a date, e.g. today?
+    VestedOn(LocalDate),
+    /// When is the next vest after a date, and how big is it?
+    NextVestAfter(LocalDate),
+    /// What are the shares vested by a date worth at a price per share?
+    ValueOn {
+        date: LocalDate,
+        price_per_share: Price,
+    },
+    /// What happens to the grant when leaving on a date?
+    LeavingOn(LocalDate),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnswerDetails {
+    Vested {
+        date: LocalDate,
+        vested_shares: i64,
+        unvested_shares: i64,
+    },
+    /// `None` once the grant has fully vested or stopped vesting.
+    NextVest(Option<(LocalDate, i64)>),
+    Value {
+        vested_shares: i64,
+        value: Money,
+    },
+    Leaving {
+        vested_shares: i64,
+        forfeited_shares: i64,
+    },
+}
+
+/// An answer as both structured data and a sentence to reply with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub details: AnswerDetails,
+    pub text: String,
+}
+
+impl Grant {
+    /// Answers a common question about the grant.
+    pub fn answer(&self, question: Question) -> Answer {
+        match question {
+            Question::VestedOn(date) => {
+                let vested_shares = self.calculate_vested_shares(date);
+                let unvested_shares = self.calculate_unvested_amount(date);
+
+                Answer {
+                    details: AnswerDetails::Vested {
+                        date,
+                        vested_shares,
+                        unvested_shares,
+                    },
+                    text: format!(
+                        "As of {}, you have vested {}, with {} still unvested.",
+                        long_date(date),
+                        shares(vested_shares),
+                        shares(unvested_shares)
+                    ),
+                }
+            }
+            Question::NextVestAfter(date) => {
+                let next_vest = self
+                    .vest_events()
+                    .into_iter()
+                    .find(|event| event.date > date)
+                    .map(|event| (event.date, event.amount));
+
+                let text = match next_vest {
+                    Some((next_date, amount)) => format!(
+                        "Your next vest is {} on {}.",
+                        shares(amount),
+                        long_date(next_date)
+                    ),
+                    None => format!("You have no vests after {}.", long_date(date)),
+                };
+
+                Answer {
+                    details: AnswerDetails::NextVest(next_vest),
+                    text,
+                }
+            }
+            Question::ValueOn {
+                date,
+                price_per_share,
+            } => {
+                let vested_shares = self.calculate_vested_shares(date);
+                let value = price_per_share * vested_shares;
+
+                Answer {
+                    details: AnswerDetails::Value {
+                        vested_shares,
+                        value,
+                    },
+                    text: format!(
+                        "At {} a share, your {} vested by {} are worth {}.",
+                        price_per_share,
+                        shares(vested_shares),
+                        long_date(date),
+                        value
+                    ),
+                }
+            }
+            Question::LeavingOn(date) => {
+                let mut grant = self.clone();
+                grant.terminate(date);
+
+                let vested_shares = grant.calculate_vested_shares(date);
+                let forfeited_shares = grant.calculate_vesting_schedule().forfeited_amount;
+
+                Answer {
+                    details: AnswerDetails::Leaving {
+                        vested_shares,
+                        forfeited_shares,
+                    },
+                    text: format!(
+                        "If you leave on {}, you keep {} and forfeit {}.",
+                        long_date(date),
+                        shares(vested_shares),
+                        shares(forfeited_shares)
+                    ),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AnswerDetails, LocalDate, Question};
+    use crate::{Currency, Grant, Price, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_answer_common_questions() {
+        let grant = Grant::for_test(
+            4_800,
+            LocalDate::from_ymd(2021, 1, 15),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        );
+        let today = LocalDate::from_ymd(2022, 1, 18);
+
+        assert_eq!(
+            grant.answer(Question::VestedOn(today)).text,
+            "As of Jan 18, 2022, you have vested 1,200 shares, with 3,600 shares still \
+             unvested."
+        );
+
+        let next_vest = grant.answer(Question::NextVestAfter(today));
+
+        assert_eq!(
+            next_vest.details,
+            AnswerDetails::NextVest(Some((LocalDate::from_ymd(2022, 2, 15), 100)))
+        );
+        assert_eq!(
+            next_vest.text,
+            "Your next vest is 100 shares on Feb 15, 2022."
+        );
+
+        assert_eq!(
+            grant
+                .answer(Question::ValueOn {
+                    date: today,
+                    price_per_share: Price::from_major_units(12.5, Currency::Usd),
+                })
+                .text,
+            "At $12.50 a share, your 1,200 shares vested by Jan 18, 2022 are worth \
+             $15,000.00."
+        );
+
+        let leaving = grant.answer(Question::LeavingOn(LocalDate::from_ymd(2022, 2, 20)));
+
+        assert_eq!(
+            leaving.details,
+            AnswerDetails::Leaving {
+                vested_shares: 1_300,
+                forfeited_shares: 3_500,
+            }
+        );
+        assert_eq!(
+            leaving.text,
+            "If you leave on Feb 20, 2022, you keep 1,300 shares and forfeit 3,500 shares."
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{Grant, LocalDate, VestingError, VestingWarning, Warnings};
+
+/// Dates a grant's approval depends on. A grant dated before either is backdated,
+/// which has tax and compliance implications.
+#[derive(Default)]
+pub struct GrantApproval {
+    pub board_approval_date: Option<LocalDate>,
+    /// Date of the 409A valuation used to price the grant.
+    pub valuation_date: Option<LocalDate>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ValidationMode {
+    /// Reports backdating as warnings.
+    #[default]
+    Lenient,
+    /// Rejects backdated grants.
+    Strict,
+}
+
+impl Grant {
+    /// Checks whether the grant is dated before its board approval or 409A valuation.
+    pub fn backdating_warnings(&self, approval: &GrantApproval) -> Warnings {
+        let mut warnings = Vec::new();
+
+        if let Some(board_approval_date) = approval.board_approval_date {
+            if self.grant_date < board_approval_date {
+                warnings.push(VestingWarning::GrantDatePrecedesBoardApproval {
+                    grant_date: self.grant_date,
+                    board_approval_date,
+                });
+            }
+        }
+
+        if let Some(valuation_date) = approval.valuation_date {
+            if self.grant_date < valuation_date {
+                warnings.push(VestingWarning::GrantDatePrecedesValuation {
+                    grant_date: self.grant_date,
+                    valuation_date,
+                });
+            }
+        }
+
+        Warnings { warnings }
+    }
+
+    /// Validates the grant's terms and its dating against its approval. Backdating is
+    /// returned as warnings, or rejected in strict mode.
+    pub fn validate_approval(
+        &self,
+        approval: &GrantApproval,
+        mode: ValidationMode,
+    ) -> Result<Warnings, VestingError> {
+        self.validate()?;
+
+        let latest_approval_date = [approval.board_approval_date, approval.valuation_date]
+            .into_iter()
+            .flatten()
+            .max();
+
+        match latest_approval_date {
+            Some(approval_date)
+                if mode == ValidationMode::Strict && self.grant_date < approval_date =>
+            {
+                Err(VestingError::BackdatedGrant {
+                    grant_date: self.grant_date,
+                    approval_date,
+                })
+            }
+            _ => Ok(self.backdating_warnings(approval)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GrantApproval, LocalDate, ValidationMode};
+    use crate::{Grant, VestingError, VestingScheduleConfiguration, VestingWarning};
+
+    #[test]
+    fn it_flags_backdated_grants() {
+        let grant = Grant::for_test(
+            10_000,
+            LocalDate::from_ymd(2021, 3, 1),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        );
+
+        let approval = GrantApproval {
+            board_approval_date: Some(LocalDate::from_ymd(2021, 3, 15)),
+            valuation_date: Some(LocalDate::from_ymd(2021, 1, 10)),
+        };
+
+        assert_eq!(
+            grant
+                .validate_approval(&approval, ValidationMode::Lenient)
+                .unwrap()
+                .warnings,
+            vec![VestingWarning::GrantDatePrecedesBoardApproval {
+                grant_date: LocalDate::from_ymd(2021, 3, 1),
+                board_approval_date: LocalDate::from_ymd(2021, 3, 15),
+            }]
+        );
+        assert_eq!(
+            grant
+                .validate_approval(&approval, ValidationMode::Strict)
+                .err(),
+            Some(VestingError::BackdatedGrant {
+                grant_date: LocalDate::from_ymd(2021, 3, 1),
+                approval_date: LocalDate::from_ymd(2021, 3, 15),
+            })
+        );
+        assert!(grant
+            .validate_approval(&GrantApproval::default(), ValidationMode::Strict)
+            .unwrap()
+            .is_empty());
+    }
+}
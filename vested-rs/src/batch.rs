@@ -0,0 +1,90 @@
+use crate::{Grant, LocalDate, VestingError};
+
+/// Lazily calculates the vested amount of each grant on the given date, in input
+/// order, so large batches can be streamed out as they're computed.
+pub fn vested_amounts(grants: &[Grant], as_of: LocalDate) -> impl Iterator<Item = f32> + '_ {
+    grants
+        .iter()
+        .map(move |grant| grant.calculate_vested_amount(as_of))
+}
+
+/// Calculates the vested amounts of all grants on the given date in one call.
+pub fn calculate_vested_amounts(grants: &[Grant], as_of: LocalDate) -> Vec<f32> {
+    vested_amounts(grants, as_of).collect()
+}
+
+/// Calculates the vested amounts of all grants on the given date, validating each
+/// grant individually so that one invalid grant doesn't fail the whole batch.
+pub fn try_calculate_vested_amounts(
+    grants: &[Grant],
+    as_of: LocalDate,
+) -> Vec<Result<f32, VestingError>> {
+    grants
+        .iter()
+        .map(|grant| {
+            grant
+                .validate()
+                .map(|_| grant.calculate_vested_amount(as_of))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        calculate_vested_amounts, try_calculate_vested_amounts, vested_amounts, LocalDate,
+    };
+    use crate::{Grant, VestingError, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_calculate_vested_amounts_for_a_batch_of_grants() {
+        let grants: Vec<Grant> = (1..=300)
+            .map(|index| {
+                Grant::for_test(
+                    index * 100,
+                    LocalDate::from_ymd(2020, 2, 6),
+                    VestingScheduleConfiguration::monthly(12, 0.25, 48),
+                )
+            })
+            .collect();
+
+        let vested = calculate_vested_amounts(&grants, LocalDate::from_ymd(2022, 2, 6));
+
+        assert_eq!(vested.len(), 300);
+        assert_eq!(vested[0], 50.0);
+        assert_eq!(vested[299], 15_000.0);
+
+        let chunk_totals: Vec<f32> = vested_amounts(&grants, LocalDate::from_ymd(2021, 2, 6))
+            .collect::<Vec<f32>>()
+            .chunks(100)
+            .map(|chunk| chunk.iter().sum())
+            .collect();
+
+        assert_eq!(chunk_totals, vec![126_250.0, 376_250.0, 626_250.0]);
+    }
+
+    #[test]
+    fn it_returns_per_grant_results_for_invalid_grants() {
+        let grant = |cliff: i32| {
+            Grant::for_test(
+                4_800,
+                LocalDate::from_ymd(2020, 2, 6),
+                VestingScheduleConfiguration::monthly(cliff, 0.25, 48),
+            )
+        };
+
+        let grants = vec![grant(12), grant(60), grant(12)];
+
+        assert_eq!(
+            try_calculate_vested_amounts(&grants, LocalDate::from_ymd(2021, 2, 6)),
+            vec![
+                Ok(1_200.0),
+                Err(VestingError::CliffExceedsLength {
+                    cliff: 60,
+                    length: 48
+                }),
+                Ok(1_200.0),
+            ]
+        );
+    }
+}
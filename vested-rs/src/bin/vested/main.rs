@@ -0,0 +1,284 @@
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::process;
+use std::str::FromStr;
+
+use vested::{Grant, ImportedGrant, LocalDate, VestingInterval};
+
+#[cfg(feature = "tui")]
+mod tui;
+
+const USAGE: &str = "\
+Usage: vested [GRANT] [COMMAND]
+
+Commands:
+  schedule       Print the vesting schedule as a table (default)
+  vested DATE    Print the shares vested on a date
+  describe       Describe the vesting schedule in plain language
+  csv            Write the vesting schedule as CSV
+  ics            Write the vesting dates as an iCalendar file
+  tui            Browse the vesting timeline of one or more grants interactively
+
+Grant:
+  --file PATH                 Read the grant from a JSON or TOML file instead of the
+                              flags below, repeatable for the tui command
+  --amount SHARES             Shares granted
+  --grant-date DATE           Date of the grant, as YYYY-MM-DD
+  --commencement-date DATE    Date vesting is measured from, if not the grant date
+  --interval INTERVAL         weekly, bi-weekly, monthly (default), quarterly or annual
+  --length MONTHS             Months until fully vested
+  --cliff MONTHS              Months until the cliff (default 0)
+  --cliff-percentage FRACTION Share of the grant vesting at the cliff, e.g. 0.25
+  --termination-date DATE     Stop vesting on a date, forfeiting the rest
+";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Schedule,
+    Vested(LocalDate),
+    Describe,
+    Csv,
+    Ics,
+    #[cfg(feature = "tui")]
+    Tui,
+}
+
+fn parse_value<T: FromStr>(flag: &str, value: Option<String>) -> Result<T, String>
+where
+    T::Err: Display,
+{
+    let value = value.ok_or_else(|| format!("{} needs a value", flag))?;
+    value
+        .parse()
+        .map_err(|error| format!("invalid value \"{}\" for {}: {}", value, flag, error))
+}
+
+fn parse_interval(value: &str) -> Result<VestingInterval, String> {
+    match value {
+        "weekly" => Ok(VestingInterval::Weekly),
+        "bi-weekly" | "biweekly" => Ok(VestingInterval::BiWeekly),
+        "monthly" => Ok(VestingInterval::Monthly),
+        "quarterly" => Ok(VestingInterval::Quarterly),
+        "annual" | "yearly" => Ok(VestingInterval::Annual),
+        _ => Err(format!("unknown interval \"{}\"", value)),
+    }
+}
+
+fn load_grant(path: &str) -> Result<Grant, String> {
+    let source =
+        fs::read_to_string(path).map_err(|error| format!("could not read {}: {}", path, error))?;
+
+    let grant: ImportedGrant = if path.ends_with(".toml") {
+        toml::from_str(&source).map_err(|error| format!("invalid grant in {}: {}", path, error))?
+    } else {
+        serde_json::from_str(&source)
+            .map_err(|error| format!("invalid grant in {}: {}", path, error))?
+    };
+
+    let grant = grant.into_grant();
+    grant
+        .validate()
+        .map_err(|error| format!("invalid grant in {}: {}", path, error))?;
+
+    Ok(grant)
+}
+
+/// Reads the grants and command from the arguments, without the program name.
+fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<(Vec<Grant>, Command), String> {
+    let mut args = args.into_iter();
+    let mut builder = Grant::builder();
+    let mut files = Vec::new();
+    let mut termination_date: Option<LocalDate> = None;
+    let mut command = None;
+    // First flag setting a grant term, which a grant read from a file would ignore.
+    let mut grant_flag = None;
+
+    while let Some(arg) = args.next() {
+        if grant_flag.is_none()
+            && matches!(
+                arg.as_str(),
+                "--amount"
+                    | "--grant-date"
+                    | "--commencement-date"
+                    | "--interval"
+                    | "--length"
+                    | "--cliff"
+                    | "--cliff-percentage"
+            )
+        {
+            grant_flag = Some(arg.clone());
+        }
+
+        match arg.as_str() {
+            "--file" => files.push(parse_value::<String>(&arg, args.next())?),
+            "--amount" => builder = builder.amount(parse_value(&arg, args.next())?),
+            "--grant-date" => builder = builder.grant_date(parse_value(&arg, args.next())?),
+            "--commencement-date" => {
+                builder = builder.vesting_commencement_date(parse_value(&arg, args.next())?)
+            }
+            "--interval" => {
+                let interval: String = parse_value(&arg, args.next())?;
+                builder = builder.interval(parse_interval(&interval)?);
+            }
+            "--length" => builder = builder.length_months(parse_value(&arg, args.next())?),
+            "--cliff" => builder = builder.cliff_months(parse_value(&arg, args.next())?),
+            "--cliff-percentage" => {
+                builder = builder.cliff_percentage(parse_value(&arg, args.next())?)
+            }
+            "--termination-date" => termination_date = Some(parse_value(&arg, args.next())?),
+            _ if command.is_some() => return Err(format!("unexpected argument \"{}\"", arg)),
+            "schedule" => command = Some(Command::Schedule),
+            "vested" => command = Some(Command::Vested(parse_value("vested", args.next())?)),
+            "describe" => command = Some(Command::Describe),
+            "csv" => command = Some(Command::Csv),
+            "ics" => command = Some(Command::Ics),
+            #[cfg(feature = "tui")]
+            "tui" => command = Some(Command::Tui),
+            _ => return Err(format!("unknown argument \"{}\"", arg)),
+        }
+    }
+
+    let mut grants = if files.is_empty() {
+        vec![builder.build().map_err(|error| error.to_string())?]
+    } else if let Some(grant_flag) = grant_flag {
+        return Err(format!("{} can't be combined with --file", grant_flag));
+    } else {
+        files
+            .iter()
+            .map(|path| load_grant(path))
+            .collect::<Result<_, _>>()?
+    };
+
+    if let Some(termination_date) = termination_date {
+        for grant in grants.iter_mut() {
+            grant.terminate(termination_date);
+        }
+    }
+
+    let command = command.unwrap_or(Command::Schedule);
+
+    #[cfg(feature = "tui")]
+    if command == Command::Tui {
+        return Ok((grants, command));
+    }
+
+    if grants.len() > 1 {
+        return Err("only the tui command takes more than one grant".to_string());
+    }
+
+    Ok((grants, command))
+}
+
+fn run(grants: &[Grant], command: Command) -> io::Result<()> {
+    let grant = &grants[0];
+    let vesting_schedule = grant.calculate_vesting_schedule();
+
+    match command {
+        Command::Schedule => print!("{}", vesting_schedule),
+        Command::Vested(date) => println!("{}", grant.calculate_vested_shares(date)),
+        Command::Describe => println!("{}", grant.describe_vesting()),
+        Command::Csv => vesting_schedule.to_csv(io::stdout().lock())?,
+        Command::Ics => print!("{}", vesting_schedule.to_ics()),
+        #[cfg(feature = "tui")]
+        Command::Tui => tui::run(grants, LocalDate::today_in(&chrono::Local))?,
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        print!("{}", USAGE);
+        return;
+    }
+
+    let (grants, command) = parse_args(args).unwrap_or_else(|error| {
+        eprintln!("error: {}\n\n{}", error, USAGE);
+        process::exit(2);
+    });
+
+    if let Err(error) = run(&grants, command) {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use super::{parse_args, Command, LocalDate};
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn it_can_parse_grants_from_flags() {
+        let (grants, command) = parse_args(args(
+            "--amount 4800 --grant-date 2021-01-15 --length 48 --cliff 12 \
+             --cliff-percentage 0.25 vested 2022-02-15",
+        ))
+        .unwrap();
+
+        assert_eq!(command, Command::Vested(LocalDate::from_ymd(2022, 2, 15)));
+        assert_eq!(
+            grants[0].calculate_vested_shares(LocalDate::from_ymd(2022, 2, 15)),
+            1_300
+        );
+
+        assert_eq!(
+            parse_args(args("--grant-date 2021-01-15 --length 48")).err(),
+            Some("missing required grant term amount".to_string())
+        );
+        assert!(parse_args(args("--amount 100 --length 12 csv ics")).is_err());
+    }
+
+    #[test]
+    fn it_validates_grants_read_from_files() {
+        let path = env::temp_dir().join(format!(
+            "vested-negative-length-{}.json",
+            std::process::id()
+        ));
+        fs::write(
+            &path,
+            r#"{
+                "amount": 4800,
+                "grant_date": "2021-01-15",
+                "vesting_schedule": {
+                    "interval": "monthly",
+                    "cliff_percentage": 0.0,
+                    "cliff": 0,
+                    "length": -3
+                }
+            }"#,
+        )
+        .unwrap();
+        let path = path.to_str().unwrap().to_string();
+
+        let error = parse_args(vec!["--file".to_string(), path.clone()])
+            .err()
+            .unwrap();
+
+        assert!(error.starts_with(&format!(
+            "invalid grant in {}: vesting length must not be negative, got -3",
+            path
+        )));
+        assert_eq!(
+            parse_args(vec![
+                "--file".to_string(),
+                path.clone(),
+                "--amount".to_string(),
+                "100".to_string()
+            ])
+            .err(),
+            Some("--amount can't be combined with --file".to_string())
+        );
+
+        fs::remove_file(path).unwrap();
+    }
+}
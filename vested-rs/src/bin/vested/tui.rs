@@ -0,0 +1,252 @@
+use std::io;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Block, Gauge, List, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use vested::{Grant, LocalDate};
+
+/// Vests shown in the upcoming list.
+const UPCOMING_VESTS: usize = 5;
+
+/// Shares from one grant vesting on one date.
+struct TimelineRow {
+    date: LocalDate,
+    grant_index: usize,
+    vested_this_period: i64,
+    cumulative_vested_amount: i64,
+}
+
+struct App {
+    today: LocalDate,
+    rows: Vec<TimelineRow>,
+    vested_amount: i64,
+    total_amount: i64,
+    table_state: TableState,
+}
+
+impl App {
+    fn new(grants: &[Grant], today: LocalDate) -> App {
+        let mut rows = Vec::new();
+        let mut vested_amount = 0;
+        let mut total_amount = 0;
+
+        for (grant_index, grant) in grants.iter().enumerate() {
+            let vesting_schedule = grant.calculate_vesting_schedule();
+
+            vested_amount += grant.calculate_vested_shares(today);
+            total_amount += vesting_schedule
+                .periods
+                .last()
+                .map_or(0, |period| period.cumulative_vested_amount)
+                + vesting_schedule.forfeited_amount;
+
+            rows.extend(
+                vesting_schedule
+                    .periods
+                    .into_iter()
+                    .filter(|period| period.vested_this_period > 0)
+                    .map(|period| TimelineRow {
+                        date: period.date,
+                        grant_index,
+                        vested_this_period: period.vested_this_period,
+                        cumulative_vested_amount: period.cumulative_vested_amount,
+                    }),
+            );
+        }
+
+        rows.sort_by_key(|row| (row.date, row.grant_index));
+
+        // Start scrolled to the first vest from today on.
+        let next_row = rows
+            .iter()
+            .position(|row| row.date >= today)
+            .unwrap_or(rows.len().saturating_sub(1));
+
+        App {
+            today,
+            rows,
+            vested_amount,
+            total_amount,
+            table_state: TableState::default().with_selected(next_row),
+        }
+    }
+
+    fn ratio_vested(&self) -> f64 {
+        if self.total_amount == 0 {
+            return 0.0;
+        }
+
+        self.vested_amount as f64 / self.total_amount as f64
+    }
+
+    fn upcoming(&self) -> impl Iterator<Item = &TimelineRow> {
+        self.rows
+            .iter()
+            .filter(|row| row.date >= self.today)
+            .take(UPCOMING_VESTS)
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [gauge_area, body_area, help_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [timeline_area, upcoming_area] =
+            Layout::horizontal([Constraint::Percentage(65), Constraint::Percentage(35)])
+                .areas(body_area);
+
+        frame.render_widget(
+            Gauge::default()
+                .block(Block::bordered().title(format!("Vested as of {}", self.today)))
+                .ratio(self.ratio_vested())
+                .label(format!(
+                    "{} of {} shares ({:.1}%)",
+                    self.vested_amount,
+                    self.total_amount,
+                    self.ratio_vested() * 100.0
+                )),
+            gauge_area,
+        );
+
+        let rows = self.rows.iter().map(|row| {
+            let style = if row.date < self.today {
+                Style::default().add_modifier(Modifier::DIM)
+            } else {
+                Style::default()
+            };
+
+            Row::new(vec![
+                row.date.to_string(),
+                format!("#{}", row.grant_index + 1),
+                row.vested_this_period.to_string(),
+                row.cumulative_vested_amount.to_string(),
+            ])
+            .style(style)
+        });
+        let timeline = Table::new(
+            rows,
+            [
+                Constraint::Length(10),
+                Constraint::Length(5),
+                Constraint::Length(10),
+                Constraint::Length(10),
+            ],
+        )
+        .header(
+            Row::new(vec!["Date", "Grant", "Vesting", "Cumulative"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .block(Block::bordered().title("Timeline"));
+
+        frame.render_stateful_widget(timeline, timeline_area, &mut self.table_state);
+
+        let upcoming: Vec<String> = self
+            .upcoming()
+            .map(|row| {
+                format!(
+                    "{}  {} shares (#{})",
+                    row.date,
+                    row.vested_this_period,
+                    row.grant_index + 1
+                )
+            })
+            .collect();
+
+        frame.render_widget(
+            List::new(upcoming).block(Block::bordered().title("Upcoming")),
+            upcoming_area,
+        );
+        frame.render_widget("↑/↓ scroll  PgUp/PgDn page  q quit", help_area);
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Down | KeyCode::Char('j') => self.table_state.scroll_down_by(1),
+                KeyCode::Up | KeyCode::Char('k') => self.table_state.scroll_up_by(1),
+                KeyCode::PageDown => self.table_state.scroll_down_by(10),
+                KeyCode::PageUp => self.table_state.scroll_up_by(10),
+                KeyCode::Home => self.table_state.select_first(),
+                KeyCode::End => self.table_state.select_last(),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Shows the grants' vesting timeline until the user quits.
+pub fn run(grants: &[Grant], today: LocalDate) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = App::new(grants, today).run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use vested::{Grant, LocalDate};
+
+    use super::App;
+
+    #[test]
+    fn it_can_render_the_timeline_of_several_grants() {
+        let grants = [
+            Grant::builder()
+                .amount(4_800)
+                .grant_date(LocalDate::from_ymd(2021, 1, 15))
+                .length_months(48)
+                .cliff_months(12)
+                .cliff_percentage(0.25)
+                .build()
+                .unwrap(),
+            Grant::builder()
+                .amount(1_200)
+                .grant_date(LocalDate::from_ymd(2022, 1, 20))
+                .length_months(12)
+                .build()
+                .unwrap(),
+        ];
+        let mut app = App::new(&grants, LocalDate::from_ymd(2022, 1, 18));
+
+        assert_eq!(app.vested_amount, 1_200);
+        assert_eq!(app.total_amount, 6_000);
+        assert_eq!(
+            app.upcoming().map(|row| row.date).collect::<Vec<_>>()[..2],
+            [
+                LocalDate::from_ymd(2022, 2, 15),
+                LocalDate::from_ymd(2022, 2, 20),
+            ]
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 12)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+
+        assert!(screen.contains("1200 of 6000 shares (20.0%)"));
+        assert!(screen.contains("2022-02-15  100 shares (#1)"));
+    }
+}
@@ -0,0 +1,337 @@
+use crate::{
+    CalculationVersion, CliffSpec, Grant, GrantType, LocalDate, MonthEndPolicy, ProrationPolicy,
+    RoundingStrategy, StubPolicy, VestingDuration, VestingError, VestingInterval,
+    VestingScheduleConfiguration,
+};
+
+/// Builds a validated `Grant` one term at a time.
+///
+/// The length is given in months and the cliff as a calendar duration, both converted
+/// to whole vesting intervals, so a quarterly schedule needs them to be multiples of
+/// three months unless a stub policy allows a shorter first or last interval.
+#[derive(Default)]
+pub struct GrantBuilder {
+    amount: Option<i64>,
+    grant_date: Option<LocalDate>,
+    vesting_commencement_date: Option<LocalDate>,
+    interval: Option<VestingInterval>,
+    cliff: CliffSpec,
+    cliff_percentage: f32,
+    length_months: Option<i32>,
+    calculation_version: CalculationVersion,
+    rounding_strategy: RoundingStrategy,
+    month_end_policy: MonthEndPolicy,
+    prorate_daily: bool,
+    stub_policy: Option<StubPolicy>,
+    vesting_schedule: Option<VestingScheduleConfiguration>,
+    grant_type: GrantType,
+}
+
+impl Grant {
+    pub fn builder() -> GrantBuilder {
+        GrantBuilder::default()
+    }
+}
+
+impl GrantBuilder {
+    pub fn amount(mut self, amount: i64) -> Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    pub fn grant_date(mut self, grant_date: LocalDate) -> Self {
+        self.grant_date = Some(grant_date);
+        self
+    }
+
+    pub fn vesting_commencement_date(mut self, vesting_commencement_date: LocalDate) -> Self {
+        self.vesting_commencement_date = Some(vesting_commencement_date);
+        self
+    }
+
+    pub fn interval(mut self, interval: VestingInterval) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    pub fn weekly(self) -> Self {
+        self.interval(VestingInterval::Weekly)
+    }
+
+    pub fn bi_weekly(self) -> Self {
+        self.interval(VestingInterval::BiWeekly)
+    }
+
+    pub fn monthly(self) -> Self {
+        self.interval(VestingInterval::Monthly)
+    }
+
+    pub fn quarterly(self) -> Self {
+        self.interval(VestingInterval::Quarterly)
+    }
+
+    pub fn annually(self) -> Self {
+        self.interval(VestingInterval::Annual)
+    }
+
+    pub fn cliff(mut self, cliff: CliffSpec) -> Self {
+        self.cliff = cliff;
+        self
+    }
+
+    pub fn cliff_months(self, cliff_months: i32) -> Self {
+        self.cliff(CliffSpec::Months(cliff_months))
+    }
+
+    pub fn cliff_percentage(mut self, cliff_percentage: f32) -> Self {
+        self.cliff_percentage = cliff_percentage;
+        self
+    }
+
+    pub fn length_months(mut self, length_months: i32) -> Self {
+        self.length_months = Some(length_months);
+        self
+    }
+
+    pub fn calculation_version(mut self, calculation_version: CalculationVersion) -> Self {
+        self.calculation_version = calculation_version;
+        self
+    }
+
+    pub fn rounding_strategy(mut self, rounding_strategy: RoundingStrategy) -> Self {
+        self.rounding_strategy = rounding_strategy;
+        self
+    }
+
+    pub fn month_end_policy(mut self, month_end_policy: MonthEndPolicy) -> Self {
+        self.month_end_policy = month_end_policy;
+        self
+    }
+
+    /// Vests continuously by day after the cliff instead of only on vesting dates.
+    pub fn prorate_daily(mut self, prorate_daily: bool) -> Self {
+        self.prorate_daily = prorate_daily;
+        self
+    }
+
+    /// Allows a length that isn't a whole number of intervals, vesting the remainder in
+    /// a shorter first or last interval.
+    pub fn stub_policy(mut self, stub_policy: StubPolicy) -> Self {
+        self.stub_policy = Some(stub_policy);
+        self
+    }
+
+    pub fn grant_type(mut self, grant_type: GrantType) -> Self {
+        self.grant_type = grant_type;
+        self
+    }
+
+    /// Uses an already configured schedule, such as a weighted one, instead of the
+    /// interval, cliff and length set on the builder.
+    pub fn vesting_schedule(mut self, vesting_schedule: VestingScheduleConfiguration) -> Self {
+        self.vesting_schedule = Some(vesting_schedule);
+        self
+    }
+
+    /// Builds the grant, failing if a required term is missing or the terms don't
+    /// produce a meaningful schedule.
+    pub fn build(self) -> Result<Grant, VestingError> {
+        let vesting_schedule = match self.vesting_schedule {
+            Some(vesting_schedule) => vesting_schedule,
+            None => {
+                let interval = self.interval.unwrap_or(VestingInterval::Monthly);
+                let length_months = self
+                    .length_months
+                    .ok_or(VestingError::MissingTerm("length_months"))?;
+
+                let vesting_schedule = match self.stub_policy {
+                    Some(stub_policy) => VestingScheduleConfiguration::without_cliff(interval, 0)
+                        .with_stub(length_months, stub_policy)?,
+                    None => VestingScheduleConfiguration::from_durations(
+                        interval,
+                        VestingDuration::Months(0),
+                        0.0,
+                        VestingDuration::Months(length_months),
+                        ProrationPolicy::Strict,
+                    )?,
+                };
+
+                vesting_schedule
+                    .with_cliff(self.cliff, self.cliff_percentage, ProrationPolicy::Strict)?
+                    .with_calculation_version(self.calculation_version)
+                    .with_rounding_strategy(self.rounding_strategy)
+                    .with_month_end_policy(self.month_end_policy)
+                    .with_daily_proration(self.prorate_daily)
+            }
+        };
+
+        let grant = Grant {
+            amount: self.amount.ok_or(VestingError::MissingTerm("amount"))?,
+            grant_date: self
+                .grant_date
+                .ok_or(VestingError::MissingTerm("grant_date"))?,
+            vesting_schedule,
+            termination_date: None,
+            vesting_commencement_date: self.vesting_commencement_date,
+            grant_type: self.grant_type,
+        };
+
+        grant.validate()?;
+
+        Ok(grant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use crate::{Grant, LocalDate, StubPolicy, VestingDuration, VestingError};
+
+    #[test]
+    fn it_can_build_a_grant() {
+        let grant = Grant::builder()
+            .amount(10_000)
+            .grant_date(LocalDate::from_ymd(2020, 2, 6))
+            .monthly()
+            .cliff_months(12)
+            .cliff_percentage(0.25)
+            .length_months(48)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            grant.calculate_vested_amount(LocalDate::from_ymd(2021, 2, 6)),
+            2500.0
+        );
+
+        let quarterly = Grant::builder()
+            .amount(10_000)
+            .grant_date(LocalDate::from_ymd(2020, 2, 6))
+            .quarterly()
+            .cliff_months(12)
+            .cliff_percentage(0.25)
+            .length_months(48)
+            .build()
+            .unwrap();
+
+        assert_eq!(quarterly.calculate_vesting_schedule().periods.len(), 17);
+
+        assert_eq!(
+            Grant::builder()
+                .grant_date(LocalDate::from_ymd(2020, 2, 6))
+                .length_months(48)
+                .build()
+                .err(),
+            Some(VestingError::MissingTerm("amount"))
+        );
+        assert_eq!(
+            Grant::builder()
+                .amount(10_000)
+                .grant_date(LocalDate::from_ymd(2020, 2, 6))
+                .cliff_months(60)
+                .length_months(48)
+                .build()
+                .err(),
+            Some(VestingError::CliffExceedsLength {
+                cliff: 60,
+                length: 48
+            })
+        );
+        assert_eq!(
+            Grant::builder()
+                .amount(10_000)
+                .grant_date(LocalDate::from_ymd(2020, 2, 6))
+                .quarterly()
+                .length_months(50)
+                .build()
+                .err(),
+            Some(VestingError::UnevenDuration(VestingDuration::Months(50)))
+        );
+    }
+
+    #[test]
+    fn it_can_build_a_quarterly_grant_with_a_stub() {
+        let builder = || {
+            Grant::builder()
+                .amount(5_000)
+                .grant_date(LocalDate::from_ymd(2020, 1, 1))
+                .quarterly()
+                .length_months(50)
+        };
+
+        let trailing = builder().stub_policy(StubPolicy::Trailing).build().unwrap();
+        let schedule = trailing.calculate_vesting_schedule();
+
+        assert_eq!(schedule.periods.len(), 18);
+        assert_eq!(schedule.periods[1].date, LocalDate::from_ymd(2020, 4, 1));
+        assert_eq!(schedule.periods[1].vested_this_period, 300);
+        assert_eq!(schedule.periods[16].date, LocalDate::from_ymd(2024, 1, 1));
+        assert_eq!(schedule.periods[17].date, LocalDate::from_ymd(2024, 3, 1));
+        assert_eq!(schedule.periods[17].vested_this_period, 200);
+        assert_eq!(trailing.vesting_schedule.total_vesting_months(), 50);
+        assert_eq!(
+            trailing.calculate_vested_shares(LocalDate::from_ymd(2024, 2, 15)),
+            4_800
+        );
+        assert_eq!(
+            trailing.calculate_vested_shares(LocalDate::from_ymd(2024, 3, 1)),
+            5_000
+        );
+
+        let leading = builder()
+            .stub_policy(StubPolicy::Leading)
+            .cliff_months(14)
+            .cliff_percentage(0.28)
+            .build()
+            .unwrap();
+        let schedule = leading.calculate_vesting_schedule();
+
+        assert_eq!(schedule.periods.len(), 18);
+        assert_eq!(schedule.periods[5].date, LocalDate::from_ymd(2021, 3, 1));
+        assert_eq!(schedule.periods[5].cumulative_vested_amount, 1_400);
+        assert_eq!(schedule.periods[6].date, LocalDate::from_ymd(2021, 6, 1));
+        assert_eq!(schedule.periods[6].vested_this_period, 300);
+        assert_eq!(schedule.periods[17].date, LocalDate::from_ymd(2024, 3, 1));
+        assert_eq!(schedule.periods[17].cumulative_vested_amount, 5_000);
+        assert_eq!(leading.vesting_schedule.cliff_months(), 14);
+
+        assert_eq!(
+            builder()
+                .stub_policy(StubPolicy::Leading)
+                .cliff_months(12)
+                .build()
+                .err(),
+            Some(VestingError::UnevenDuration(VestingDuration::Months(12)))
+        );
+    }
+
+    #[test]
+    fn it_can_build_a_grant_vesting_daily() {
+        let builder = || {
+            Grant::builder()
+                .amount(1_200)
+                .grant_date(LocalDate::from_ymd(2021, 1, 1))
+                .length_months(12)
+        };
+        let date = LocalDate::from_ymd(2021, 1, 16);
+
+        /*
+         * 15 of January's 31 days have elapsed, so 15/31 of the first month's 100
+         * shares have vested.
+         */
+
+        let daily = builder().prorate_daily(true).build().unwrap();
+
+        assert_relative_eq!(
+            daily.calculate_vested_amount(date),
+            100.0 * 15.0 / 31.0,
+            epsilon = 0.001
+        );
+        assert_eq!(daily.calculate_vested_shares(date), 0);
+
+        let monthly = builder().prorate_daily(false).build().unwrap();
+
+        assert_eq!(monthly.calculate_vested_amount(date), 0.0);
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::BTreeSet;
+
+use crate::{Grant, LocalDate};
+
+/// Shares still unvested on a given date.
+#[derive(Debug, PartialEq, PartialOrd)]
+pub struct UnvestedBalance {
+    pub date: LocalDate,
+    pub unvested_amount: i64,
+}
+
+impl Grant {
+    /// Calculates the amount still unvested on a given date. Nothing is unvested
+    /// before the grant has been made, or once it has been terminated.
+    pub fn calculate_unvested_amount(&self, date: LocalDate) -> i64 {
+        if date < self.grant_date || self.is_terminated_on(date) {
+            return 0;
+        }
+
+        self.amount - self.calculate_vested_shares(date)
+    }
+
+    /// Calculates the unvested balance on each vesting period, burning down to zero.
+    pub fn calculate_unvested_balances(&self) -> Vec<UnvestedBalance> {
+        self.calculate_vesting_schedule()
+            .periods
+            .into_iter()
+            .map(|period| UnvestedBalance {
+                date: period.date,
+                unvested_amount: self.amount - period.cumulative_vested_amount,
+            })
+            .collect()
+    }
+}
+
+/// Calculates the combined unvested balance of all grants on every date any of
+/// them has a vesting period.
+pub fn aggregate_unvested_balances(grants: &[Grant]) -> Vec<UnvestedBalance> {
+    let dates: BTreeSet<LocalDate> = grants
+        .iter()
+        .flat_map(|grant| grant.calculate_vesting_schedule().periods)
+        .map(|period| period.date)
+        .collect();
+
+    dates
+        .into_iter()
+        .map(|date| UnvestedBalance {
+            date,
+            unvested_amount: grants
+                .iter()
+                .map(|grant| grant.calculate_unvested_amount(date))
+                .sum(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{aggregate_unvested_balances, LocalDate, UnvestedBalance};
+    use crate::{Grant, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_calculate_unvested_burn_down() {
+        let grants = vec![
+            Grant::for_test(
+                1_200,
+                LocalDate::from_ymd(2020, 1, 15),
+                VestingScheduleConfiguration::monthly(0, 0.0, 3),
+            ),
+            Grant::for_test(
+                400,
+                LocalDate::from_ymd(2020, 2, 15),
+                VestingScheduleConfiguration::monthly(0, 0.0, 2),
+            ),
+        ];
+
+        assert_eq!(
+            grants[0].calculate_unvested_balances(),
+            vec![
+                UnvestedBalance {
+                    date: LocalDate::from_ymd(2020, 1, 15),
+                    unvested_amount: 1_200,
+                },
+                UnvestedBalance {
+                    date: LocalDate::from_ymd(2020, 2, 15),
+                    unvested_amount: 800,
+                },
+                UnvestedBalance {
+                    date: LocalDate::from_ymd(2020, 3, 15),
+                    unvested_amount: 400,
+                },
+                UnvestedBalance {
+                    date: LocalDate::from_ymd(2020, 4, 15),
+                    unvested_amount: 0,
+                },
+            ]
+        );
+
+        let balances: Vec<i64> = aggregate_unvested_balances(&grants)
+            .into_iter()
+            .map(|balance| balance.unvested_amount)
+            .collect();
+
+        assert_eq!(balances, vec![1_200, 1_200, 600, 0]);
+    }
+}
@@ -0,0 +1,262 @@
+use std::error::Error;
+use std::fmt;
+
+use chrono::{Datelike, Duration, Weekday};
+
+use crate::{Grant, LocalDate, SaleRestrictions, VestingSchedule};
+
+/// Days searched for a business day before giving up on a calendar that may have none,
+/// e.g. one listing every day as a holiday.
+const MAX_ROLL_DAYS: i64 = 366;
+
+/// Days a market or payroll system is closed, for moving vesting dates onto days
+/// shares can actually be delivered.
+pub trait HolidayCalendar {
+    /// Checks if the date is a holiday. Weekends are handled separately.
+    fn is_holiday(&self, date: LocalDate) -> bool;
+
+    /// Checks if the date is neither a weekend nor a holiday.
+    fn is_business_day(&self, date: LocalDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !self.is_holiday(date)
+    }
+}
+
+/// A calendar closed only on weekends.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WeekendCalendar;
+
+/// A calendar closed on weekends and on a fixed list of holidays.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HolidayList {
+    holidays: Vec<LocalDate>,
+}
+
+/// How a vesting date that isn't a business day is moved.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BusinessDayConvention {
+    /// Leaves the date as is.
+    #[default]
+    Unadjusted,
+    /// Moves to the next business day.
+    Following,
+    /// Moves to the previous business day.
+    Preceding,
+    /// Moves to the next business day, unless that's in the next month, in which case
+    /// it moves to the previous one.
+    ModifiedFollowing,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BusinessDayError {
+    /// The calendar has no business day within a year of the date.
+    NoBusinessDay(LocalDate),
+}
+
+impl fmt::Display for BusinessDayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BusinessDayError::NoBusinessDay(date) => {
+                write!(f, "no business day within a year of {}", date)
+            }
+        }
+    }
+}
+
+impl Error for BusinessDayError {}
+
+impl HolidayCalendar for WeekendCalendar {
+    fn is_holiday(&self, _date: LocalDate) -> bool {
+        false
+    }
+}
+
+impl HolidayList {
+    pub fn new(mut holidays: Vec<LocalDate>) -> HolidayList {
+        holidays.sort();
+        HolidayList { holidays }
+    }
+}
+
+impl HolidayCalendar for HolidayList {
+    fn is_holiday(&self, date: LocalDate) -> bool {
+        self.holidays.binary_search(&date).is_ok()
+    }
+}
+
+impl HolidayCalendar for SaleRestrictions {
+    fn is_holiday(&self, date: LocalDate) -> bool {
+        self.holidays.contains(&date)
+    }
+}
+
+/// Steps a day at a time in the given direction until reaching a business day, or
+/// returns `None` if there isn't one within `MAX_ROLL_DAYS`.
+fn roll<C: HolidayCalendar + ?Sized>(
+    date: LocalDate,
+    calendar: &C,
+    days: i64,
+) -> Option<LocalDate> {
+    (0..=MAX_ROLL_DAYS)
+        .map(|step| date + Duration::days(step * days))
+        .find(|&date| calendar.is_business_day(date))
+}
+
+impl BusinessDayConvention {
+    /// Moves the date onto a business day of the calendar under the convention, failing
+    /// if the calendar has no business day within a year of it.
+    pub fn adjust<C: HolidayCalendar + ?Sized>(
+        self,
+        date: LocalDate,
+        calendar: &C,
+    ) -> Result<LocalDate, BusinessDayError> {
+        let adjusted_date = match self {
+            BusinessDayConvention::Unadjusted => Some(date),
+            BusinessDayConvention::Following => roll(date, calendar, 1),
+            BusinessDayConvention::Preceding => roll(date, calendar, -1),
+            BusinessDayConvention::ModifiedFollowing => match roll(date, calendar, 1) {
+                Some(following_date) if following_date.month() == date.month() => {
+                    Some(following_date)
+                }
+                _ => roll(date, calendar, -1),
+            },
+        };
+
+        adjusted_date.ok_or(BusinessDayError::NoBusinessDay(date))
+    }
+}
+
+impl VestingSchedule {
+    /// Moves each period onto a business day of the calendar under the convention.
+    pub fn adjust_to_business_days<C: HolidayCalendar + ?Sized>(
+        &mut self,
+        calendar: &C,
+        convention: BusinessDayConvention,
+    ) -> Result<(), BusinessDayError> {
+        for period in self.periods.iter_mut() {
+            period.date = convention.adjust(period.date, calendar)?;
+        }
+
+        self.to_date = convention.adjust(self.to_date, calendar)?;
+
+        Ok(())
+    }
+}
+
+impl Grant {
+    /// Calculates the vesting schedule with vesting dates moved onto business days of
+    /// the calendar, e.g. for shares delivered through a broker. Use
+    /// `VestingSchedule::vested_amount_on` to look up the amount vested on a date.
+    pub fn calculate_business_day_schedule<C: HolidayCalendar + ?Sized>(
+        &self,
+        calendar: &C,
+        convention: BusinessDayConvention,
+    ) -> Result<VestingSchedule, BusinessDayError> {
+        let mut vesting_schedule = self.calculate_vesting_schedule();
+        vesting_schedule.adjust_to_business_days(calendar, convention)?;
+        Ok(vesting_schedule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BusinessDayConvention, BusinessDayError, HolidayCalendar, HolidayList, LocalDate,
+        WeekendCalendar,
+    };
+    use crate::{Grant, Interpolation, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_move_vesting_dates_onto_business_days() {
+        let grant = Grant::for_test(
+            300,
+            LocalDate::from_ymd(2022, 6, 4),
+            VestingScheduleConfiguration::monthly(0, 0.0, 3),
+        );
+
+        /*
+         * - 2022/7/4: Independence Day, a Monday
+         * - 2022/9/4: a Sunday before Labor Day
+         */
+
+        let holidays = HolidayList::new(vec![
+            LocalDate::from_ymd(2022, 9, 5),
+            LocalDate::from_ymd(2022, 7, 4),
+        ]);
+        let dates = |convention| {
+            grant
+                .calculate_business_day_schedule(&holidays, convention)
+                .unwrap()
+                .periods
+                .into_iter()
+                .skip(1)
+                .map(|period| period.date)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            dates(BusinessDayConvention::Following),
+            vec![
+                LocalDate::from_ymd(2022, 7, 5),
+                LocalDate::from_ymd(2022, 8, 4),
+                LocalDate::from_ymd(2022, 9, 6),
+            ]
+        );
+        assert_eq!(
+            dates(BusinessDayConvention::Preceding),
+            vec![
+                LocalDate::from_ymd(2022, 7, 1),
+                LocalDate::from_ymd(2022, 8, 4),
+                LocalDate::from_ymd(2022, 9, 2),
+            ]
+        );
+
+        /*
+         * 2022/4/30 is a Saturday and the following Monday is in May.
+         */
+
+        assert_eq!(
+            BusinessDayConvention::ModifiedFollowing
+                .adjust(LocalDate::from_ymd(2022, 4, 30), &WeekendCalendar),
+            Ok(LocalDate::from_ymd(2022, 4, 29))
+        );
+
+        let schedule = grant
+            .calculate_business_day_schedule(&holidays, BusinessDayConvention::Following)
+            .unwrap();
+
+        assert_eq!(
+            schedule.vested_amount_on(LocalDate::from_ymd(2022, 7, 4), Interpolation::Step),
+            0.0
+        );
+        assert_eq!(
+            schedule.vested_amount_on(LocalDate::from_ymd(2022, 7, 5), Interpolation::Step),
+            100.0
+        );
+    }
+
+    #[test]
+    fn it_gives_up_on_calendars_without_business_days() {
+        struct ClosedCalendar;
+
+        impl HolidayCalendar for ClosedCalendar {
+            fn is_holiday(&self, _date: LocalDate) -> bool {
+                true
+            }
+        }
+
+        let date = LocalDate::from_ymd(2022, 6, 4);
+
+        assert_eq!(
+            BusinessDayConvention::Following.adjust(date, &ClosedCalendar),
+            Err(BusinessDayError::NoBusinessDay(date))
+        );
+        assert_eq!(
+            BusinessDayConvention::ModifiedFollowing.adjust(date, &ClosedCalendar),
+            Err(BusinessDayError::NoBusinessDay(date))
+        );
+        assert_eq!(
+            BusinessDayConvention::Unadjusted.adjust(date, &ClosedCalendar),
+            Ok(date)
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{Grant, LocalDate};
+
+/// Fully diluted share counts that hold from their snapshot date until the next one.
+#[derive(Debug, Default, PartialEq)]
+pub struct CapTable {
+    snapshots: Vec<(LocalDate, i64)>,
+}
+
+impl CapTable {
+    pub fn new(mut snapshots: Vec<(LocalDate, i64)>) -> CapTable {
+        snapshots.sort_by_key(|(date, _)| *date);
+        CapTable { snapshots }
+    }
+
+    /// Returns the fully diluted share count on the given date, if known.
+    pub fn fully_diluted_shares_on(&self, date: LocalDate) -> Option<i64> {
+        self.snapshots
+            .iter()
+            .take_while(|(snapshot_date, _)| *snapshot_date <= date)
+            .last()
+            .map(|(_, shares)| *shares)
+    }
+
+    /// Converts a percentage of the fully diluted shares on the given date, e.g. 0.001
+    /// for 0.1%, into a share count rounded to the nearest share.
+    pub fn shares_for_percentage(&self, percentage: f64, date: LocalDate) -> Option<i64> {
+        let fully_diluted_shares = self.fully_diluted_shares_on(date)?;
+
+        Some((fully_diluted_shares as f64 * percentage).round() as i64)
+    }
+
+    /// Converts a share count into a percentage of the fully diluted shares on the
+    /// given date.
+    pub fn percentage_for_shares(&self, shares: i64, date: LocalDate) -> Option<f64> {
+        match self.fully_diluted_shares_on(date)? {
+            0 => None,
+            fully_diluted_shares => Some(shares as f64 / fully_diluted_shares as f64),
+        }
+    }
+}
+
+impl Grant {
+    /// Calculates the percentage of the company the grant was for on its grant date.
+    pub fn percentage_of_company(&self, cap_table: &CapTable) -> Option<f64> {
+        cap_table.percentage_for_shares(self.amount, self.grant_date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CapTable, LocalDate};
+    use crate::Grant;
+
+    #[test]
+    fn it_can_size_grants_as_a_percentage_of_the_company() {
+        let cap_table = CapTable::new(vec![
+            (LocalDate::from_ymd(2022, 6, 1), 12_500_000),
+            (LocalDate::from_ymd(2021, 1, 1), 10_000_000),
+        ]);
+        let grant_date = LocalDate::from_ymd(2021, 3, 1);
+
+        assert_eq!(
+            cap_table.shares_for_percentage(0.001, LocalDate::from_ymd(2020, 12, 31)),
+            None
+        );
+
+        let grant = Grant::builder()
+            .amount(cap_table.shares_for_percentage(0.001, grant_date).unwrap())
+            .grant_date(grant_date)
+            .monthly()
+            .cliff_months(12)
+            .cliff_percentage(0.25)
+            .length_months(48)
+            .build()
+            .unwrap();
+
+        assert_eq!(grant.amount, 10_000);
+        assert_eq!(grant.percentage_of_company(&cap_table), Some(0.001));
+        assert_eq!(
+            cap_table.percentage_for_shares(10_000, LocalDate::from_ymd(2022, 6, 1)),
+            Some(0.0008)
+        );
+    }
+}
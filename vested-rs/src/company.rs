@@ -0,0 +1,165 @@
+use crate::{EquityPlan, Grant, LocalDate, PlanError};
+
+/// A company running several equity plans, e.g. a pre-IPO plan and the plan that
+/// replaced it, each issuing grants out of its own reserve.
+pub struct Company {
+    pub name: String,
+    pub plans: Vec<EquityPlan>,
+}
+
+/// One plan's share of a company's equity on a given date.
+#[derive(Debug, PartialEq)]
+pub struct PlanBreakdown {
+    pub plan_name: String,
+    pub share_reserve: i64,
+    /// Shares granted under the plan, including ones since forfeited.
+    pub granted_amount: i64,
+    pub vested_amount: i64,
+    pub unvested_amount: i64,
+    pub remaining_reserve: i64,
+}
+
+impl Company {
+    pub fn new(name: &str, plans: Vec<EquityPlan>) -> Company {
+        Company {
+            name: name.to_string(),
+            plans,
+        }
+    }
+
+    pub fn plan(&self, plan_name: &str) -> Option<&EquityPlan> {
+        self.plans.iter().find(|plan| plan.name == plan_name)
+    }
+
+    fn plan_mut(&mut self, plan_name: &str) -> Result<&mut EquityPlan, PlanError> {
+        self.plans
+            .iter_mut()
+            .find(|plan| plan.name == plan_name)
+            .ok_or_else(|| PlanError::UnknownPlan(plan_name.to_string()))
+    }
+
+    /// Issues the grant out of the named plan's reserve, returning its index among
+    /// that plan's issued grants.
+    pub fn issue(&mut self, plan_name: &str, grant: Grant) -> Result<usize, PlanError> {
+        self.plan_mut(plan_name)?.issue(grant)
+    }
+
+    /// Moves unissued shares from one plan's reserve to another's, e.g. rolling what's
+    /// left of a retired plan into its successor.
+    pub fn transfer_reserve(
+        &mut self,
+        from_plan: &str,
+        to_plan: &str,
+        shares: i64,
+    ) -> Result<(), PlanError> {
+        // Looks up the destination first so a bad name doesn't strand the shares.
+        self.plan_mut(to_plan)?;
+
+        let from = self.plan_mut(from_plan)?;
+        let remaining = from.remaining_reserve();
+
+        if shares > remaining {
+            return Err(PlanError::InsufficientReserve {
+                requested: shares,
+                remaining,
+            });
+        }
+
+        from.share_reserve -= shares;
+        self.plan_mut(to_plan)?.share_reserve += shares;
+
+        Ok(())
+    }
+
+    /// Splits the company's granted, vested and unvested shares on the given date by
+    /// plan, in the order the plans were added.
+    pub fn breakdown_on(&self, date: LocalDate) -> Vec<PlanBreakdown> {
+        self.plans
+            .iter()
+            .map(|plan| {
+                let grants = plan.issued_grants();
+
+                PlanBreakdown {
+                    plan_name: plan.name.clone(),
+                    share_reserve: plan.share_reserve,
+                    granted_amount: grants.iter().map(|grant| grant.amount).sum(),
+                    vested_amount: grants
+                        .iter()
+                        .map(|grant| grant.calculate_vested_shares(date))
+                        .sum(),
+                    unvested_amount: grants
+                        .iter()
+                        .map(|grant| grant.calculate_unvested_amount(date))
+                        .sum(),
+                    remaining_reserve: plan.remaining_reserve(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Company, LocalDate, PlanBreakdown};
+    use crate::{EquityPlan, Grant, GrantKind, PlanError, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_split_grants_across_plans() {
+        let mut company = Company::new(
+            "Acme",
+            vec![
+                EquityPlan::new("2015 Plan", 10_000, 48, vec![GrantKind::Rsu]),
+                EquityPlan::new("2021 Plan", 5_000, 48, vec![GrantKind::Rsu]),
+            ],
+        );
+
+        let grant = |amount| {
+            Grant::for_test(
+                amount,
+                LocalDate::from_ymd(2021, 1, 1),
+                VestingScheduleConfiguration::monthly(0, 0.0, 48),
+            )
+        };
+
+        company.issue("2015 Plan", grant(4_800)).unwrap();
+        company.issue("2021 Plan", grant(2_400)).unwrap();
+
+        assert_eq!(
+            company.transfer_reserve("2015 Plan", "2021 Plan", 6_000),
+            Err(PlanError::InsufficientReserve {
+                requested: 6_000,
+                remaining: 5_200,
+            })
+        );
+        assert_eq!(
+            company.issue("2010 Plan", grant(100)),
+            Err(PlanError::UnknownPlan("2010 Plan".to_string()))
+        );
+
+        company
+            .transfer_reserve("2015 Plan", "2021 Plan", 5_200)
+            .unwrap();
+
+        assert_eq!(
+            company.breakdown_on(LocalDate::from_ymd(2022, 1, 1)),
+            vec![
+                PlanBreakdown {
+                    plan_name: "2015 Plan".to_string(),
+                    share_reserve: 4_800,
+                    granted_amount: 4_800,
+                    vested_amount: 1_200,
+                    unvested_amount: 3_600,
+                    remaining_reserve: 0,
+                },
+                PlanBreakdown {
+                    plan_name: "2021 Plan".to_string(),
+                    share_reserve: 10_200,
+                    granted_amount: 2_400,
+                    vested_amount: 600,
+                    unvested_amount: 1_800,
+                    remaining_reserve: 7_800,
+                },
+            ]
+        );
+    }
+}
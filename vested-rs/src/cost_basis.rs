@@ -0,0 +1,291 @@
+use std::error::Error;
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use crate::{Currency, LocalDate, Money, TaxLot};
+
+/// A sale as reported by a broker on Form 1099-B.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BrokerSale {
+    pub description: String,
+    pub date_acquired: LocalDate,
+    pub date_sold: LocalDate,
+    pub quantity: i64,
+    pub proceeds: Money,
+    /// Often only the price paid for equity compensation, leaving out the income
+    /// already taxed as wages.
+    pub reported_cost_basis: Money,
+}
+
+/// A reported sale matched to the lots it sold from.
+#[derive(Debug, PartialEq)]
+pub struct BasisAdjustment {
+    pub sale: BrokerSale,
+    pub computed_cost_basis: Money,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CostBasisReport {
+    pub adjustments: Vec<BasisAdjustment>,
+    /// Sales of more shares than the lots acquired on their date hold.
+    pub unmatched: Vec<BrokerSale>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BrokerCsvError {
+    /// The header is missing one of the required columns.
+    MissingColumn(&'static str),
+    /// A row couldn't be parsed, numbered from 1 including the header.
+    InvalidRow(usize),
+}
+
+impl fmt::Display for BrokerCsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BrokerCsvError::MissingColumn(column) => {
+                write!(f, "missing column \"{}\"", column)
+            }
+            BrokerCsvError::InvalidRow(row) => write!(f, "invalid row {}", row),
+        }
+    }
+}
+
+impl Error for BrokerCsvError {}
+
+/// Columns read from a broker CSV, matched against its header ignoring case.
+const BROKER_CSV_COLUMNS: [&str; 6] = [
+    "Description",
+    "Date Acquired",
+    "Date Sold",
+    "Quantity",
+    "Proceeds",
+    "Cost Basis",
+];
+
+/// Splits a CSV line on commas outside of double quotes, unescaping quoted values.
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut values = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                values.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => values.push(String::new()),
+            c => values.last_mut().unwrap().push(c),
+        }
+    }
+
+    values
+}
+
+fn parse_date(value: &str) -> Option<LocalDate> {
+    value.parse().ok().or_else(|| {
+        NaiveDate::parse_from_str(value, "%m/%d/%Y")
+            .ok()
+            .map(LocalDate::from)
+    })
+}
+
+fn parse_money(value: &str, currency: Currency) -> Option<Money> {
+    let amount: f64 = value
+        .chars()
+        .filter(|c| !matches!(c, '$' | ','))
+        .collect::<String>()
+        .parse()
+        .ok()?;
+
+    Some(Money::from_major_units(amount, currency))
+}
+
+impl BrokerSale {
+    /// Parses a 1099-B style CSV with a header naming the `Description`, `Date
+    /// Acquired`, `Date Sold`, `Quantity`, `Proceeds` and `Cost Basis` columns in any
+    /// order. Dates may be `YYYY-MM-DD` or `MM/DD/YYYY`, and amounts may include a
+    /// currency symbol and thousands separators. Other columns and blank lines are
+    /// ignored.
+    pub fn parse_csv(source: &str, currency: Currency) -> Result<Vec<BrokerSale>, BrokerCsvError> {
+        let mut lines = source.lines().enumerate();
+        let header = lines
+            .next()
+            .map(|(_, line)| split_csv_line(line))
+            .unwrap_or_default();
+
+        let mut indices = [0; 6];
+        for (index, column) in indices.iter_mut().zip(BROKER_CSV_COLUMNS) {
+            *index = header
+                .iter()
+                .position(|name| name.trim().eq_ignore_ascii_case(column))
+                .ok_or(BrokerCsvError::MissingColumn(column))?;
+        }
+
+        let mut sales = Vec::new();
+
+        for (row, line) in lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let values = split_csv_line(line);
+            let value = |column: usize| values.get(indices[column]).map(|value| value.trim());
+
+            let sale = (|| {
+                Some(BrokerSale {
+                    description: value(0)?.to_string(),
+                    date_acquired: parse_date(value(1)?)?,
+                    date_sold: parse_date(value(2)?)?,
+                    quantity: value(3)?.replace(',', "").parse().ok()?,
+                    proceeds: parse_money(value(4)?, currency)?,
+                    reported_cost_basis: parse_money(value(5)?, currency)?,
+                })
+            })();
+
+            sales.push(sale.ok_or(BrokerCsvError::InvalidRow(row + 1))?);
+        }
+
+        Ok(sales)
+    }
+}
+
+impl BasisAdjustment {
+    /// Amount to add to the reported cost basis, negative if it was over-reported.
+    pub fn adjustment(&self) -> Money {
+        self.computed_cost_basis - self.sale.reported_cost_basis
+    }
+}
+
+impl CostBasisReport {
+    /// The sales whose reported cost basis differs from the computed one.
+    pub fn needing_adjustment(&self) -> impl Iterator<Item = &BasisAdjustment> {
+        self.adjustments
+            .iter()
+            .filter(|adjustment| adjustment.adjustment().minor_units() != 0)
+    }
+
+    pub fn total_adjustment(&self, currency: Currency) -> Money {
+        Money::sum(
+            currency,
+            self.adjustments
+                .iter()
+                .map(|adjustment| adjustment.adjustment()),
+        )
+    }
+}
+
+/// Compares the cost basis a broker reported for each sale against the basis of the
+/// lots sold, which includes the income taxed on acquisition. Sales are matched to
+/// lots by acquisition date, selling the lots acquired on a date in order.
+pub fn reconcile_cost_basis(sales: &[BrokerSale], lots: &[TaxLot]) -> CostBasisReport {
+    let mut remaining: Vec<i64> = lots.iter().map(|lot| lot.shares).collect();
+    let mut adjustments = Vec::new();
+    let mut unmatched = Vec::new();
+
+    for sale in sales {
+        let available: i64 = lots
+            .iter()
+            .zip(&remaining)
+            .filter(|(lot, _)| lot.date_acquired == sale.date_acquired)
+            .map(|(_, shares)| shares)
+            .sum();
+
+        if sale.quantity > available {
+            unmatched.push(sale.clone());
+            continue;
+        }
+
+        let mut quantity = sale.quantity;
+        let mut computed_cost_basis = Money::zero(sale.reported_cost_basis.currency());
+
+        for (lot, shares) in lots.iter().zip(remaining.iter_mut()) {
+            if quantity == 0 {
+                break;
+            }
+
+            if lot.date_acquired != sale.date_acquired {
+                continue;
+            }
+
+            let sold = quantity.min(*shares);
+            computed_cost_basis += lot.cost_basis().scale(sold as f64 / lot.shares as f64);
+            *shares -= sold;
+            quantity -= sold;
+        }
+
+        adjustments.push(BasisAdjustment {
+            sale: sale.clone(),
+            computed_cost_basis,
+        });
+    }
+
+    CostBasisReport {
+        adjustments,
+        unmatched,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{reconcile_cost_basis, BrokerCsvError, BrokerSale, LocalDate};
+    use crate::{
+        usd, usd_price, Currency, Grant, GrantLedger, PriceTable, VestingScheduleConfiguration,
+    };
+
+    #[test]
+    fn it_can_reconcile_broker_cost_basis() {
+        let grant = Grant::for_test(
+            200,
+            LocalDate::from_ymd(2022, 1, 1),
+            VestingScheduleConfiguration::monthly(0, 0.0, 2),
+        );
+        let prices = PriceTable::new(vec![
+            (LocalDate::from_ymd(2022, 1, 1), usd_price(10.0)),
+            (LocalDate::from_ymd(2022, 3, 1), usd_price(15.0)),
+        ]);
+        let lots = GrantLedger::new(grant)
+            .calculate_tax_lots(&[], &prices)
+            .unwrap();
+
+        let sales = BrokerSale::parse_csv(
+            "Description,Date Acquired,Date Sold,Quantity,Proceeds,Cost Basis\n\
+             \"60 sh XYZ, Inc.\",02/01/2022,06/01/2022,60,\"$1,200.00\",0.00\n\
+             40 sh XYZ,2022-02-01,2022-06-01,40,800.00,400.00\n\
+             100 sh XYZ,03/01/2022,06/01/2022,100,2000.00,1500.00\n\
+             10 sh XYZ,04/01/2022,06/01/2022,10,200.00,0.00\n",
+            Currency::Usd,
+        )
+        .unwrap();
+
+        assert_eq!(sales[0].description, "60 sh XYZ, Inc.");
+
+        /*
+         * - The first sale's basis of the $10 vest price was reported as zero
+         * - The others were reported correctly, except for a lot that never vested
+         */
+
+        let report = reconcile_cost_basis(&sales, &lots);
+
+        assert_eq!(report.adjustments.len(), 3);
+        assert_eq!(report.adjustments[0].computed_cost_basis, usd(600.0));
+        assert_eq!(report.needing_adjustment().count(), 1);
+        assert_eq!(report.total_adjustment(Currency::Usd), usd(600.0));
+        assert_eq!(report.unmatched, vec![sales[3].clone()]);
+
+        assert_eq!(
+            BrokerSale::parse_csv("Description,Date Sold\n", Currency::Usd),
+            Err(BrokerCsvError::MissingColumn("Date Acquired"))
+        );
+        assert_eq!(
+            BrokerSale::parse_csv(
+                "Description,Date Acquired,Date Sold,Quantity,Proceeds,Cost Basis\n\
+                 1 sh XYZ,yesterday,2022-06-01,1,10.00,0.00\n",
+                Currency::Usd,
+            ),
+            Err(BrokerCsvError::InvalidRow(2))
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use std::io::{self, Write};
+
+use crate::VestingSchedule;
+
+impl VestingSchedule {
+    /// Writes the schedule as CSV with a header, one line per period: the date, the
+    /// shares vesting on it, the cumulative shares vested and the shares left unvested,
+    /// including any that were forfeited.
+    pub fn to_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let total = self
+            .periods
+            .last()
+            .map_or(0, |period| period.cumulative_vested_amount)
+            + self.forfeited_amount;
+
+        writeln!(
+            writer,
+            "date,vested_this_period,cumulative_vested,remaining"
+        )?;
+
+        for period in &self.periods {
+            writeln!(
+                writer,
+                "{},{},{},{}",
+                period.date,
+                period.vested_this_period,
+                period.cumulative_vested_amount,
+                total - period.cumulative_vested_amount
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Grant, LocalDate, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_write_a_schedule_as_csv() {
+        let mut grant = Grant::for_test(
+            300,
+            LocalDate::from_ymd(2021, 1, 15),
+            VestingScheduleConfiguration::monthly(0, 0.0, 3),
+        );
+        grant.terminate(LocalDate::from_ymd(2021, 3, 15));
+
+        let mut csv = Vec::new();
+        grant.calculate_vesting_schedule().to_csv(&mut csv).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "date,vested_this_period,cumulative_vested,remaining\n\
+             2021-01-15,0,0,300\n\
+             2021-02-15,100,100,200\n\
+             2021-03-15,100,200,100\n"
+        );
+    }
+}
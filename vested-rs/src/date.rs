@@ -0,0 +1,257 @@
+use std::fmt;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+use chrono::format::{DelayedFormat, StrftimeItems};
+use chrono::{
+    DateTime, Datelike, Duration, IsoWeek, NaiveDate, ParseError, TimeZone, Utc, Weekday,
+};
+use chronoutil::RelativeDuration;
+
+/// A civil calendar date, with no time of day or timezone attached.
+///
+/// All vesting math works on civil dates: a grant made on Feb 6 vests on the 6th
+/// wherever the holder is. Timestamps have to be converted explicitly, choosing the
+/// timezone whose calendar applies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LocalDate(NaiveDate);
+
+impl LocalDate {
+    /// Makes a date from the year, month and day, returning `None` if it doesn't exist.
+    pub fn from_ymd_opt(year: i32, month: u32, day: u32) -> Option<LocalDate> {
+        NaiveDate::from_ymd_opt(year, month, day).map(LocalDate)
+    }
+
+    /// Makes a date from the year, month and day.
+    ///
+    /// Panics if the date doesn't exist, e.g. Feb 30.
+    pub fn from_ymd(year: i32, month: u32, day: u32) -> LocalDate {
+        LocalDate::from_ymd_opt(year, month, day).expect("invalid or out-of-range date")
+    }
+
+    /// The calendar date of a timestamp, as observed in the timestamp's own timezone.
+    pub fn from_datetime<Tz: TimeZone>(datetime: &DateTime<Tz>) -> LocalDate {
+        LocalDate(datetime.date_naive())
+    }
+
+    /// The calendar date of a timestamp, as observed in the given timezone.
+    pub fn from_datetime_in<Tz: TimeZone, Local: TimeZone>(
+        datetime: &DateTime<Tz>,
+        timezone: &Local,
+    ) -> LocalDate {
+        LocalDate(datetime.with_timezone(timezone).date_naive())
+    }
+
+    /// Today's date as observed in the given timezone.
+    pub fn today_in<Tz: TimeZone>(timezone: &Tz) -> LocalDate {
+        LocalDate::from_datetime_in(&Utc::now(), timezone)
+    }
+
+    /// The first instant of this date in the given timezone, or `None` if the
+    /// timezone skips the whole day.
+    pub fn start_of_day_in<Tz: TimeZone>(self, timezone: &Tz) -> Option<DateTime<Tz>> {
+        timezone
+            .from_local_datetime(&self.0.and_hms_opt(0, 0, 0)?)
+            .earliest()
+    }
+
+    pub fn naive_date(self) -> NaiveDate {
+        self.0
+    }
+
+    /// Formats the date with the specified strftime-style format string.
+    pub fn format<'a>(&self, format: &'a str) -> DelayedFormat<StrftimeItems<'a>> {
+        self.0.format(format)
+    }
+}
+
+impl From<NaiveDate> for LocalDate {
+    fn from(date: NaiveDate) -> LocalDate {
+        LocalDate(date)
+    }
+}
+
+impl From<LocalDate> for NaiveDate {
+    fn from(date: LocalDate) -> NaiveDate {
+        date.0
+    }
+}
+
+/// Eases moving off chrono's deprecated `Date`, which earlier versions of this crate
+/// used with an implicit UTC timezone. The date is kept as observed in its timezone.
+#[allow(deprecated)]
+impl<Tz: TimeZone> From<chrono::Date<Tz>> for LocalDate {
+    fn from(date: chrono::Date<Tz>) -> LocalDate {
+        LocalDate(date.naive_local())
+    }
+}
+
+impl fmt::Display for LocalDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.format("%Y-%m-%d"))
+    }
+}
+
+/// Parses an ISO 8601 calendar date, e.g. `2020-02-06`.
+impl FromStr for LocalDate {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<LocalDate, ParseError> {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").map(LocalDate)
+    }
+}
+
+/// Serializes as an ISO 8601 calendar date, e.g. `2020-02-06`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for LocalDate {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for LocalDate {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<LocalDate, D::Error> {
+        let value = <std::borrow::Cow<str>>::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl Add<RelativeDuration> for LocalDate {
+    type Output = LocalDate;
+
+    fn add(self, duration: RelativeDuration) -> LocalDate {
+        LocalDate(self.0 + duration)
+    }
+}
+
+impl Sub<RelativeDuration> for LocalDate {
+    type Output = LocalDate;
+
+    fn sub(self, duration: RelativeDuration) -> LocalDate {
+        LocalDate(self.0 - duration)
+    }
+}
+
+impl Add<Duration> for LocalDate {
+    type Output = LocalDate;
+
+    fn add(self, duration: Duration) -> LocalDate {
+        LocalDate(self.0 + duration)
+    }
+}
+
+impl Sub<Duration> for LocalDate {
+    type Output = LocalDate;
+
+    fn sub(self, duration: Duration) -> LocalDate {
+        LocalDate(self.0 - duration)
+    }
+}
+
+impl Sub<LocalDate> for LocalDate {
+    type Output = Duration;
+
+    fn sub(self, other: LocalDate) -> Duration {
+        self.0.signed_duration_since(other.0)
+    }
+}
+
+impl Datelike for LocalDate {
+    fn year(&self) -> i32 {
+        self.0.year()
+    }
+
+    fn month(&self) -> u32 {
+        self.0.month()
+    }
+
+    fn month0(&self) -> u32 {
+        self.0.month0()
+    }
+
+    fn day(&self) -> u32 {
+        self.0.day()
+    }
+
+    fn day0(&self) -> u32 {
+        self.0.day0()
+    }
+
+    fn ordinal(&self) -> u32 {
+        self.0.ordinal()
+    }
+
+    fn ordinal0(&self) -> u32 {
+        self.0.ordinal0()
+    }
+
+    fn weekday(&self) -> Weekday {
+        self.0.weekday()
+    }
+
+    fn iso_week(&self) -> IsoWeek {
+        self.0.iso_week()
+    }
+
+    fn with_year(&self, year: i32) -> Option<LocalDate> {
+        self.0.with_year(year).map(LocalDate)
+    }
+
+    fn with_month(&self, month: u32) -> Option<LocalDate> {
+        self.0.with_month(month).map(LocalDate)
+    }
+
+    fn with_month0(&self, month0: u32) -> Option<LocalDate> {
+        self.0.with_month0(month0).map(LocalDate)
+    }
+
+    fn with_day(&self, day: u32) -> Option<LocalDate> {
+        self.0.with_day(day).map(LocalDate)
+    }
+
+    fn with_day0(&self, day0: u32) -> Option<LocalDate> {
+        self.0.with_day0(day0).map(LocalDate)
+    }
+
+    fn with_ordinal(&self, ordinal: u32) -> Option<LocalDate> {
+        self.0.with_ordinal(ordinal).map(LocalDate)
+    }
+
+    fn with_ordinal0(&self, ordinal0: u32) -> Option<LocalDate> {
+        self.0.with_ordinal0(ordinal0).map(LocalDate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, TimeZone, Utc};
+
+    use super::LocalDate;
+
+    #[test]
+    fn it_converts_timestamps_using_an_explicit_timezone() {
+        let timestamp = Utc.with_ymd_and_hms(2021, 2, 6, 3, 30, 0).unwrap();
+        let pacific = FixedOffset::west_opt(8 * 3600).unwrap();
+
+        assert_eq!(
+            LocalDate::from_datetime(&timestamp),
+            LocalDate::from_ymd(2021, 2, 6)
+        );
+        assert_eq!(
+            LocalDate::from_datetime_in(&timestamp, &pacific),
+            LocalDate::from_ymd(2021, 2, 5)
+        );
+        assert_eq!(
+            LocalDate::from_ymd(2021, 2, 5).start_of_day_in(&pacific),
+            Some(pacific.with_ymd_and_hms(2021, 2, 5, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            "2021-02-05".parse::<LocalDate>(),
+            Ok(LocalDate::from_ymd(2021, 2, 5))
+        );
+        #[allow(deprecated)]
+        let date = timestamp.date();
+        assert_eq!(LocalDate::from(date), LocalDate::from_ymd(2021, 2, 6));
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{Grant, LocalDate};
+
+/// A vesting date on which the floating point and exact calculations of a grant
+/// disagree.
+#[derive(Debug, PartialEq)]
+pub struct Divergence {
+    pub date: LocalDate,
+    /// Amount vested according to `Grant::calculate_vested_amount`.
+    pub float_amount: f32,
+    /// Shares vested according to `Grant::calculate_vested_shares`.
+    pub exact_amount: i64,
+}
+
+impl Divergence {
+    pub fn difference(&self) -> f64 {
+        (self.float_amount as f64 - self.exact_amount as f64).abs()
+    }
+}
+
+impl Grant {
+    /// Runs both the floating point and the exact integer calculation on every vesting
+    /// date and lists the dates where they differ by more than the tolerance, e.g. to
+    /// catch precision loss on very large grants. Rounding alone accounts for
+    /// differences of less than one share.
+    pub fn find_divergences(&self, tolerance: f64) -> Vec<Divergence> {
+        self.calculate_vesting_schedule()
+            .periods
+            .into_iter()
+            .map(|period| Divergence {
+                date: period.date,
+                float_amount: self.calculate_vested_amount(period.date),
+                exact_amount: self.calculate_vested_shares(period.date),
+            })
+            .filter(|divergence| divergence.difference() > tolerance)
+            .collect()
+    }
+}
+
+/// Checks every grant with `Grant::find_divergences`, pairing each divergence with the
+/// index of its grant.
+pub fn find_divergences(grants: &[Grant], tolerance: f64) -> Vec<(usize, Divergence)> {
+    grants
+        .iter()
+        .enumerate()
+        .flat_map(|(index, grant)| {
+            grant
+                .find_divergences(tolerance)
+                .into_iter()
+                .map(move |divergence| (index, divergence))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_divergences, LocalDate};
+    use crate::{Grant, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_find_divergences_between_float_and_exact_amounts() {
+        let grant = |amount| {
+            Grant::for_test(
+                amount,
+                LocalDate::from_ymd(2021, 1, 15),
+                VestingScheduleConfiguration::monthly(12, 0.25, 48),
+            )
+        };
+
+        /*
+         * 100,000,007 is past the 24 bits an f32 can hold exactly, so the float
+         * calculation is off by whole shares.
+         */
+
+        let divergences = find_divergences(&[grant(4_800), grant(100_000_007)], 0.5);
+
+        assert!(!divergences.is_empty());
+        assert!(divergences.iter().all(|(index, _)| *index == 1));
+
+        let (_, last) = divergences.last().unwrap();
+
+        assert_eq!(last.date, LocalDate::from_ymd(2025, 1, 15));
+        assert_eq!(last.exact_amount, 100_000_007);
+        assert_eq!(last.float_amount, 100_000_008.0);
+    }
+}
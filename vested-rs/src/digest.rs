@@ -0,0 +1,266 @@
+use chronoutil::RelativeDuration;
+
+use crate::ics::{write_calendar, CalendarEvent};
+use crate::money::group_thousands;
+use crate::{Holder, LocalDate};
+
+/// Shares from one grant vesting on one date.
+#[derive(Debug, PartialEq)]
+pub struct DigestEvent {
+    pub date: LocalDate,
+    pub grant_date: LocalDate,
+    /// Index of the grant in the holder's grants, to tell apart grants made on the
+    /// same date.
+    pub grant_index: usize,
+    pub amount: i64,
+}
+
+/// A monthly summary of one holder's vesting, for sending by email.
+#[derive(Debug, PartialEq)]
+pub struct VestDigest {
+    pub holder: String,
+    pub as_of: LocalDate,
+    /// Vests in the month before `as_of`, excluding it.
+    pub recent: Vec<DigestEvent>,
+    /// Vests in the month from `as_of`, including it.
+    pub upcoming: Vec<DigestEvent>,
+    /// Shares vested across all grants on `as_of`.
+    pub vested_amount: i64,
+    pub unvested_amount: i64,
+}
+
+/// Escapes the characters with special meaning in HTML text and attribute values.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Holder {
+    /// Summarizes the holder's vests in the month either side of the given date, e.g.
+    /// the day a monthly digest is sent.
+    pub fn vest_digest(&self, as_of: LocalDate) -> VestDigest {
+        let recent_from = as_of - RelativeDuration::months(1);
+        let upcoming_until = as_of + RelativeDuration::months(1);
+        let mut recent = Vec::new();
+        let mut upcoming = Vec::new();
+
+        for (grant_index, grant) in self.grants.iter().enumerate() {
+            for event in grant.vest_events() {
+                let digest_event = DigestEvent {
+                    date: event.date,
+                    grant_date: grant.grant_date,
+                    grant_index,
+                    amount: event.amount,
+                };
+
+                if event.date >= recent_from && event.date < as_of {
+                    recent.push(digest_event);
+                } else if event.date >= as_of && event.date < upcoming_until {
+                    upcoming.push(digest_event);
+                }
+            }
+        }
+
+        recent.sort_by_key(|event| (event.date, event.grant_index));
+        upcoming.sort_by_key(|event| (event.date, event.grant_index));
+
+        let vested_amount = self
+            .grants
+            .iter()
+            .map(|grant| grant.calculate_vested_shares(as_of))
+            .sum();
+        let unvested_amount = self
+            .grants
+            .iter()
+            .map(|grant| grant.calculate_unvested_amount(as_of))
+            .sum();
+
+        VestDigest {
+            holder: self.name.clone(),
+            as_of,
+            recent,
+            upcoming,
+            vested_amount,
+            unvested_amount,
+        }
+    }
+}
+
+impl VestDigest {
+    pub fn recent_total(&self) -> i64 {
+        self.recent.iter().map(|event| event.amount).sum()
+    }
+
+    pub fn upcoming_total(&self) -> i64 {
+        self.upcoming.iter().map(|event| event.amount).sum()
+    }
+
+    fn sections(&self) -> [(&'static str, &[DigestEvent], i64); 2] {
+        [
+            ("Last month", &self.recent, self.recent_total()),
+            ("Next month", &self.upcoming, self.upcoming_total()),
+        ]
+    }
+
+    fn balance(&self) -> String {
+        format!(
+            "{} shares vested, {} unvested.",
+            group_thousands(self.vested_amount),
+            group_thousands(self.unvested_amount)
+        )
+    }
+
+    /// Renders the digest as Markdown, with a table of vests per month.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# Vesting digest for {}, {}\n", self.holder, self.as_of);
+
+        for (title, events, total) in self.sections() {
+            markdown += &format!("\n## {}\n\n", title);
+
+            if events.is_empty() {
+                markdown += "No vests.\n";
+                continue;
+            }
+
+            markdown += "| Date | Grant | Shares |\n| --- | --- | ---: |\n";
+
+            for event in events {
+                markdown += &format!(
+                    "| {} | {} | {} |\n",
+                    event.date,
+                    event.grant_date,
+                    group_thousands(event.amount)
+                );
+            }
+
+            markdown += &format!("\nTotal: {} shares\n", group_thousands(total));
+        }
+
+        markdown + &format!("\n{}\n", self.balance())
+    }
+
+    /// Renders the digest as an HTML fragment for the body of an email.
+    pub fn to_html(&self) -> String {
+        let mut html = format!(
+            "<h1>Vesting digest for {}, {}</h1>\n",
+            escape_html(&self.holder),
+            self.as_of
+        );
+
+        for (title, events, total) in self.sections() {
+            html += &format!("<h2>{}</h2>\n", title);
+
+            if events.is_empty() {
+                html += "<p>No vests.</p>\n";
+                continue;
+            }
+
+            html += "<table>\n<tr><th>Date</th><th>Grant</th><th>Shares</th></tr>\n";
+
+            for event in events {
+                html += &format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    event.date,
+                    event.grant_date,
+                    group_thousands(event.amount)
+                );
+            }
+
+            html += &format!(
+                "</table>\n<p>Total: {} shares</p>\n",
+                group_thousands(total)
+            );
+        }
+
+        html + &format!("<p>{}</p>\n", self.balance())
+    }
+
+    /// Writes the upcoming vests as an iCalendar file to attach to the digest.
+    pub fn to_ics(&self) -> String {
+        let holder: String = self
+            .holder
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect();
+        let events: Vec<CalendarEvent> = self
+            .upcoming
+            .iter()
+            .map(|event| CalendarEvent {
+                date: event.date,
+                uid: format!(
+                    "{}-{}-{}@vested",
+                    holder.to_ascii_lowercase(),
+                    event.grant_index,
+                    event.date.format("%Y%m%d")
+                ),
+                summary: format!("{} shares vest", group_thousands(event.amount)),
+            })
+            .collect();
+
+        write_calendar(&events, self.as_of)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LocalDate;
+    use crate::{Grant, Holder, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_generate_a_monthly_vest_digest() {
+        let holder = Holder {
+            name: "Jane <Doe>".to_string(),
+            grants: vec![
+                Grant::for_test(
+                    4_800,
+                    LocalDate::from_ymd(2021, 1, 15),
+                    VestingScheduleConfiguration::monthly(12, 0.25, 48),
+                ),
+                Grant::for_test(
+                    2_400,
+                    LocalDate::from_ymd(2022, 1, 20),
+                    VestingScheduleConfiguration::monthly(0, 0.0, 24),
+                ),
+            ],
+        };
+
+        let digest = holder.vest_digest(LocalDate::from_ymd(2022, 1, 18));
+
+        assert_eq!(digest.recent_total(), 1_200);
+        assert_eq!(digest.upcoming_total(), 100);
+        assert_eq!(
+            digest.to_markdown(),
+            "# Vesting digest for Jane <Doe>, 2022-01-18\n\
+             \n\
+             ## Last month\n\
+             \n\
+             | Date | Grant | Shares |\n\
+             | --- | --- | ---: |\n\
+             | 2022-01-15 | 2021-01-15 | 1,200 |\n\
+             \n\
+             Total: 1,200 shares\n\
+             \n\
+             ## Next month\n\
+             \n\
+             | Date | Grant | Shares |\n\
+             | --- | --- | ---: |\n\
+             | 2022-02-15 | 2021-01-15 | 100 |\n\
+             \n\
+             Total: 100 shares\n\
+             \n\
+             1,200 shares vested, 3,600 unvested.\n"
+        );
+        assert!(digest
+            .to_html()
+            .starts_with("<h1>Vesting digest for Jane &lt;Doe&gt;, 2022-01-18</h1>\n"));
+        assert!(digest.to_ics().contains(
+            "BEGIN:VEVENT\r\n\
+             UID:janedoe-0-20220215@vested\r\n\
+             DTSTAMP:20220118T000000Z\r\n\
+             DTSTART;VALUE=DATE:20220215\r\n\
+             SUMMARY:100 shares vest\r\n"
+        ));
+    }
+}
@@ -0,0 +1,325 @@
+use chronoutil::RelativeDuration;
+
+use crate::{
+    IntervalLength, VestingError, VestingInterval, VestingScheduleConfiguration,
+    DAYS_PER_WEEKLY_YEAR,
+};
+
+/// Average length of a month in days, over the 400 year Gregorian cycle.
+const AVERAGE_DAYS_PER_MONTH: f32 = 365.2425 / 12.0;
+
+/// A calendar duration used to express a vesting length or cliff independently of
+/// the vesting interval.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum VestingDuration {
+    Days(i32),
+    Months(i32),
+    Years(i32),
+}
+
+/// A cliff given as a calendar duration, independently of the vesting interval, e.g. a
+/// 1 year cliff on a quarterly schedule.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum CliffSpec {
+    /// Vesting starts with the first interval.
+    #[default]
+    None,
+    Days(i32),
+    Months(i32),
+    Years(i32),
+}
+
+/// How to handle a duration that isn't a whole number of vesting intervals, such as
+/// 365 days on a monthly schedule.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ProrationPolicy {
+    /// Reject durations that don't divide cleanly.
+    #[default]
+    Strict,
+    RoundDown,
+    RoundUp,
+    RoundNearest,
+}
+
+/// Where the shorter interval falls when a schedule's length isn't a whole number of
+/// vesting intervals, such as 50 months on a quarterly schedule.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum StubPolicy {
+    /// The first interval is shorter, so the remaining vesting dates line up with the
+    /// end of the schedule.
+    Leading,
+    /// The last interval is shorter, so vesting dates follow the grant date until the
+    /// schedule ends.
+    #[default]
+    Trailing,
+}
+
+impl VestingDuration {
+    /// Converts the duration into a number of vesting intervals, applying the proration
+    /// policy when it doesn't divide cleanly. Months and years are converted to weekly
+    /// intervals at 52 weeks a year.
+    pub fn to_intervals(
+        self,
+        interval: &VestingInterval,
+        proration: ProrationPolicy,
+    ) -> Result<i32, VestingError> {
+        let intervals = match interval.length() {
+            IntervalLength::Months(months_per_interval) => match self {
+                VestingDuration::Months(months) if months % months_per_interval == 0 => {
+                    return Ok(months / months_per_interval)
+                }
+                VestingDuration::Years(years) if (years * 12) % months_per_interval == 0 => {
+                    return Ok(years * 12 / months_per_interval)
+                }
+                VestingDuration::Days(0) => return Ok(0),
+                VestingDuration::Months(months) => months as f32 / months_per_interval as f32,
+                VestingDuration::Years(years) => (years * 12) as f32 / months_per_interval as f32,
+                VestingDuration::Days(days) => {
+                    days as f32 / (AVERAGE_DAYS_PER_MONTH * months_per_interval as f32)
+                }
+            },
+            IntervalLength::Days(days_per_interval) => {
+                let days = match self {
+                    VestingDuration::Days(days) => days as f32,
+                    VestingDuration::Months(months) => {
+                        (months * DAYS_PER_WEEKLY_YEAR) as f32 / 12.0
+                    }
+                    VestingDuration::Years(years) => (years * DAYS_PER_WEEKLY_YEAR) as f32,
+                };
+                let intervals = days / days_per_interval as f32;
+
+                if intervals.fract() == 0.0 {
+                    return Ok(intervals as i32);
+                }
+
+                intervals
+            }
+        };
+
+        match proration {
+            ProrationPolicy::Strict => Err(VestingError::UnevenDuration(self)),
+            ProrationPolicy::RoundDown => Ok(intervals.floor() as i32),
+            ProrationPolicy::RoundUp => Ok(intervals.ceil() as i32),
+            ProrationPolicy::RoundNearest => Ok(intervals.round() as i32),
+        }
+    }
+
+    /// Converts the duration into one that can be added to a date.
+    pub(crate) fn to_relative_duration(self) -> RelativeDuration {
+        match self {
+            VestingDuration::Days(days) => RelativeDuration::days(days as i64),
+            VestingDuration::Months(months) => RelativeDuration::months(months),
+            VestingDuration::Years(years) => RelativeDuration::years(years),
+        }
+    }
+}
+
+impl CliffSpec {
+    /// Converts the cliff into a number of vesting intervals, applying the proration
+    /// policy when it doesn't divide cleanly.
+    pub fn to_intervals(
+        self,
+        interval: &VestingInterval,
+        proration: ProrationPolicy,
+    ) -> Result<i32, VestingError> {
+        match self {
+            CliffSpec::None => Ok(0),
+            CliffSpec::Days(days) => VestingDuration::Days(days).to_intervals(interval, proration),
+            CliffSpec::Months(months) => {
+                VestingDuration::Months(months).to_intervals(interval, proration)
+            }
+            CliffSpec::Years(years) => {
+                VestingDuration::Years(years).to_intervals(interval, proration)
+            }
+        }
+    }
+}
+
+impl VestingScheduleConfiguration {
+    /// Replaces the schedule's cliff with one given as a calendar duration. A schedule
+    /// without a cliff vests nothing extra up front, whatever the cliff percentage.
+    pub fn with_cliff(
+        mut self,
+        cliff: CliffSpec,
+        cliff_percentage: f32,
+        proration: ProrationPolicy,
+    ) -> Result<Self, VestingError> {
+        self.cliff_intervals = match (cliff, self.stub_policy) {
+            (CliffSpec::Months(months), StubPolicy::Leading) if self.stub_months > 0 => {
+                self.intervals_after_leading_stub(months, proration)?
+            }
+            (CliffSpec::Years(years), StubPolicy::Leading) if self.stub_months > 0 => {
+                self.intervals_after_leading_stub(years * 12, proration)?
+            }
+            _ => cliff.to_intervals(&self.interval, proration)?,
+        };
+        self.cliff_percentage = match cliff {
+            CliffSpec::None => 0.0,
+            _ => cliff_percentage,
+        };
+
+        Ok(self)
+    }
+
+    /// Converts months from the grant date into intervals on a schedule whose first
+    /// interval is a shorter stub.
+    fn intervals_after_leading_stub(
+        &self,
+        months: i32,
+        proration: ProrationPolicy,
+    ) -> Result<i32, VestingError> {
+        let months_per_interval = match self.interval.length() {
+            IntervalLength::Months(months_per_interval) => months_per_interval,
+            IntervalLength::Days(_) => {
+                return VestingDuration::Months(months).to_intervals(&self.interval, proration)
+            }
+        };
+
+        if months == 0 {
+            return Ok(0);
+        }
+
+        // Counting the stub as a full interval lines the remaining months up with the
+        // schedule's intervals.
+        VestingDuration::Months(months - self.stub_months + months_per_interval)
+            .to_intervals(&self.interval, proration)
+            .map_err(|_| VestingError::UnevenDuration(VestingDuration::Months(months)))
+    }
+
+    /// Sets the schedule's length in months, vesting whatever doesn't fill a whole
+    /// interval in a shorter first or last interval, e.g. 50 months quarterly as 16
+    /// quarters and a 2 month stub. Set the cliff afterwards so it's counted from the
+    /// right interval. Weekly schedules have no stubs, so the length must divide evenly.
+    pub fn with_stub(
+        mut self,
+        length_months: i32,
+        stub_policy: StubPolicy,
+    ) -> Result<Self, VestingError> {
+        let months_per_interval = match self.interval.length() {
+            IntervalLength::Months(months_per_interval) => months_per_interval,
+            IntervalLength::Days(_) => {
+                self.total_intervals = VestingDuration::Months(length_months)
+                    .to_intervals(&self.interval, ProrationPolicy::Strict)?;
+                return Ok(self);
+            }
+        };
+
+        self.stub_months = length_months.rem_euclid(months_per_interval);
+        self.total_intervals =
+            length_months.div_euclid(months_per_interval) + (self.stub_months > 0) as i32;
+        self.stub_policy = stub_policy;
+
+        Ok(self)
+    }
+
+    /// Creates a vesting schedule whose cliff and length are given as calendar durations,
+    /// e.g. a 1 year cliff on a 4 year schedule.
+    pub fn from_durations(
+        interval: VestingInterval,
+        cliff: VestingDuration,
+        cliff_percentage: f32,
+        length: VestingDuration,
+        proration: ProrationPolicy,
+    ) -> Result<Self, VestingError> {
+        Ok(VestingScheduleConfiguration::new(
+            interval,
+            cliff.to_intervals(&interval, proration)?,
+            cliff_percentage,
+            length.to_intervals(&interval, proration)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CliffSpec, ProrationPolicy, VestingDuration};
+    use crate::{Grant, LocalDate, VestingError, VestingInterval, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_configure_schedules_with_calendar_durations() {
+        let configuration = VestingScheduleConfiguration::from_durations(
+            VestingInterval::Monthly,
+            VestingDuration::Years(1),
+            0.25,
+            VestingDuration::Months(48),
+            ProrationPolicy::Strict,
+        )
+        .unwrap();
+
+        assert_eq!(configuration.cliff_intervals, 12);
+        assert_eq!(configuration.total_intervals, 48);
+
+        assert_eq!(
+            VestingScheduleConfiguration::from_durations(
+                VestingInterval::Monthly,
+                VestingDuration::Days(365),
+                0.25,
+                VestingDuration::Years(4),
+                ProrationPolicy::Strict,
+            )
+            .err(),
+            Some(VestingError::UnevenDuration(VestingDuration::Days(365)))
+        );
+
+        let prorated = |proration| {
+            VestingDuration::Days(365)
+                .to_intervals(&VestingInterval::Monthly, proration)
+                .unwrap()
+        };
+
+        assert_eq!(prorated(ProrationPolicy::RoundDown), 11);
+        assert_eq!(prorated(ProrationPolicy::RoundUp), 12);
+        assert_eq!(prorated(ProrationPolicy::RoundNearest), 12);
+    }
+
+    #[test]
+    fn it_can_express_the_cliff_independently_of_the_interval() {
+        let quarterly = VestingScheduleConfiguration::new(VestingInterval::Quarterly, 0, 0.0, 16)
+            .with_cliff(CliffSpec::Years(1), 0.25, ProrationPolicy::Strict)
+            .unwrap();
+
+        assert_eq!(quarterly.cliff_intervals, 4);
+        assert_eq!(
+            CliffSpec::Days(45).to_intervals(&VestingInterval::Quarterly, ProrationPolicy::Strict),
+            Err(VestingError::UnevenDuration(VestingDuration::Days(45)))
+        );
+
+        let grant = Grant::for_test(
+            4_800,
+            LocalDate::from_ymd(2021, 1, 1),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48)
+                .with_cliff(CliffSpec::None, 0.25, ProrationPolicy::Strict)
+                .unwrap(),
+        );
+
+        assert_eq!(
+            grant.calculate_vested_shares(LocalDate::from_ymd(2021, 2, 1)),
+            100
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn it_can_load_cliffs_and_proration_policies_from_json() {
+        assert_eq!(
+            serde_json::from_str::<CliffSpec>(r#"{ "years": 1 }"#).unwrap(),
+            CliffSpec::Years(1)
+        );
+        assert_eq!(
+            serde_json::from_str::<CliffSpec>(r#""none""#).unwrap(),
+            CliffSpec::None
+        );
+        assert_eq!(
+            serde_json::to_value(ProrationPolicy::RoundNearest).unwrap(),
+            "round_nearest"
+        );
+    }
+}
@@ -0,0 +1,349 @@
+use std::error::Error;
+use std::fmt;
+
+use chronoutil::RelativeDuration;
+
+use crate::{LocalDate, VestingPeriod, VestingSchedule};
+
+/// How far the tranche percentages may exceed the whole earn-out due to rounding.
+const TRANCHE_TOLERANCE: f32 = 0.0001;
+
+/// The post-close condition that releases an earn-out tranche.
+pub enum EarnOutCondition {
+    /// Vests in full on the date the milestone was achieved, if it has been.
+    Milestone { achieved_on: Option<LocalDate> },
+    /// Vests in full once the holder has stayed the given number of months past close.
+    Retention { months: i32 },
+}
+
+pub struct EarnOutTranche {
+    /// Fraction of the earn-out amount (0.0 - 1.0) released by this tranche.
+    pub percentage: f32,
+    pub condition: EarnOutCondition,
+}
+
+/// An award paid out after an acquisition closes, vesting in conditional tranches.
+pub struct EarnOut {
+    pub amount: i64,
+    pub close_date: LocalDate,
+    pub tranches: Vec<EarnOutTranche>,
+    /// The holder left on this date, forfeiting retention tranches not yet reached.
+    pub termination_date: Option<LocalDate>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum EarnOutError {
+    /// A tranche's percentage is outside 0.0 - 1.0.
+    PercentageOutOfRange(f32),
+    /// The tranches together pay out more than the whole earn-out.
+    TranchesExceedAmount(f32),
+}
+
+impl fmt::Display for EarnOutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EarnOutError::PercentageOutOfRange(percentage) => write!(
+                f,
+                "earn-out tranche percentage must be between 0.0 and 1.0, got {}",
+                percentage
+            ),
+            EarnOutError::TranchesExceedAmount(total) => {
+                write!(
+                    f,
+                    "earn-out tranches must sum to at most 1.0, got {}",
+                    total
+                )
+            }
+        }
+    }
+}
+
+impl Error for EarnOutError {}
+
+impl EarnOutTranche {
+    /// Date on which the tranche's condition is met, if it is known. Retention
+    /// tranches are never met if the holder left before reaching them.
+    fn trigger_date(
+        &self,
+        close_date: LocalDate,
+        termination_date: Option<LocalDate>,
+    ) -> Option<LocalDate> {
+        match self.condition {
+            EarnOutCondition::Milestone { achieved_on } => achieved_on,
+            EarnOutCondition::Retention { months } => {
+                Some(close_date + RelativeDuration::months(months)).filter(|retention_date| {
+                    termination_date
+                        .is_none_or(|termination_date| *retention_date <= termination_date)
+                })
+            }
+        }
+    }
+
+    fn is_forfeited(&self, close_date: LocalDate, termination_date: Option<LocalDate>) -> bool {
+        matches!(self.condition, EarnOutCondition::Retention { .. })
+            && self.trigger_date(close_date, termination_date).is_none()
+    }
+}
+
+impl EarnOut {
+    /// Checks that every tranche is a fraction of the earn-out and that together they
+    /// pay out no more than the whole amount.
+    pub fn validate(&self) -> Result<(), EarnOutError> {
+        if let Some(tranche) = self
+            .tranches
+            .iter()
+            .find(|tranche| !(0.0..=1.0).contains(&tranche.percentage))
+        {
+            return Err(EarnOutError::PercentageOutOfRange(tranche.percentage));
+        }
+
+        let total_percentage: f32 = self.tranches.iter().map(|tranche| tranche.percentage).sum();
+
+        if total_percentage > 1.0 + TRANCHE_TOLERANCE {
+            return Err(EarnOutError::TranchesExceedAmount(total_percentage));
+        }
+
+        Ok(())
+    }
+
+    /// Whole shares paid by each tranche, in the order given. Each tranche is rounded
+    /// down and the leftovers go to the last tranche, so tranches covering the whole
+    /// earn-out pay all of it.
+    fn tranche_amounts(&self) -> Vec<i64> {
+        let total_percentage: f64 = self
+            .tranches
+            .iter()
+            .map(|tranche| tranche.percentage as f64)
+            .sum();
+        let total_amount = (self.amount as f64 * total_percentage.min(1.0)).round() as i64;
+        let mut amounts: Vec<i64> = self
+            .tranches
+            .iter()
+            .map(|tranche| (self.amount as f64 * tranche.percentage as f64).floor() as i64)
+            .collect();
+
+        if let Some((last, rest)) = amounts.split_last_mut() {
+            *last = total_amount - rest.iter().sum::<i64>();
+        }
+
+        amounts
+    }
+
+    /// Calculates the earn-out amount vested on a given future date.
+    pub fn calculate_vested_amount(&self, future_date: LocalDate) -> Result<f32, EarnOutError> {
+        self.validate()?;
+
+        Ok(self.vested_shares_on(&self.tranche_amounts(), future_date) as f32)
+    }
+
+    fn vested_shares_on(&self, tranche_amounts: &[i64], date: LocalDate) -> i64 {
+        self.tranches
+            .iter()
+            .zip(tranche_amounts)
+            .filter(|(tranche, _)| {
+                tranche
+                    .trigger_date(self.close_date, self.termination_date)
+                    .is_some_and(|trigger_date| trigger_date <= date)
+            })
+            .map(|(_, amount)| amount)
+            .sum()
+    }
+
+    /// Calculate the earn-out schedule, listing the cumulative amount vested on each
+    /// tranche's trigger date. Milestones that haven't been achieved are left out, and
+    /// retention tranches the holder left before reaching are forfeited.
+    pub fn calculate_vesting_schedule(&self) -> Result<VestingSchedule, EarnOutError> {
+        self.validate()?;
+
+        let tranche_amounts = self.tranche_amounts();
+        let mut trigger_dates: Vec<LocalDate> = self
+            .tranches
+            .iter()
+            .filter_map(|tranche| tranche.trigger_date(self.close_date, self.termination_date))
+            .collect();
+
+        trigger_dates.sort();
+        trigger_dates.dedup();
+
+        let mut periods: Vec<VestingPeriod> = trigger_dates
+            .iter()
+            .map(|date| {
+                let cumulative_vested_amount = self.vested_shares_on(&tranche_amounts, *date);
+
+                VestingPeriod::new(
+                    *date,
+                    cumulative_vested_amount as f64,
+                    cumulative_vested_amount,
+                )
+            })
+            .collect();
+
+        VestingPeriod::update_vested_this_period(&mut periods);
+
+        Ok(VestingSchedule {
+            periods,
+            from_date: self.close_date,
+            to_date: trigger_dates.last().copied().unwrap_or(self.close_date),
+            forfeited_amount: self
+                .tranches
+                .iter()
+                .zip(&tranche_amounts)
+                .filter(|(tranche, _)| tranche.is_forfeited(self.close_date, self.termination_date))
+                .map(|(_, amount)| amount)
+                .sum(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EarnOut, EarnOutCondition, EarnOutError, EarnOutTranche, LocalDate};
+    use crate::VestingPeriod;
+
+    #[test]
+    fn it_can_calculate_earn_out_tranches() {
+        let earn_out = EarnOut {
+            amount: 10_000,
+            close_date: LocalDate::from_ymd(2022, 1, 15),
+            tranches: vec![
+                EarnOutTranche {
+                    percentage: 0.5,
+                    condition: EarnOutCondition::Retention { months: 12 },
+                },
+                EarnOutTranche {
+                    percentage: 0.25,
+                    condition: EarnOutCondition::Milestone {
+                        achieved_on: Some(LocalDate::from_ymd(2022, 6, 1)),
+                    },
+                },
+                EarnOutTranche {
+                    percentage: 0.25,
+                    condition: EarnOutCondition::Milestone { achieved_on: None },
+                },
+            ],
+            termination_date: None,
+        };
+
+        assert_eq!(
+            earn_out.calculate_vested_amount(LocalDate::from_ymd(2022, 3, 1)),
+            Ok(0.0)
+        );
+        assert_eq!(
+            earn_out.calculate_vested_amount(LocalDate::from_ymd(2022, 6, 1)),
+            Ok(2500.0)
+        );
+        assert_eq!(
+            earn_out.calculate_vested_amount(LocalDate::from_ymd(2023, 1, 15)),
+            Ok(7500.0)
+        );
+        assert_eq!(
+            earn_out.calculate_vested_amount(LocalDate::from_ymd(2030, 1, 1)),
+            Ok(7500.0)
+        );
+
+        let vesting_schedule = earn_out.calculate_vesting_schedule().unwrap();
+
+        assert_eq!(vesting_schedule.to_date, LocalDate::from_ymd(2023, 1, 15));
+        assert_eq!(
+            vesting_schedule.periods,
+            vec![
+                VestingPeriod {
+                    date: LocalDate::from_ymd(2022, 6, 1),
+                    cumulative_vested_amount: 2500,
+                    vested_this_period: 2500,
+                    precise_cumulative_amount: 2500.0,
+                },
+                VestingPeriod {
+                    date: LocalDate::from_ymd(2023, 1, 15),
+                    cumulative_vested_amount: 7500,
+                    vested_this_period: 5000,
+                    precise_cumulative_amount: 7500.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_forfeits_retention_tranches_after_termination() {
+        let earn_out = EarnOut {
+            amount: 10_000,
+            close_date: LocalDate::from_ymd(2022, 1, 15),
+            tranches: vec![
+                EarnOutTranche {
+                    percentage: 0.5,
+                    condition: EarnOutCondition::Retention { months: 12 },
+                },
+                EarnOutTranche {
+                    percentage: 0.5,
+                    condition: EarnOutCondition::Milestone {
+                        achieved_on: Some(LocalDate::from_ymd(2022, 6, 1)),
+                    },
+                },
+            ],
+            termination_date: Some(LocalDate::from_ymd(2022, 9, 1)),
+        };
+
+        /* The holder left before the 12 month retention tranche on 2023/1/15. */
+
+        assert_eq!(
+            earn_out.calculate_vested_amount(LocalDate::from_ymd(2030, 1, 1)),
+            Ok(5000.0)
+        );
+
+        let vesting_schedule = earn_out.calculate_vesting_schedule().unwrap();
+
+        assert_eq!(vesting_schedule.periods.len(), 1);
+        assert_eq!(vesting_schedule.forfeited_amount, 5000);
+    }
+
+    #[test]
+    fn it_pays_rounding_leftovers_in_the_last_tranche() {
+        let third = |months| EarnOutTranche {
+            percentage: 1.0 / 3.0,
+            condition: EarnOutCondition::Retention { months },
+        };
+        let earn_out = EarnOut {
+            amount: 10_000,
+            close_date: LocalDate::from_ymd(2022, 1, 15),
+            tranches: vec![third(12), third(24), third(36)],
+            termination_date: None,
+        };
+
+        let vesting_schedule = earn_out.calculate_vesting_schedule().unwrap();
+
+        assert_eq!(
+            vesting_schedule
+                .periods
+                .iter()
+                .map(|period| period.vested_this_period)
+                .collect::<Vec<_>>(),
+            [3333, 3333, 3334]
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_tranches() {
+        let earn_out = |percentages: &[f32]| EarnOut {
+            amount: 10_000,
+            close_date: LocalDate::from_ymd(2022, 1, 15),
+            tranches: percentages
+                .iter()
+                .map(|percentage| EarnOutTranche {
+                    percentage: *percentage,
+                    condition: EarnOutCondition::Retention { months: 12 },
+                })
+                .collect(),
+            termination_date: None,
+        };
+
+        assert_eq!(
+            earn_out(&[1.5]).validate(),
+            Err(EarnOutError::PercentageOutOfRange(1.5))
+        );
+        assert_eq!(
+            earn_out(&[0.75, 0.5]).validate(),
+            Err(EarnOutError::TranchesExceedAmount(1.25))
+        );
+        assert!(earn_out(&[0.5, 0.5]).validate().is_ok());
+    }
+}
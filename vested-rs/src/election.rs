@@ -0,0 +1,211 @@
+use chrono::Duration;
+
+use crate::{Exercise, Grant, GrantLedger, GrantType, LocalDate, Money, Price};
+
+/// Days after receiving restricted shares an 83(b) election must be filed within.
+const EIGHTY_THREE_B_FILING_DAYS: i64 = 30;
+
+/// An 83(b) election to be taxed on unvested shares when they're received, rather than
+/// on their value as they vest.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EightyThreeBElection {
+    /// Date the shares were received, by early exercise or a restricted stock grant.
+    pub transfer_date: LocalDate,
+    pub shares: i64,
+    /// Price paid per share, the strike price or restricted stock purchase price.
+    pub purchase_price: Price,
+    /// Fair market value per share on the transfer date.
+    pub fair_market_value: Price,
+    /// Date the election was mailed to the IRS, if it has been.
+    pub filed_date: Option<LocalDate>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ElectionStatus {
+    /// Not filed yet, but still before the deadline.
+    Open,
+    Filed,
+    /// Filed after the deadline, so it doesn't count.
+    FiledLate,
+    /// Not filed by the deadline.
+    Missed,
+}
+
+impl EightyThreeBElection {
+    /// Last day the election can be filed, 30 days after the shares were received.
+    pub fn deadline(&self) -> LocalDate {
+        self.transfer_date + Duration::days(EIGHTY_THREE_B_FILING_DAYS)
+    }
+
+    pub fn status_on(&self, date: LocalDate) -> ElectionStatus {
+        match self.filed_date {
+            Some(filed_date) if filed_date <= self.deadline() => ElectionStatus::Filed,
+            Some(_) => ElectionStatus::FiledLate,
+            None if date <= self.deadline() => ElectionStatus::Open,
+            None => ElectionStatus::Missed,
+        }
+    }
+
+    /// Checks if a timely election was filed.
+    pub fn is_effective(&self) -> bool {
+        self.status_on(self.transfer_date) == ElectionStatus::Filed
+    }
+
+    /// Calculates the ordinary income recognized on the transfer date under an
+    /// effective election: the spread between fair market value and the price paid.
+    pub fn ordinary_income(&self) -> Option<Money> {
+        if !self.is_effective() {
+            return None;
+        }
+
+        let spread = self.fair_market_value - self.purchase_price;
+
+        Some(if spread.micro_units() > 0 {
+            spread * self.shares
+        } else {
+            Money::zero(spread.currency())
+        })
+    }
+
+    /// Calculates the shares' tax basis under an effective election: the price paid plus
+    /// the income recognized. `None` without one, since the basis is then set as the
+    /// shares vest.
+    pub fn tax_basis(&self) -> Option<Money> {
+        Some(self.purchase_price * self.shares + self.ordinary_income()?)
+    }
+}
+
+impl Grant {
+    /// Last day to file an 83(b) election for restricted stock, 30 days after the grant
+    /// date. `None` for other grants, which are elected on early exercise instead.
+    pub fn eighty_three_b_deadline(&self) -> Option<LocalDate> {
+        match self.grant_type {
+            GrantType::Rsa { .. } => {
+                Some(self.grant_date + Duration::days(EIGHTY_THREE_B_FILING_DAYS))
+            }
+            _ => None,
+        }
+    }
+
+    /// Makes an unfiled 83(b) election for restricted stock, valued at the given fair
+    /// market value on the grant date.
+    pub fn eighty_three_b_election(
+        &self,
+        fair_market_value: Price,
+    ) -> Option<EightyThreeBElection> {
+        match self.grant_type {
+            GrantType::Rsa { purchase_price } => Some(EightyThreeBElection {
+                transfer_date: self.grant_date,
+                shares: self.amount,
+                purchase_price,
+                fair_market_value,
+                filed_date: None,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl Exercise {
+    /// Last day to file an 83(b) election for shares exercised early, 30 days after the
+    /// exercise.
+    pub fn eighty_three_b_deadline(&self) -> LocalDate {
+        self.date + Duration::days(EIGHTY_THREE_B_FILING_DAYS)
+    }
+}
+
+impl GrantLedger {
+    /// Makes an unfiled 83(b) election for the unvested shares bought by an exercise,
+    /// valued at the given fair market value on the exercise date. `None` if the
+    /// exercise didn't include unvested shares.
+    pub fn eighty_three_b_election(
+        &self,
+        exercise: &Exercise,
+        fair_market_value: Price,
+    ) -> Option<EightyThreeBElection> {
+        let shares = self
+            .repurchaseable_shares_on(exercise.date)
+            .min(exercise.quantity);
+
+        if shares <= 0 {
+            return None;
+        }
+
+        Some(EightyThreeBElection {
+            transfer_date: exercise.date,
+            shares,
+            purchase_price: self.grant.strike_price()?,
+            fair_market_value,
+            filed_date: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ElectionStatus, LocalDate};
+    use crate::{usd, usd_price, Grant, GrantLedger, GrantType, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_track_eighty_three_b_elections() {
+        let rsa = Grant {
+            grant_type: GrantType::Rsa {
+                purchase_price: usd_price(0.01),
+            },
+            ..Grant::for_test(
+                10_000,
+                LocalDate::from_ymd(2021, 1, 1),
+                VestingScheduleConfiguration::monthly(12, 0.25, 48),
+            )
+        };
+
+        assert_eq!(
+            rsa.eighty_three_b_deadline(),
+            Some(LocalDate::from_ymd(2021, 1, 31))
+        );
+
+        let mut election = rsa.eighty_three_b_election(usd_price(0.05)).unwrap();
+
+        assert_eq!(
+            election.status_on(LocalDate::from_ymd(2021, 1, 31)),
+            ElectionStatus::Open
+        );
+        assert_eq!(
+            election.status_on(LocalDate::from_ymd(2021, 2, 1)),
+            ElectionStatus::Missed
+        );
+        assert_eq!(election.tax_basis(), None);
+
+        election.filed_date = Some(LocalDate::from_ymd(2021, 1, 15));
+
+        assert_eq!(election.ordinary_income(), Some(usd(400.0)));
+        assert_eq!(election.tax_basis(), Some(usd(500.0)));
+
+        let mut ledger = GrantLedger::new(Grant {
+            grant_type: GrantType::Iso {
+                strike_price: usd_price(0.1),
+            },
+            ..rsa
+        });
+        ledger.early_exercise = true;
+        ledger
+            .record_exercise(LocalDate::from_ymd(2022, 1, 1), 10_000)
+            .unwrap();
+
+        /*
+         * 2500 of the exercised shares had already vested, so only the other 7500 can
+         * be elected.
+         */
+
+        let exercise = ledger.exercises()[0];
+        let election = ledger
+            .eighty_three_b_election(&exercise, usd_price(0.1))
+            .unwrap();
+
+        assert_eq!(
+            exercise.eighty_three_b_deadline(),
+            LocalDate::from_ymd(2022, 1, 31)
+        );
+        assert_eq!(election.shares, 7_500);
+    }
+}
@@ -0,0 +1,77 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{LocalDate, VestingDuration};
+
+#[derive(Debug, PartialEq)]
+pub enum VestingError {
+    NegativeAmount(i64),
+    ZeroAmount,
+    NegativeLength(i32),
+    NegativeCliff(i32),
+    CliffExceedsLength {
+        cliff: i32,
+        length: i32,
+    },
+    CliffPercentageOutOfRange(f32),
+    /// The weights of a weighted schedule don't add up to the whole grant.
+    WeightsDoNotSumToOne(f32),
+    /// The duration isn't a whole number of vesting intervals.
+    UnevenDuration(VestingDuration),
+    /// A required grant term wasn't provided to the builder.
+    MissingTerm(&'static str),
+    /// A share amount doesn't fit in an `i64`, e.g. when totalling large token grants.
+    AmountOverflow,
+    /// The grant is dated before its board approval or 409A valuation date.
+    BackdatedGrant {
+        grant_date: LocalDate,
+        approval_date: LocalDate,
+    },
+}
+
+impl fmt::Display for VestingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VestingError::NegativeAmount(amount) => {
+                write!(f, "grant amount must not be negative, got {}", amount)
+            }
+            VestingError::ZeroAmount => write!(f, "grant amount must not be zero"),
+            VestingError::NegativeLength(length) => {
+                write!(f, "vesting length must not be negative, got {}", length)
+            }
+            VestingError::NegativeCliff(cliff) => {
+                write!(f, "cliff must not be negative, got {}", cliff)
+            }
+            VestingError::CliffExceedsLength { cliff, length } => write!(
+                f,
+                "cliff of {} periods exceeds vesting length of {} periods",
+                cliff, length
+            ),
+            VestingError::CliffPercentageOutOfRange(cliff_percentage) => write!(
+                f,
+                "cliff percentage must be between 0.0 and 1.0, got {}",
+                cliff_percentage
+            ),
+            VestingError::WeightsDoNotSumToOne(total) => {
+                write!(f, "vesting weights must sum to 1.0, got {}", total)
+            }
+            VestingError::UnevenDuration(duration) => write!(
+                f,
+                "{:?} is not a whole number of vesting intervals",
+                duration
+            ),
+            VestingError::MissingTerm(term) => write!(f, "missing required grant term {}", term),
+            VestingError::AmountOverflow => write!(f, "too many shares to represent"),
+            VestingError::BackdatedGrant {
+                grant_date,
+                approval_date,
+            } => write!(
+                f,
+                "grant date {} precedes its approval on {}",
+                grant_date, approval_date
+            ),
+        }
+    }
+}
+
+impl Error for VestingError {}
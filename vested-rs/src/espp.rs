@@ -0,0 +1,144 @@
+use crate::{EsppTransfer, LocalDate, Money, Price, ValuationProvider};
+
+/// An employee stock purchase plan offering, during which payroll contributions buy
+/// discounted shares on each purchase date.
+pub struct EsppOffering {
+    pub start_date: LocalDate,
+    pub purchase_dates: Vec<LocalDate>,
+    /// Discount (0.0 - 1.0) off the purchase price, e.g. 0.15 for 15%.
+    pub discount: f32,
+    /// Prices shares off the lower of the fair market value at the start of the offering
+    /// and on the purchase date.
+    pub lookback: bool,
+}
+
+/// Shares bought on one purchase date.
+#[derive(Debug, PartialEq)]
+pub struct EsppPurchase {
+    pub offering_start_date: LocalDate,
+    pub purchase_date: LocalDate,
+    pub fair_market_value_on_offering_start: Price,
+    pub fair_market_value_on_purchase_date: Price,
+    pub purchase_price: Price,
+    /// Price before any lookback, i.e. had the shares been bought at the offering start.
+    pub price_at_offering_start: Price,
+    pub shares: i64,
+    pub amount_spent: Money,
+    /// Contributions left over after buying whole shares, carried to the next purchase
+    /// date or refunded after the last one.
+    pub carried_forward: Money,
+    /// Bargain element taxed as compensation: the fair market value on the purchase date
+    /// less the price paid.
+    pub discount_income: Money,
+}
+
+impl EsppOffering {
+    /// Calculates the shares bought on each purchase date from the contributions made
+    /// during each purchase period, or `None` if a fair market value is missing.
+    pub fn calculate_purchases<V: ValuationProvider>(
+        &self,
+        contributions: &[Money],
+        provider: &V,
+    ) -> Option<Vec<EsppPurchase>> {
+        let fair_market_value_on_offering_start = provider.fmv_on(self.start_date)?;
+        let discounted = |price: Price| price.scale(1.0 - self.discount as f64);
+        let mut carried_forward = Money::zero(fair_market_value_on_offering_start.currency());
+
+        self.purchase_dates
+            .iter()
+            .zip(contributions)
+            .map(|(purchase_date, contribution)| {
+                let fair_market_value_on_purchase_date = provider.fmv_on(*purchase_date)?;
+                let purchase_price = if self.lookback
+                    && fair_market_value_on_offering_start < fair_market_value_on_purchase_date
+                {
+                    discounted(fair_market_value_on_offering_start)
+                } else {
+                    discounted(fair_market_value_on_purchase_date)
+                };
+
+                let available = carried_forward + *contribution;
+                let shares = purchase_price.shares_for(available);
+                let amount_spent = purchase_price * shares;
+                carried_forward = available - amount_spent;
+
+                Some(EsppPurchase {
+                    offering_start_date: self.start_date,
+                    purchase_date: *purchase_date,
+                    fair_market_value_on_offering_start,
+                    fair_market_value_on_purchase_date,
+                    purchase_price,
+                    price_at_offering_start: discounted(fair_market_value_on_offering_start),
+                    shares,
+                    amount_spent,
+                    carried_forward,
+                    discount_income: (fair_market_value_on_purchase_date - purchase_price) * shares,
+                })
+            })
+            .collect()
+    }
+}
+
+impl EsppPurchase {
+    /// Describes the purchase as a transfer of shares to the participant on the
+    /// purchase date, for Form 3922 reporting.
+    pub fn transfer(&self, participant: &str) -> EsppTransfer {
+        EsppTransfer {
+            participant: participant.to_string(),
+            grant_date: self.offering_start_date,
+            exercise_date: self.purchase_date,
+            fair_market_value_on_grant_date: self.fair_market_value_on_offering_start,
+            fair_market_value_on_exercise_date: self.fair_market_value_on_purchase_date,
+            price_paid_per_share: self.purchase_price,
+            price_if_exercised_on_grant_date: Some(self.price_at_offering_start),
+            shares: self.shares,
+            transfer_date: self.purchase_date,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EsppOffering, LocalDate};
+    use crate::{usd, usd_price, PriceTable};
+
+    #[test]
+    fn it_can_calculate_espp_purchases_with_lookback() {
+        let offering = EsppOffering {
+            start_date: LocalDate::from_ymd(2022, 1, 1),
+            purchase_dates: vec![
+                LocalDate::from_ymd(2022, 6, 30),
+                LocalDate::from_ymd(2022, 12, 31),
+            ],
+            discount: 0.15,
+            lookback: true,
+        };
+
+        let prices = PriceTable::new(vec![
+            (LocalDate::from_ymd(2022, 1, 1), usd_price(20.0)),
+            (LocalDate::from_ymd(2022, 6, 30), usd_price(30.0)),
+            (LocalDate::from_ymd(2022, 12, 31), usd_price(10.0)),
+        ]);
+
+        /*
+         * - 2022/6/30: priced off the $20 offering start, $17 per share, 294 shares for
+         *   $4,998 with $2 carried forward, $13 discount per share
+         * - 2022/12/31: priced off the $10 purchase date, $8.50 per share, 588 shares
+         */
+
+        let purchases = offering
+            .calculate_purchases(&[usd(5_000.0), usd(5_000.0)], &prices)
+            .unwrap();
+
+        assert_eq!(purchases[0].purchase_price, usd_price(17.0));
+        assert_eq!(purchases[0].shares, 294);
+        assert_eq!(purchases[0].carried_forward, usd(2.0));
+        assert_eq!(purchases[0].discount_income, usd(3_822.0));
+        assert_eq!(purchases[1].purchase_price, usd_price(8.5));
+        assert_eq!(purchases[1].shares, 588);
+        assert_eq!(
+            purchases[1].transfer("Sam").price_paid_per_share,
+            usd_price(8.5)
+        );
+    }
+}
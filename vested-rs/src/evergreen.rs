@@ -0,0 +1,153 @@
+use chrono::Datelike;
+
+use crate::{EquityPlan, Grant};
+
+/// An automatic increase to a plan's reserve on January 1 of each year, e.g. 5% of the
+/// company's outstanding shares.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EvergreenProvision {
+    /// Fraction of the outstanding shares added each year, e.g. 0.05.
+    pub percentage: f64,
+    /// Most shares a single increase may add.
+    pub max_shares: Option<i64>,
+    pub first_year: i32,
+    /// Last year the reserve increases, often 10 years after the plan is adopted.
+    pub last_year: i32,
+}
+
+/// A plan's reserve over one year of a forecast.
+#[derive(Debug, PartialEq)]
+pub struct ReserveForecast {
+    pub year: i32,
+    pub evergreen_increase: i64,
+    /// Shares projected to be granted during the year.
+    pub granted_amount: i64,
+    /// Shares left at the end of the year, negative once the pool is depleted.
+    pub remaining_reserve: i64,
+}
+
+impl EvergreenProvision {
+    /// Calculates the shares added to the reserve in the given year.
+    pub fn increase_for(&self, year: i32, outstanding_shares: i64) -> i64 {
+        if year < self.first_year || year > self.last_year {
+            return 0;
+        }
+
+        let increase = (outstanding_shares as f64 * self.percentage).floor() as i64;
+
+        match self.max_shares {
+            Some(max_shares) => increase.min(max_shares),
+            None => increase,
+        }
+    }
+}
+
+impl EquityPlan {
+    /// Adds the year's evergreen increase to the reserve, returning the shares added.
+    pub fn apply_evergreen(&mut self, year: i32, outstanding_shares: i64) -> i64 {
+        let increase = self.evergreen.map_or(0, |evergreen| {
+            evergreen.increase_for(year, outstanding_shares)
+        });
+
+        self.share_reserve += increase;
+
+        increase
+    }
+
+    /// Projects the remaining reserve at the end of each year from `from_year` through
+    /// `to_year`, replenished by the evergreen increase and drawn down by the projected
+    /// grants dated in that year. Assumes the outstanding share count stays the same.
+    pub fn forecast_reserve(
+        &self,
+        from_year: i32,
+        to_year: i32,
+        outstanding_shares: i64,
+        projected_grants: &[Grant],
+    ) -> Vec<ReserveForecast> {
+        let mut remaining_reserve = self.remaining_reserve();
+
+        (from_year..=to_year)
+            .map(|year| {
+                let evergreen_increase = self.evergreen.map_or(0, |evergreen| {
+                    evergreen.increase_for(year, outstanding_shares)
+                });
+                let granted_amount = projected_grants
+                    .iter()
+                    .filter(|grant| grant.grant_date.year() == year)
+                    .map(|grant| grant.amount)
+                    .sum();
+
+                remaining_reserve += evergreen_increase - granted_amount;
+
+                ReserveForecast {
+                    year,
+                    evergreen_increase,
+                    granted_amount,
+                    remaining_reserve,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EvergreenProvision, ReserveForecast};
+    use crate::{EquityPlan, Grant, GrantKind, LocalDate, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_forecast_a_reserve_with_evergreen_increases() {
+        let grant = |amount, year| {
+            Grant::for_test(
+                amount,
+                LocalDate::from_ymd(year, 3, 1),
+                VestingScheduleConfiguration::monthly(12, 0.25, 48),
+            )
+        };
+
+        let mut plan = EquityPlan::new("2021 Plan", 10_000, 48, vec![GrantKind::Rsu]);
+        plan.evergreen = Some(EvergreenProvision {
+            percentage: 0.05,
+            max_shares: Some(4_000),
+            first_year: 2022,
+            last_year: 2023,
+        });
+        plan.issue(grant(4_800, 2021)).unwrap();
+
+        /*
+         * 5% of 100000 outstanding shares is 5000, capped at 4000 a year through 2023.
+         */
+
+        assert_eq!(
+            plan.forecast_reserve(
+                2022,
+                2024,
+                100_000,
+                &[grant(6_000, 2022), grant(3_000, 2023), grant(2_000, 2024)]
+            ),
+            vec![
+                ReserveForecast {
+                    year: 2022,
+                    evergreen_increase: 4_000,
+                    granted_amount: 6_000,
+                    remaining_reserve: 3_200,
+                },
+                ReserveForecast {
+                    year: 2023,
+                    evergreen_increase: 4_000,
+                    granted_amount: 3_000,
+                    remaining_reserve: 4_200,
+                },
+                ReserveForecast {
+                    year: 2024,
+                    evergreen_increase: 0,
+                    granted_amount: 2_000,
+                    remaining_reserve: 2_200,
+                },
+            ]
+        );
+
+        assert_eq!(plan.apply_evergreen(2022, 60_000), 3_000);
+        assert_eq!(plan.remaining_reserve(), 8_200);
+    }
+}
@@ -0,0 +1,543 @@
+use std::error::Error;
+use std::fmt;
+
+use chrono::Duration;
+
+use crate::{Grant, LocalDate, Money, Price, VestingDuration};
+
+/// Shares bought under an option on one date.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Exercise {
+    pub date: LocalDate,
+    pub quantity: i64,
+}
+
+/// An option grant along with the exercises recorded against it.
+pub struct GrantLedger {
+    pub grant: Grant,
+    /// Lets the whole grant be exercised before it vests. The company may buy back
+    /// shares exercised early until they vest.
+    pub early_exercise: bool,
+    exercises: Vec<Exercise>,
+}
+
+/// A vesting period split into the shares vested and the shares exercised early but not
+/// yet vested.
+#[derive(Debug, PartialEq)]
+pub struct LedgerPeriod {
+    pub date: LocalDate,
+    pub cumulative_vested_amount: i64,
+    pub exercised_amount: i64,
+    /// Shares exercised ahead of vesting, which are still subject to repurchase.
+    pub exercised_unvested_amount: i64,
+}
+
+/// How long vested options stay exercisable.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExerciseWindow {
+    /// Time after termination to exercise, e.g. 90 days.
+    pub post_termination: VestingDuration,
+    /// Time after the grant date the options expire regardless, usually 10 years.
+    pub term: VestingDuration,
+}
+
+/// A vesting period annotated with whether the options vested by then can still be
+/// exercised.
+#[derive(Debug, PartialEq)]
+pub struct ExercisablePeriod {
+    pub date: LocalDate,
+    pub cumulative_vested_amount: i64,
+    /// The options expired before this date.
+    pub expired: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ExerciseError {
+    /// Only stock options and stock appreciation rights can be exercised.
+    NotExercisable,
+    NonPositiveQuantity(i64),
+    /// The exercise is for more shares than are vested and not yet exercised.
+    ExceedsExercisable {
+        date: LocalDate,
+        requested: i64,
+        exercisable: i64,
+    },
+}
+
+impl fmt::Display for ExerciseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExerciseError::NotExercisable => write!(
+                f,
+                "only stock options and stock appreciation rights can be exercised"
+            ),
+            ExerciseError::NonPositiveQuantity(quantity) => {
+                write!(f, "exercise quantity must be positive, got {}", quantity)
+            }
+            ExerciseError::ExceedsExercisable {
+                date,
+                requested,
+                exercisable,
+            } => write!(
+                f,
+                "can't exercise {} shares on {}, only {} are exercisable",
+                requested, date, exercisable
+            ),
+        }
+    }
+}
+
+impl Error for ExerciseError {}
+
+impl Grant {
+    /// Calculates the cost of exercising every share vested on the given date at the
+    /// strike price. `None` unless the grant is a stock option; stock appreciation
+    /// rights cost nothing to exercise and pay out their `intrinsic_value_on` instead.
+    pub fn exercise_cost_on(&self, date: LocalDate) -> Option<Money> {
+        if !self.grant_type.is_option() {
+            return None;
+        }
+
+        Some(self.strike_price()? * self.calculate_vested_shares(date))
+    }
+
+    /// Calculates the paper gain on the shares vested on the given date: what the fair
+    /// market value exceeds the strike price by, or zero when the grant is underwater.
+    /// `None` for grants without a strike price.
+    pub fn intrinsic_value_on(&self, date: LocalDate, fair_market_value: Price) -> Option<Money> {
+        let strike_price = self.strike_price()?;
+
+        if fair_market_value <= strike_price {
+            return Some(Money::zero(strike_price.currency()));
+        }
+
+        Some((fair_market_value - strike_price) * self.calculate_vested_shares(date))
+    }
+
+    /// Calculates the last day the vested options can be exercised for a holder who
+    /// left on the given date: the end of the post-termination window, or the end of
+    /// the options' term if that comes first.
+    pub fn exercise_expiration_date(
+        &self,
+        window: &ExerciseWindow,
+        termination_date: LocalDate,
+    ) -> LocalDate {
+        let term_expiration_date = self.grant_date + window.term.to_relative_duration();
+
+        (termination_date + window.post_termination.to_relative_duration())
+            .min(term_expiration_date)
+    }
+
+    /// Calculates the vesting periods, flagging the ones after the options expire.
+    /// Options on a grant that hasn't been terminated expire at the end of their term.
+    /// A terminated grant's schedule ends on the termination date, so it's followed by
+    /// a period on the day after the post-termination window closes, when the options
+    /// vested by then lapse.
+    pub fn calculate_exercisable_periods(&self, window: &ExerciseWindow) -> Vec<ExercisablePeriod> {
+        let expiration_date = match self.termination_date {
+            Some(termination_date) => self.exercise_expiration_date(window, termination_date),
+            None => self.grant_date + window.term.to_relative_duration(),
+        };
+
+        let mut periods: Vec<ExercisablePeriod> = self
+            .calculate_vesting_schedule()
+            .periods
+            .into_iter()
+            .map(|period| ExercisablePeriod {
+                date: period.date,
+                cumulative_vested_amount: period.cumulative_vested_amount,
+                expired: period.date > expiration_date,
+            })
+            .collect();
+
+        let lapse_date = expiration_date + Duration::days(1);
+
+        if let (Some(_), Some(last_period)) = (self.termination_date, periods.last()) {
+            if last_period.date < lapse_date {
+                periods.push(ExercisablePeriod {
+                    date: lapse_date,
+                    cumulative_vested_amount: last_period.cumulative_vested_amount,
+                    expired: true,
+                });
+            }
+        }
+
+        periods
+    }
+}
+
+impl GrantLedger {
+    pub fn new(grant: Grant) -> GrantLedger {
+        GrantLedger {
+            grant,
+            early_exercise: false,
+            exercises: Vec::new(),
+        }
+    }
+
+    /// The recorded exercises, in date order.
+    pub fn exercises(&self) -> &[Exercise] {
+        &self.exercises
+    }
+
+    /// Calculates the shares exercised on or before the given date.
+    pub fn exercised_on(&self, date: LocalDate) -> i64 {
+        self.exercises
+            .iter()
+            .filter(|exercise| exercise.date <= date)
+            .map(|exercise| exercise.quantity)
+            .sum()
+    }
+
+    /// Calculates the shares not yet exercised that can be on the given date: the
+    /// vested shares, or the whole grant for early exercise until it's terminated.
+    pub fn exercisable_on(&self, date: LocalDate) -> i64 {
+        let limit = if self.early_exercise && !self.grant.is_terminated_on(date) {
+            self.grant.amount
+        } else {
+            self.grant.calculate_vested_shares(date)
+        };
+
+        (limit - self.exercised_on(date)).max(0)
+    }
+
+    /// Calculates the shares exercised early that haven't vested on the given date,
+    /// which the company may buy back if the holder leaves.
+    pub fn repurchaseable_shares_on(&self, date: LocalDate) -> i64 {
+        (self.exercised_on(date) - self.grant.calculate_vested_shares(date)).max(0)
+    }
+
+    /// Calculates the grant's vesting schedule along with what was exercised by each
+    /// period.
+    pub fn calculate_ledger_periods(&self) -> Vec<LedgerPeriod> {
+        self.grant
+            .calculate_vesting_schedule()
+            .periods
+            .into_iter()
+            .map(|period| LedgerPeriod {
+                date: period.date,
+                cumulative_vested_amount: period.cumulative_vested_amount,
+                exercised_amount: self.exercised_on(period.date),
+                exercised_unvested_amount: self.repurchaseable_shares_on(period.date),
+            })
+            .collect()
+    }
+
+    /// Records an exercise, failing if it's for more shares than are exercisable. An
+    /// exercise dated before ones already recorded must also leave enough shares for
+    /// those.
+    pub fn record_exercise(&mut self, date: LocalDate, quantity: i64) -> Result<(), ExerciseError> {
+        if !self.grant.grant_type.is_exercisable() {
+            return Err(ExerciseError::NotExercisable);
+        }
+
+        if quantity <= 0 {
+            return Err(ExerciseError::NonPositiveQuantity(quantity));
+        }
+
+        let exercisable = self
+            .exercises
+            .iter()
+            .map(|exercise| exercise.date)
+            .filter(|exercise_date| *exercise_date > date)
+            .chain([date])
+            .map(|date| self.exercisable_on(date))
+            .min()
+            .unwrap_or(0);
+
+        if quantity > exercisable {
+            return Err(ExerciseError::ExceedsExercisable {
+                date,
+                requested: quantity,
+                exercisable,
+            });
+        }
+
+        let index = self
+            .exercises
+            .partition_point(|exercise| exercise.date <= date);
+        self.exercises.insert(index, Exercise { date, quantity });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ExercisablePeriod, Exercise, ExerciseError, ExerciseWindow, GrantLedger, LedgerPeriod,
+        LocalDate,
+    };
+    use crate::{usd, usd_price, Grant, GrantType, VestingDuration, VestingScheduleConfiguration};
+
+    const THREE_YEAR_TERM: ExerciseWindow = ExerciseWindow {
+        post_termination: VestingDuration::Days(90),
+        term: VestingDuration::Years(3),
+    };
+
+    fn iso_grant() -> Grant {
+        Grant {
+            grant_type: GrantType::Iso {
+                strike_price: usd_price(1.5),
+            },
+            ..Grant::for_test(
+                4_800,
+                LocalDate::from_ymd(2021, 1, 1),
+                VestingScheduleConfiguration::monthly(12, 0.25, 48),
+            )
+        }
+    }
+
+    #[test]
+    fn it_can_calculate_exercise_cost_and_intrinsic_value() {
+        let grant = Grant {
+            grant_type: GrantType::Nso {
+                strike_price: usd_price(1.5),
+            },
+            ..Grant::for_test(
+                4_800,
+                LocalDate::from_ymd(2021, 1, 1),
+                VestingScheduleConfiguration::monthly(12, 0.25, 48),
+            )
+        };
+        let date = LocalDate::from_ymd(2022, 1, 1);
+
+        assert_eq!(grant.exercise_cost_on(date), Some(usd(1_800.0)));
+        assert_eq!(
+            grant.intrinsic_value_on(date, usd_price(4.0)),
+            Some(usd(3_000.0))
+        );
+        assert_eq!(
+            grant.intrinsic_value_on(date, usd_price(1.0)),
+            Some(usd(0.0))
+        );
+    }
+
+    #[test]
+    fn it_only_values_grants_with_a_strike_price() {
+        let rsu = Grant::for_test(
+            4_800,
+            LocalDate::from_ymd(2021, 1, 1),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        );
+        let date = LocalDate::from_ymd(2022, 1, 1);
+
+        assert_eq!(rsu.exercise_cost_on(date), None);
+        assert_eq!(rsu.intrinsic_value_on(date, usd_price(4.0)), None);
+    }
+
+    #[test]
+    fn it_keeps_sub_cent_strike_prices() {
+        let grant = Grant {
+            grant_type: GrantType::Iso {
+                strike_price: usd_price(0.0001),
+            },
+            ..Grant::for_test(
+                12_400,
+                LocalDate::from_ymd(2021, 1, 1),
+                VestingScheduleConfiguration::monthly(12, 0.25, 48),
+            )
+        };
+        let date = LocalDate::from_ymd(2022, 1, 1);
+
+        /*
+         * 3,100 shares have vested at the cliff, costing 31 cents at a hundredth of a
+         * cent each.
+         */
+
+        assert_eq!(grant.exercise_cost_on(date), Some(usd(0.31)));
+        assert_eq!(
+            grant.intrinsic_value_on(date, usd_price(0.0005)),
+            Some(usd(1.24))
+        );
+    }
+
+    #[test]
+    fn it_can_exercise_stock_appreciation_rights() {
+        let grant = Grant {
+            grant_type: GrantType::Sar {
+                strike_price: usd_price(1.5),
+            },
+            ..Grant::for_test(
+                4_800,
+                LocalDate::from_ymd(2021, 1, 1),
+                VestingScheduleConfiguration::monthly(12, 0.25, 48),
+            )
+        };
+        let date = LocalDate::from_ymd(2022, 1, 1);
+
+        /*
+         * Exercising the 1,200 rights vested at the cliff costs nothing and settles
+         * their $1 gain each at a $2.50 fair market value.
+         */
+
+        assert_eq!(grant.exercise_cost_on(date), None);
+        assert_eq!(
+            grant.intrinsic_value_on(date, usd_price(2.5)),
+            Some(usd(1_200.0))
+        );
+
+        let mut ledger = GrantLedger::new(grant);
+
+        assert_eq!(ledger.record_exercise(date, 1_200), Ok(()));
+        assert_eq!(ledger.exercisable_on(date), 0);
+
+        let mut rsu_ledger = GrantLedger::new(Grant::for_test(
+            4_800,
+            LocalDate::from_ymd(2021, 1, 1),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        ));
+
+        assert_eq!(
+            rsu_ledger.record_exercise(date, 1_200),
+            Err(ExerciseError::NotExercisable)
+        );
+    }
+
+    #[test]
+    fn it_can_record_exercises_against_vested_shares() {
+        let mut ledger = GrantLedger::new(iso_grant());
+
+        ledger
+            .record_exercise(LocalDate::from_ymd(2022, 6, 1), 1_000)
+            .unwrap();
+
+        assert_eq!(ledger.exercisable_on(LocalDate::from_ymd(2022, 6, 1)), 700);
+        assert_eq!(
+            ledger.record_exercise(LocalDate::from_ymd(2022, 6, 1), 800),
+            Err(ExerciseError::ExceedsExercisable {
+                date: LocalDate::from_ymd(2022, 6, 1),
+                requested: 800,
+                exercisable: 700,
+            })
+        );
+
+        /*
+         * 1200 shares vested by 2022/1/1, but exercising more than 700 then would leave
+         * too few for the 1000 share exercise already recorded on 2022/6/1.
+         */
+
+        assert_eq!(
+            ledger.record_exercise(LocalDate::from_ymd(2022, 1, 1), 800),
+            Err(ExerciseError::ExceedsExercisable {
+                date: LocalDate::from_ymd(2022, 1, 1),
+                requested: 800,
+                exercisable: 700,
+            })
+        );
+
+        ledger
+            .record_exercise(LocalDate::from_ymd(2022, 1, 1), 300)
+            .unwrap();
+
+        assert_eq!(
+            ledger.exercises()[0],
+            Exercise {
+                date: LocalDate::from_ymd(2022, 1, 1),
+                quantity: 300,
+            }
+        );
+        assert_eq!(ledger.exercisable_on(LocalDate::from_ymd(2022, 6, 1)), 400);
+    }
+
+    #[test]
+    fn it_can_find_when_options_expire() {
+        let grant = iso_grant();
+
+        assert_eq!(
+            grant.exercise_expiration_date(&THREE_YEAR_TERM, LocalDate::from_ymd(2022, 3, 1)),
+            LocalDate::from_ymd(2022, 5, 30)
+        );
+        assert_eq!(
+            grant.exercise_expiration_date(&THREE_YEAR_TERM, LocalDate::from_ymd(2023, 12, 1)),
+            LocalDate::from_ymd(2024, 1, 1)
+        );
+    }
+
+    #[test]
+    fn it_flags_periods_vesting_after_the_options_term() {
+        /*
+         * The options expire after 3 years, before the last year of the schedule vests.
+         */
+
+        let periods = iso_grant().calculate_exercisable_periods(&THREE_YEAR_TERM);
+
+        assert!(!periods[36].expired);
+        assert!(periods[37].expired);
+    }
+
+    #[test]
+    fn it_flags_when_options_lapse_after_termination() {
+        let mut grant = iso_grant();
+
+        /*
+         * Leaving on 2022/3/1 with 1,400 shares vested, the options can be exercised
+         * until 2022/5/30 and lapse the day after.
+         */
+
+        grant.terminate(LocalDate::from_ymd(2022, 3, 1));
+
+        let periods = grant.calculate_exercisable_periods(&THREE_YEAR_TERM);
+
+        assert!(!periods[14].expired);
+        assert_eq!(
+            periods.last(),
+            Some(&ExercisablePeriod {
+                date: LocalDate::from_ymd(2022, 5, 31),
+                cumulative_vested_amount: 1_400,
+                expired: true,
+            })
+        );
+    }
+
+    #[test]
+    fn it_can_exercise_early() {
+        let grant = Grant {
+            grant_type: GrantType::Iso {
+                strike_price: usd_price(0.1),
+            },
+            ..Grant::for_test(
+                4_800,
+                LocalDate::from_ymd(2021, 1, 1),
+                VestingScheduleConfiguration::monthly(12, 0.25, 48),
+            )
+        };
+        let mut ledger = GrantLedger::new(grant);
+
+        assert_eq!(
+            ledger.record_exercise(LocalDate::from_ymd(2021, 1, 1), 4_800),
+            Err(ExerciseError::ExceedsExercisable {
+                date: LocalDate::from_ymd(2021, 1, 1),
+                requested: 4_800,
+                exercisable: 0,
+            })
+        );
+
+        ledger.early_exercise = true;
+        ledger
+            .record_exercise(LocalDate::from_ymd(2021, 1, 1), 4_800)
+            .unwrap();
+
+        assert_eq!(
+            ledger.repurchaseable_shares_on(LocalDate::from_ymd(2022, 1, 1)),
+            3_600
+        );
+        assert_eq!(
+            ledger.calculate_ledger_periods()[13],
+            LedgerPeriod {
+                date: LocalDate::from_ymd(2022, 2, 1),
+                cumulative_vested_amount: 1_300,
+                exercised_amount: 4_800,
+                exercised_unvested_amount: 3_500,
+            }
+        );
+
+        ledger.grant.terminate(LocalDate::from_ymd(2022, 1, 1));
+
+        assert_eq!(
+            ledger.repurchaseable_shares_on(LocalDate::from_ymd(2023, 1, 1)),
+            3_600
+        );
+        assert_eq!(ledger.exercisable_on(LocalDate::from_ymd(2023, 1, 1)), 0);
+    }
+}
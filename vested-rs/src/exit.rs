@@ -0,0 +1,511 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{Grant, LocalDate, Money, Price};
+
+/// How far the escrow releases may exceed the whole proceeds due to rounding.
+const HOLDBACK_TOLERANCE: f32 = 0.0001;
+
+/// How far the scenario probabilities may exceed certainty due to rounding.
+const PROBABILITY_TOLERANCE: f32 = 0.0001;
+
+/// A portion of exit proceeds held back at close and released on a later date.
+pub struct EscrowRelease {
+    pub date: LocalDate,
+    /// Fraction of the gross proceeds (0.0 - 1.0) released on this date.
+    pub percentage: f32,
+}
+
+/// An exit event (acquisition, tender, etc.) paying out a fixed price per vested share.
+pub struct Exit {
+    pub date: LocalDate,
+    pub price_per_share: Price,
+    /// Holdback releases; the sum of their percentages is withheld at close.
+    pub escrow: Vec<EscrowRelease>,
+}
+
+#[derive(Debug, PartialEq, PartialOrd)]
+pub struct ProceedsPayment {
+    pub date: LocalDate,
+    pub amount: Money,
+}
+
+pub struct ProceedsSchedule {
+    pub gross_proceeds: Money,
+    pub payments: Vec<ProceedsPayment>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum EscrowError {
+    /// A release's percentage is outside 0.0 - 1.0.
+    PercentageOutOfRange(f32),
+    /// The releases together hold back more than the whole proceeds.
+    HoldbackExceedsProceeds(f32),
+    /// A release is dated before the exit closes, when there's nothing in escrow yet.
+    ReleaseBeforeClose {
+        release_date: LocalDate,
+        close_date: LocalDate,
+    },
+}
+
+impl fmt::Display for EscrowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EscrowError::PercentageOutOfRange(percentage) => write!(
+                f,
+                "escrow release percentage must be between 0.0 and 1.0, got {}",
+                percentage
+            ),
+            EscrowError::HoldbackExceedsProceeds(total) => {
+                write!(f, "escrow releases must sum to at most 1.0, got {}", total)
+            }
+            EscrowError::ReleaseBeforeClose {
+                release_date,
+                close_date,
+            } => write!(
+                f,
+                "escrow release on {} is before the exit closes on {}",
+                release_date, close_date
+            ),
+        }
+    }
+}
+
+impl Error for EscrowError {}
+
+#[derive(Debug, PartialEq)]
+pub enum ExitScenarioError {
+    InvalidExit(EscrowError),
+    /// A scenario's probability is outside 0.0 - 1.0.
+    ProbabilityOutOfRange(f32),
+    /// The scenarios together are more than certain to happen.
+    ProbabilitiesExceedOne(f32),
+}
+
+impl fmt::Display for ExitScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExitScenarioError::InvalidExit(error) => write!(f, "invalid exit: {}", error),
+            ExitScenarioError::ProbabilityOutOfRange(probability) => write!(
+                f,
+                "exit scenario probability must be between 0.0 and 1.0, got {}",
+                probability
+            ),
+            ExitScenarioError::ProbabilitiesExceedOne(total) => write!(
+                f,
+                "exit scenario probabilities must sum to at most 1.0, got {}",
+                total
+            ),
+        }
+    }
+}
+
+impl Error for ExitScenarioError {}
+
+impl From<EscrowError> for ExitScenarioError {
+    fn from(error: EscrowError) -> ExitScenarioError {
+        ExitScenarioError::InvalidExit(error)
+    }
+}
+
+/// One possible exit, weighted by how likely it is to happen.
+pub struct ExitScenario {
+    /// Probability (0.0 - 1.0) of this exit happening. Whatever isn't covered by the
+    /// scenarios is treated as no exit at all.
+    pub probability: f32,
+    pub exit: Exit,
+}
+
+/// Probability-weighted proceeds expected to have been paid out by a given date.
+#[derive(Debug, PartialEq, PartialOrd)]
+pub struct ExpectedValuePoint {
+    pub date: LocalDate,
+    pub cumulative_expected_value: Money,
+}
+
+impl ExitScenario {
+    /// Checks that every scenario's probability is between 0.0 and 1.0 and that
+    /// together they're no more than certain, leaving the rest to no exit at all.
+    pub fn validate_all(scenarios: &[ExitScenario]) -> Result<(), ExitScenarioError> {
+        if let Some(scenario) = scenarios
+            .iter()
+            .find(|scenario| !(0.0..=1.0).contains(&scenario.probability))
+        {
+            return Err(ExitScenarioError::ProbabilityOutOfRange(
+                scenario.probability,
+            ));
+        }
+
+        let total_probability: f32 = scenarios.iter().map(|scenario| scenario.probability).sum();
+
+        if total_probability > 1.0 + PROBABILITY_TOLERANCE {
+            return Err(ExitScenarioError::ProbabilitiesExceedOne(total_probability));
+        }
+
+        Ok(())
+    }
+}
+
+impl ProceedsSchedule {
+    /// Returns the cumulative amount paid out on or before the given date.
+    pub fn paid_on(&self, date: LocalDate) -> Money {
+        Money::sum(
+            self.gross_proceeds.currency(),
+            self.payments
+                .iter()
+                .filter(|payment| payment.date <= date)
+                .map(|payment| payment.amount),
+        )
+    }
+}
+
+impl Exit {
+    /// Checks that every release is a fraction of the proceeds released on or after
+    /// close, and that together they hold back no more than the whole amount.
+    pub fn validate(&self) -> Result<(), EscrowError> {
+        if let Some(release) = self.escrow.iter().find(|release| release.date < self.date) {
+            return Err(EscrowError::ReleaseBeforeClose {
+                release_date: release.date,
+                close_date: self.date,
+            });
+        }
+
+        if let Some(release) = self
+            .escrow
+            .iter()
+            .find(|release| !(0.0..=1.0).contains(&release.percentage))
+        {
+            return Err(EscrowError::PercentageOutOfRange(release.percentage));
+        }
+
+        let holdback_percentage = self.holdback_percentage();
+
+        if holdback_percentage > 1.0 + HOLDBACK_TOLERANCE {
+            return Err(EscrowError::HoldbackExceedsProceeds(holdback_percentage));
+        }
+
+        Ok(())
+    }
+
+    /// Total fraction of proceeds withheld at close.
+    fn holdback_percentage(&self) -> f32 {
+        self.escrow.iter().map(|release| release.percentage).sum()
+    }
+}
+
+impl Grant {
+    /// Calculates the proceeds paid for the shares vested at exit, split into the
+    /// amount paid at close followed by each escrow release in date order. Rounding
+    /// leftovers go to the last release, so the payments add up to the gross proceeds.
+    pub fn calculate_exit_proceeds(&self, exit: &Exit) -> Result<ProceedsSchedule, EscrowError> {
+        exit.validate()?;
+
+        let vested_shares = self.calculate_vested_shares(exit.date);
+        let gross_proceeds = exit.price_per_share * vested_shares;
+        let holdback_percentage = exit.holdback_percentage().min(1.0);
+
+        let mut payments = vec![ProceedsPayment {
+            date: exit.date,
+            amount: gross_proceeds.scale(1.0 - holdback_percentage as f64),
+        }];
+
+        let mut releases: Vec<&EscrowRelease> = exit.escrow.iter().collect();
+        releases.sort_by_key(|release| release.date);
+
+        payments.extend(releases.into_iter().map(|release| ProceedsPayment {
+            date: release.date,
+            amount: gross_proceeds.scale(release.percentage as f64),
+        }));
+
+        if payments.len() > 1 {
+            let paid = Money::sum(
+                gross_proceeds.currency(),
+                payments.iter().map(|payment| payment.amount),
+            );
+
+            if let Some(last_release) = payments.last_mut() {
+                last_release.amount += gross_proceeds - paid;
+            }
+        }
+
+        Ok(ProceedsSchedule {
+            gross_proceeds,
+            payments,
+        })
+    }
+
+    /// Calculates the probability-weighted value of the grant across exit scenarios, or
+    /// `None` if there are no scenarios. All scenarios must use the same currency.
+    pub fn calculate_expected_value(
+        &self,
+        scenarios: &[ExitScenario],
+    ) -> Result<Option<Money>, ExitScenarioError> {
+        ExitScenario::validate_all(scenarios)?;
+
+        let mut expected_value: Option<Money> = None;
+
+        for scenario in scenarios {
+            let scenario_value = self
+                .calculate_exit_proceeds(&scenario.exit)?
+                .gross_proceeds
+                .scale(scenario.probability as f64);
+
+            expected_value =
+                Some(expected_value.map_or(scenario_value, |total| total + scenario_value));
+        }
+
+        Ok(expected_value)
+    }
+
+    /// Calculates how the probability-weighted value accrues over time, with a point on
+    /// every date any scenario pays out, including escrow releases.
+    pub fn calculate_expected_value_timeline(
+        &self,
+        scenarios: &[ExitScenario],
+    ) -> Result<Vec<ExpectedValuePoint>, ExitScenarioError> {
+        ExitScenario::validate_all(scenarios)?;
+
+        let mut payments: Vec<(LocalDate, Money)> = Vec::new();
+
+        for scenario in scenarios {
+            payments.extend(
+                self.calculate_exit_proceeds(&scenario.exit)?
+                    .payments
+                    .into_iter()
+                    .map(|payment| {
+                        (
+                            payment.date,
+                            payment.amount.scale(scenario.probability as f64),
+                        )
+                    }),
+            );
+        }
+
+        payments.sort_by_key(|(date, _)| *date);
+
+        let mut timeline: Vec<ExpectedValuePoint> = Vec::new();
+        let mut running_total: Option<Money> = None;
+
+        for (date, expected_value) in payments {
+            let cumulative_expected_value =
+                running_total.map_or(expected_value, |total| total + expected_value);
+            running_total = Some(cumulative_expected_value);
+
+            match timeline.last_mut() {
+                Some(point) if point.date == date => {
+                    point.cumulative_expected_value = cumulative_expected_value
+                }
+                _ => timeline.push(ExpectedValuePoint {
+                    date,
+                    cumulative_expected_value,
+                }),
+            }
+        }
+
+        Ok(timeline)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EscrowError, EscrowRelease, Exit, ExitScenario, ExitScenarioError, LocalDate};
+    use crate::{usd, usd_price, Grant, VestingScheduleConfiguration};
+
+    fn four_year_grant() -> Grant {
+        Grant::for_test(
+            10_000,
+            LocalDate::from_ymd(2020, 2, 6),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        )
+    }
+
+    #[test]
+    fn it_can_calculate_exit_proceeds_with_escrow() {
+        let grant = four_year_grant();
+
+        let exit = Exit {
+            date: LocalDate::from_ymd(2022, 2, 6),
+            price_per_share: usd_price(10.0),
+            escrow: vec![
+                EscrowRelease {
+                    date: LocalDate::from_ymd(2023, 8, 6),
+                    percentage: 0.05,
+                },
+                EscrowRelease {
+                    date: LocalDate::from_ymd(2023, 2, 6),
+                    percentage: 0.10,
+                },
+            ],
+        };
+
+        /*
+         * 5,000 shares vested at exit, $50,000 gross proceeds.
+         * - 2022/2/6: 85% paid at close, $42,500
+         * - 2023/2/6: 10% released from escrow, $5,000
+         * - 2023/8/6: 5% released from escrow, $2,500
+         */
+
+        let proceeds = grant.calculate_exit_proceeds(&exit).unwrap();
+
+        assert_eq!(proceeds.gross_proceeds, usd(50_000.0));
+        assert_eq!(proceeds.payments.len(), 3);
+        assert_eq!(proceeds.payments[1].date, LocalDate::from_ymd(2023, 2, 6));
+        assert_eq!(
+            proceeds.paid_on(LocalDate::from_ymd(2022, 2, 6)),
+            usd(42_500.0)
+        );
+        assert_eq!(
+            proceeds.paid_on(LocalDate::from_ymd(2023, 3, 1)),
+            usd(47_500.0)
+        );
+        assert_eq!(
+            proceeds.paid_on(LocalDate::from_ymd(2024, 1, 1)),
+            usd(50_000.0)
+        );
+    }
+
+    #[test]
+    fn it_pays_rounding_leftovers_in_the_last_escrow_release() {
+        let grant = four_year_grant();
+
+        /*
+         * Thirds of $100 don't split evenly into cents, so the last release pays the
+         * extra cent.
+         */
+
+        let thirds = Exit {
+            date: LocalDate::from_ymd(2022, 2, 6),
+            price_per_share: usd_price(0.02),
+            escrow: (1..=3)
+                .map(|year| EscrowRelease {
+                    date: LocalDate::from_ymd(2022 + year, 2, 6),
+                    percentage: 1.0 / 3.0,
+                })
+                .collect(),
+        };
+        let proceeds = grant.calculate_exit_proceeds(&thirds).unwrap();
+
+        assert_eq!(proceeds.payments[1].amount, usd(33.33));
+        assert_eq!(proceeds.payments[3].amount, usd(33.34));
+        assert_eq!(
+            proceeds.paid_on(LocalDate::from_ymd(2025, 2, 6)),
+            usd(100.0)
+        );
+    }
+
+    #[test]
+    fn it_rejects_escrow_holding_back_more_than_the_proceeds() {
+        let grant = four_year_grant();
+
+        let overdrawn = Exit {
+            date: LocalDate::from_ymd(2022, 2, 6),
+            price_per_share: usd_price(10.0),
+            escrow: vec![
+                EscrowRelease {
+                    date: LocalDate::from_ymd(2023, 2, 6),
+                    percentage: 0.6,
+                },
+                EscrowRelease {
+                    date: LocalDate::from_ymd(2024, 2, 6),
+                    percentage: 0.6,
+                },
+            ],
+        };
+
+        assert!(matches!(
+            grant.calculate_exit_proceeds(&overdrawn),
+            Err(EscrowError::HoldbackExceedsProceeds(_))
+        ));
+    }
+
+    #[test]
+    fn it_rejects_escrow_released_before_close() {
+        let grant = four_year_grant();
+
+        let exit = Exit {
+            date: LocalDate::from_ymd(2022, 2, 6),
+            price_per_share: usd_price(10.0),
+            escrow: vec![EscrowRelease {
+                date: LocalDate::from_ymd(2021, 8, 6),
+                percentage: 0.1,
+            }],
+        };
+
+        assert_eq!(
+            exit.validate(),
+            Err(EscrowError::ReleaseBeforeClose {
+                release_date: LocalDate::from_ymd(2021, 8, 6),
+                close_date: LocalDate::from_ymd(2022, 2, 6),
+            })
+        );
+        assert!(grant.calculate_exit_proceeds(&exit).is_err());
+    }
+
+    #[test]
+    fn it_can_calculate_expected_value_across_exit_scenarios() {
+        let grant = four_year_grant();
+
+        let scenarios = vec![
+            ExitScenario {
+                probability: 0.2,
+                exit: Exit {
+                    date: LocalDate::from_ymd(2022, 2, 6),
+                    price_per_share: usd_price(10.0),
+                    escrow: vec![EscrowRelease {
+                        date: LocalDate::from_ymd(2023, 2, 6),
+                        percentage: 0.1,
+                    }],
+                },
+            },
+            ExitScenario {
+                probability: 0.1,
+                exit: Exit {
+                    date: LocalDate::from_ymd(2025, 1, 1),
+                    price_per_share: usd_price(50.0),
+                    escrow: vec![],
+                },
+            },
+        ];
+
+        /*
+         * - 20%: 5,000 shares vested at $10, $50,000 of which $5,000 is held in escrow
+         * - 10%: 10,000 shares vested at $50, $500,000
+         * - 70%: no exit
+         */
+
+        assert_eq!(
+            grant.calculate_expected_value(&scenarios),
+            Ok(Some(usd(60_000.0)))
+        );
+
+        let timeline = grant.calculate_expected_value_timeline(&scenarios).unwrap();
+
+        assert_eq!(timeline.len(), 3);
+        assert_eq!(timeline[1].date, LocalDate::from_ymd(2023, 2, 6));
+        assert_eq!(timeline[0].cumulative_expected_value, usd(9_000.0));
+        assert_eq!(timeline[1].cumulative_expected_value, usd(10_000.0));
+        assert_eq!(timeline[2].cumulative_expected_value, usd(60_000.0));
+    }
+
+    #[test]
+    fn it_rejects_exit_scenarios_with_invalid_probabilities() {
+        let grant = four_year_grant();
+        let scenario = |probability| ExitScenario {
+            probability,
+            exit: Exit {
+                date: LocalDate::from_ymd(2022, 2, 6),
+                price_per_share: usd_price(10.0),
+                escrow: vec![],
+            },
+        };
+
+        assert_eq!(
+            grant.calculate_expected_value(&[scenario(-0.2)]),
+            Err(ExitScenarioError::ProbabilityOutOfRange(-0.2))
+        );
+        assert_eq!(
+            grant.calculate_expected_value_timeline(&[scenario(0.75), scenario(0.5)]),
+            Err(ExitScenarioError::ProbabilitiesExceedOne(1.25))
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use crate::{Currency, LocalDate, PriceTable};
+
+/// Downloads daily closing prices for a public company from an HTTP endpoint.
+///
+/// The endpoint must respond with `date,price` CSV as accepted by
+/// `PriceTable::parse_csv`. Its URL may contain `{symbol}`, `{from}` and `{to}`
+/// placeholders, which are replaced with the ticker symbol and ISO dates.
+pub struct PriceFetcher {
+    endpoint: String,
+    currency: Currency,
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    Http(Box<ureq::Error>),
+    Io(io::Error),
+    /// The response body isn't a valid price table.
+    MalformedResponse,
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::Http(error) => write!(f, "price request failed: {}", error),
+            FetchError::Io(error) => write!(f, "could not read price response: {}", error),
+            FetchError::MalformedResponse => write!(f, "price response is not a valid price table"),
+        }
+    }
+}
+
+impl Error for FetchError {}
+
+impl PriceFetcher {
+    pub fn new<S: Into<String>>(endpoint: S, currency: Currency) -> PriceFetcher {
+        PriceFetcher {
+            endpoint: endpoint.into(),
+            currency,
+        }
+    }
+
+    fn url(&self, symbol: &str, from_date: LocalDate, to_date: LocalDate) -> String {
+        self.endpoint
+            .replace("{symbol}", symbol)
+            .replace("{from}", &from_date.to_string())
+            .replace("{to}", &to_date.to_string())
+    }
+
+    /// Fetches the closing prices of the symbol between the two dates, inclusive.
+    pub fn fetch(
+        &self,
+        symbol: &str,
+        from_date: LocalDate,
+        to_date: LocalDate,
+    ) -> Result<PriceTable, FetchError> {
+        let body = ureq::get(&self.url(symbol, from_date, to_date))
+            .call()
+            .map_err(|error| FetchError::Http(Box::new(error)))?
+            .into_string()
+            .map_err(FetchError::Io)?;
+
+        PriceTable::parse_csv(&body, self.currency).ok_or(FetchError::MalformedResponse)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Currency, LocalDate, PriceFetcher};
+
+    #[test]
+    fn it_fills_in_the_endpoint_placeholders() {
+        let fetcher = PriceFetcher::new(
+            "https://prices.example.com/{symbol}.csv?from={from}&to={to}",
+            Currency::Usd,
+        );
+
+        assert_eq!(
+            fetcher.url(
+                "ACME",
+                LocalDate::from_ymd(2021, 1, 1),
+                LocalDate::from_ymd(2021, 12, 31)
+            ),
+            "https://prices.example.com/ACME.csv?from=2021-01-01&to=2021-12-31"
+        );
+    }
+}
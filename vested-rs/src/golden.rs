@@ -0,0 +1,216 @@
+use crate::{Grant, GrantType, LocalDate, VestingPeriod, VestingScheduleConfiguration};
+
+/// Canonical grant terms and the schedule every implementation must produce for them.
+const GOLDEN_VECTORS: [&str; 4] = [
+    include_str!("../vectors/monthly_six_month_cliff.txt"),
+    include_str!("../vectors/monthly_no_cliff.txt"),
+    include_str!("../vectors/monthly_four_year_one_year_cliff.txt"),
+    include_str!("../vectors/monthly_uneven_split.txt"),
+];
+
+#[derive(Debug, PartialEq)]
+pub struct GoldenVector {
+    pub name: String,
+    pub amount: i64,
+    pub grant_date: LocalDate,
+    pub cliff: i32,
+    pub cliff_percentage: f32,
+    pub length: i32,
+    pub expected_periods: Vec<VestingPeriod>,
+}
+
+/// The first difference found between a computed schedule and a golden vector.
+#[derive(Debug, PartialEq)]
+pub enum GoldenVectorMismatch {
+    PeriodCount {
+        expected: usize,
+        actual: usize,
+    },
+    Period {
+        index: usize,
+        expected: VestingPeriod,
+        actual: VestingPeriod,
+    },
+}
+
+impl GoldenVector {
+    /// Parses a vector made of `key = value` grant terms followed by one
+    /// `date,cumulative_vested_amount` line per period. Blank lines and lines
+    /// starting with `#` are ignored. The older `cliff` and `length` keys are still
+    /// accepted in place of `cliff_months` and `total_vesting_months`.
+    pub fn parse(source: &str) -> Option<GoldenVector> {
+        let mut name = None;
+        let mut amount = None;
+        let mut grant_date = None;
+        let mut cliff = None;
+        let mut cliff_percentage = None;
+        let mut length = None;
+        let mut expected_periods = Vec::new();
+
+        for line in source.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some((key, value)) = line.split_once('=') {
+                let value = value.trim();
+
+                match key.trim() {
+                    "name" => name = Some(value.to_string()),
+                    "amount" => amount = value.parse().ok(),
+                    "grant_date" => grant_date = value.parse().ok(),
+                    "interval" if value != "monthly" => return None,
+                    "interval" => {}
+                    "cliff_months" | "cliff" => cliff = value.parse().ok(),
+                    "cliff_percentage" => cliff_percentage = value.parse().ok(),
+                    "total_vesting_months" | "length" => length = value.parse().ok(),
+                    _ => return None,
+                }
+            } else {
+                let (date, cumulative_vested_amount) = line.split_once(',')?;
+                let cumulative_vested_amount: i64 = cumulative_vested_amount.trim().parse().ok()?;
+
+                expected_periods.push(VestingPeriod {
+                    date: date.trim().parse().ok()?,
+                    cumulative_vested_amount,
+                    vested_this_period: 0,
+                    precise_cumulative_amount: cumulative_vested_amount as f64,
+                });
+            }
+        }
+
+        VestingPeriod::update_vested_this_period(&mut expected_periods);
+
+        Some(GoldenVector {
+            name: name?,
+            amount: amount?,
+            grant_date: grant_date?,
+            cliff: cliff?,
+            cliff_percentage: cliff_percentage?,
+            length: length?,
+            expected_periods,
+        })
+    }
+
+    /// Builds the grant described by the vector's terms.
+    pub fn grant(&self) -> Grant {
+        Grant {
+            amount: self.amount,
+            grant_date: self.grant_date,
+            vesting_schedule: VestingScheduleConfiguration::monthly(
+                self.cliff,
+                self.cliff_percentage,
+                self.length,
+            ),
+            termination_date: None,
+            vesting_commencement_date: None,
+            grant_type: GrantType::default(),
+        }
+    }
+
+    /// Checks a schedule computed by another implementation against the vector. Vectors
+    /// only list whole shares, so precise amounts aren't compared.
+    pub fn verify(&self, periods: &[VestingPeriod]) -> Result<(), GoldenVectorMismatch> {
+        if periods.len() != self.expected_periods.len() {
+            return Err(GoldenVectorMismatch::PeriodCount {
+                expected: self.expected_periods.len(),
+                actual: periods.len(),
+            });
+        }
+
+        for (index, (expected, actual)) in self.expected_periods.iter().zip(periods).enumerate() {
+            if (
+                expected.date,
+                expected.cumulative_vested_amount,
+                expected.vested_this_period,
+            ) != (
+                actual.date,
+                actual.cumulative_vested_amount,
+                actual.vested_this_period,
+            ) {
+                return Err(GoldenVectorMismatch::Period {
+                    index,
+                    expected: expected.clone(),
+                    actual: actual.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the canonical test vectors shipped with the crate.
+pub fn golden_vectors() -> Vec<GoldenVector> {
+    GOLDEN_VECTORS
+        .iter()
+        .map(|source| GoldenVector::parse(source).expect("golden vectors are well-formed"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{golden_vectors, GoldenVector, GoldenVectorMismatch, LocalDate};
+    use crate::VestingPeriod;
+
+    #[test]
+    fn it_matches_the_golden_vectors() {
+        for vector in golden_vectors() {
+            let vesting_schedule = vector.grant().calculate_vesting_schedule();
+
+            assert_eq!(
+                vector.verify(&vesting_schedule.periods),
+                Ok(()),
+                "{}",
+                vector.name
+            );
+        }
+    }
+
+    #[test]
+    fn it_reports_golden_vector_mismatches() {
+        let vector = &golden_vectors()[1];
+        let mut periods = vector.grant().calculate_vesting_schedule().periods;
+
+        periods[2].cumulative_vested_amount += 1;
+
+        assert_eq!(
+            vector.verify(&periods),
+            Err(GoldenVectorMismatch::Period {
+                index: 2,
+                expected: VestingPeriod {
+                    date: LocalDate::from_ymd(2020, 3, 15),
+                    cumulative_vested_amount: 600,
+                    vested_this_period: 300,
+                    precise_cumulative_amount: 600.0,
+                },
+                actual: VestingPeriod {
+                    date: LocalDate::from_ymd(2020, 3, 15),
+                    cumulative_vested_amount: 601,
+                    vested_this_period: 300,
+                    precise_cumulative_amount: 600.0,
+                },
+            })
+        );
+
+        assert_eq!(
+            vector.verify(&periods[..3]),
+            Err(GoldenVectorMismatch::PeriodCount {
+                expected: 5,
+                actual: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn it_accepts_legacy_term_names() {
+        let legacy = "name = legacy\namount = 1200\ngrant_date = 2020-01-15\n\
+                      interval = monthly\ncliff = 0\ncliff_percentage = 0\nlength = 4\n";
+        let current = legacy
+            .replace("cliff =", "cliff_months =")
+            .replace("length =", "total_vesting_months =");
+
+        assert_eq!(GoldenVector::parse(legacy), GoldenVector::parse(&current));
+        assert_eq!(GoldenVector::parse(legacy).unwrap().length, 4);
+    }
+}
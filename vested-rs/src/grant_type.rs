@@ -0,0 +1,178 @@
+use crate::{Grant, LocalDate, Price, RepurchaseNotice};
+
+/// Kind of equity award, along with the terms specific to it.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum GrantType {
+    /// Incentive stock option.
+    Iso { strike_price: Price },
+    /// Non-qualified stock option.
+    Nso { strike_price: Price },
+    /// Stock appreciation right, paying out the gain over the strike price.
+    Sar { strike_price: Price },
+    /// Restricted stock units, delivered as shares once they vest.
+    Rsu {
+        /// Dates vested units are delivered on, e.g. quarterly settlement windows.
+        /// Units settle as soon as they vest when empty.
+        settlement_dates: Vec<LocalDate>,
+    },
+    /// Restricted stock bought up front and reverse vesting: the company may buy back
+    /// unvested shares at the purchase price when the holder leaves.
+    Rsa { purchase_price: Price },
+}
+
+/// The kind of a `GrantType` without its terms, e.g. to list what a plan may issue.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GrantKind {
+    Iso,
+    Nso,
+    Sar,
+    Rsu,
+    Rsa,
+}
+
+impl Default for GrantType {
+    /// Restricted stock units settling as they vest.
+    fn default() -> GrantType {
+        GrantType::Rsu {
+            settlement_dates: Vec::new(),
+        }
+    }
+}
+
+impl GrantType {
+    pub fn kind(&self) -> GrantKind {
+        match self {
+            GrantType::Iso { .. } => GrantKind::Iso,
+            GrantType::Nso { .. } => GrantKind::Nso,
+            GrantType::Sar { .. } => GrantKind::Sar,
+            GrantType::Rsu { .. } => GrantKind::Rsu,
+            GrantType::Rsa { .. } => GrantKind::Rsa,
+        }
+    }
+
+    /// Checks if the grant is an option to buy shares at a strike price.
+    pub fn is_option(&self) -> bool {
+        matches!(self, GrantType::Iso { .. } | GrantType::Nso { .. })
+    }
+
+    /// Checks if the holder exercises the grant once vested: options to buy the shares,
+    /// and stock appreciation rights settling the gain over the strike price.
+    pub fn is_exercisable(&self) -> bool {
+        self.is_option() || matches!(self, GrantType::Sar { .. })
+    }
+}
+
+impl Grant {
+    pub fn grant_type(&self) -> &GrantType {
+        &self.grant_type
+    }
+
+    /// Changes the kind of award, e.g. to make it an option with a strike price.
+    pub fn with_grant_type(mut self, grant_type: GrantType) -> Grant {
+        self.grant_type = grant_type;
+        self
+    }
+
+    /// Price per share paid on exercise, for options and stock appreciation rights.
+    pub fn strike_price(&self) -> Option<Price> {
+        match self.grant_type {
+            GrantType::Iso { strike_price }
+            | GrantType::Nso { strike_price }
+            | GrantType::Sar { strike_price } => Some(strike_price),
+            GrantType::Rsu { .. } | GrantType::Rsa { .. } => None,
+        }
+    }
+
+    /// Calculates the vested shares delivered by the given date. Restricted stock units
+    /// with settlement dates deliver what vested by the last settlement date; every
+    /// other grant delivers shares as they vest.
+    pub fn calculate_settled_shares(&self, date: LocalDate) -> i64 {
+        match &self.grant_type {
+            GrantType::Rsu { settlement_dates } if !settlement_dates.is_empty() => settlement_dates
+                .iter()
+                .filter(|settlement_date| **settlement_date <= date)
+                .max()
+                .map_or(0, |settlement_date| {
+                    self.calculate_vested_shares(*settlement_date)
+                }),
+            _ => self.calculate_vested_shares(date),
+        }
+    }
+
+    /// Calculates the unvested restricted stock the company may buy back at the
+    /// purchase price once the grant is terminated. `None` unless the grant is
+    /// restricted stock that has been terminated.
+    pub fn restricted_stock_repurchase(
+        &self,
+        repurchase_window_days: i64,
+    ) -> Option<RepurchaseNotice> {
+        match (&self.grant_type, self.termination_date) {
+            (GrantType::Rsa { purchase_price }, Some(termination_date)) => Some(
+                self.repurchase_notice(termination_date, *purchase_price, repurchase_window_days),
+            ),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GrantKind, GrantType, LocalDate};
+    use crate::{usd, usd_price, Grant, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_exposes_terms_specific_to_each_grant_type() {
+        let grant = |grant_type| Grant {
+            grant_type,
+            ..Grant::for_test(
+                4_800,
+                LocalDate::from_ymd(2021, 1, 1),
+                VestingScheduleConfiguration::monthly(0, 0.0, 48),
+            )
+        };
+
+        let option = grant(GrantType::Iso {
+            strike_price: usd_price(2.5),
+        });
+
+        assert_eq!(option.strike_price(), Some(usd_price(2.5)));
+        assert_eq!(option.grant_type().kind(), GrantKind::Iso);
+
+        let rsu = grant(GrantType::Rsu {
+            settlement_dates: vec![
+                LocalDate::from_ymd(2021, 3, 15),
+                LocalDate::from_ymd(2021, 6, 15),
+            ],
+        });
+
+        /*
+         * 500 units vested by 2021/6/1 but only the 200 vested by the 3/15 settlement
+         * have been delivered.
+         */
+
+        assert_eq!(rsu.strike_price(), None);
+        assert_eq!(
+            rsu.calculate_settled_shares(LocalDate::from_ymd(2021, 6, 1)),
+            200
+        );
+        assert_eq!(
+            rsu.calculate_settled_shares(LocalDate::from_ymd(2021, 3, 14)),
+            0
+        );
+
+        let mut rsa = grant(GrantType::Rsa {
+            purchase_price: usd_price(0.01),
+        });
+
+        assert_eq!(rsa.restricted_stock_repurchase(90), None);
+
+        rsa.terminate(LocalDate::from_ymd(2022, 1, 1));
+
+        let notice = rsa.restricted_stock_repurchase(90).unwrap();
+
+        assert_eq!(notice.repurchasable_shares, 3_600);
+        assert_eq!(notice.repurchase_cost, usd(36.0));
+    }
+}
@@ -0,0 +1,162 @@
+use chrono::Datelike;
+
+use crate::{EquityPlan, Grant, LocalDate, PlanError, ReserveForecast};
+
+/// A planned hire, granted equity on their start date.
+pub struct Hire {
+    pub role: String,
+    pub start_date: LocalDate,
+    /// Shares the hire is expected to be granted.
+    pub grant_amount: i64,
+    /// Name of the plan template the grant's terms come from.
+    pub template_name: String,
+}
+
+/// The hires a company expects to make, used to forecast the equity they'll need.
+#[derive(Default)]
+pub struct HiringPlan {
+    pub hires: Vec<Hire>,
+}
+
+/// The dilution the year's grants cause to the outstanding shares.
+#[derive(Debug, PartialEq)]
+pub struct DilutionForecast {
+    pub year: i32,
+    pub granted_amount: i64,
+    /// Outstanding shares at the end of the year, counting every share granted so far.
+    pub outstanding_shares: i64,
+    /// Fraction of the outstanding shares made up by the year's grants.
+    pub dilution: f64,
+}
+
+/// A plan's reserve and the dilution to the company over the years of a hiring plan.
+#[derive(Debug, PartialEq)]
+pub struct HiringForecast {
+    pub reserve: Vec<ReserveForecast>,
+    pub dilution: Vec<DilutionForecast>,
+}
+
+/// Projects the dilution from granting the shares each year from `from_year` through
+/// `to_year`, treating granted shares as outstanding from the year they're granted.
+pub fn forecast_dilution(
+    from_year: i32,
+    to_year: i32,
+    outstanding_shares: i64,
+    projected_grants: &[Grant],
+) -> Vec<DilutionForecast> {
+    let mut outstanding_shares = outstanding_shares;
+
+    (from_year..=to_year)
+        .map(|year| {
+            let granted_amount: i64 = projected_grants
+                .iter()
+                .filter(|grant| grant.grant_date.year() == year)
+                .map(|grant| grant.amount)
+                .sum();
+
+            outstanding_shares += granted_amount;
+
+            DilutionForecast {
+                year,
+                granted_amount,
+                outstanding_shares,
+                dilution: if outstanding_shares > 0 {
+                    granted_amount as f64 / outstanding_shares as f64
+                } else {
+                    0.0
+                },
+            }
+        })
+        .collect()
+}
+
+impl HiringPlan {
+    pub fn new(hires: Vec<Hire>) -> HiringPlan {
+        HiringPlan { hires }
+    }
+
+    /// Makes the grant each hire is expected to receive, with the terms of their
+    /// template in the plan.
+    pub fn projected_grants(&self, plan: &EquityPlan) -> Result<Vec<Grant>, PlanError> {
+        self.hires
+            .iter()
+            .map(|hire| {
+                Ok(plan
+                    .template(&hire.template_name)?
+                    .grant(hire.grant_amount, hire.start_date))
+            })
+            .collect()
+    }
+
+    /// Forecasts the plan's reserve and the company's dilution each year from
+    /// `from_year` through `to_year` as the hiring plan's grants are issued.
+    pub fn forecast(
+        &self,
+        plan: &EquityPlan,
+        from_year: i32,
+        to_year: i32,
+        outstanding_shares: i64,
+    ) -> Result<HiringForecast, PlanError> {
+        let projected_grants = self.projected_grants(plan)?;
+
+        Ok(HiringForecast {
+            reserve: plan.forecast_reserve(
+                from_year,
+                to_year,
+                outstanding_shares,
+                &projected_grants,
+            ),
+            dilution: forecast_dilution(from_year, to_year, outstanding_shares, &projected_grants),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Hire, HiringPlan, LocalDate};
+    use crate::{
+        EquityPlan, GrantKind, GrantTemplate, GrantType, PlanError, VestingScheduleConfiguration,
+    };
+
+    #[test]
+    fn it_can_forecast_a_hiring_plan() {
+        let mut plan = EquityPlan::new("2021 Plan", 10_000, 48, vec![GrantKind::Rsu]);
+        plan.templates.push(GrantTemplate {
+            name: "Standard RSU".to_string(),
+            grant_type: GrantType::default(),
+            vesting_schedule: VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        });
+
+        let hire = |role: &str, start_date, grant_amount| Hire {
+            role: role.to_string(),
+            start_date,
+            grant_amount,
+            template_name: "Standard RSU".to_string(),
+        };
+
+        let mut hiring_plan = HiringPlan::new(vec![
+            hire("Engineer", LocalDate::from_ymd(2022, 2, 1), 4_000),
+            hire("Designer", LocalDate::from_ymd(2022, 9, 1), 2_000),
+            hire("Engineer", LocalDate::from_ymd(2023, 3, 1), 5_000),
+        ]);
+
+        let forecast = hiring_plan.forecast(&plan, 2022, 2023, 94_000).unwrap();
+
+        /*
+         * The reserve runs out in 2023, when the grants take the outstanding shares
+         * from 100000 to 105000.
+         */
+
+        assert_eq!(forecast.reserve[0].remaining_reserve, 4_000);
+        assert_eq!(forecast.reserve[1].remaining_reserve, -1_000);
+        assert_eq!(forecast.dilution[1].outstanding_shares, 105_000);
+        assert_eq!(forecast.dilution[1].dilution, 5_000.0 / 105_000.0);
+
+        hiring_plan.hires[0].template_name = "Executive".to_string();
+
+        assert_eq!(
+            hiring_plan.forecast(&plan, 2022, 2023, 94_000),
+            Err(PlanError::UnknownTemplate("Executive".to_string()))
+        );
+    }
+}
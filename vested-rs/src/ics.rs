@@ -0,0 +1,116 @@
+use crate::money::group_thousands;
+use crate::{LocalDate, VestingSchedule};
+
+/// Longest content line allowed by RFC 5545, in bytes, before it has to be folded.
+const MAX_LINE_LENGTH: usize = 75;
+
+/// An all-day event in an iCalendar file.
+pub(crate) struct CalendarEvent {
+    pub date: LocalDate,
+    /// Identifies the event across exports, so calendar apps update it in place.
+    pub uid: String,
+    pub summary: String,
+}
+
+/// Escapes the characters with special meaning in iCalendar text values.
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Folds a content line longer than 75 bytes onto continuation lines starting with a
+/// space, without splitting a character.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut line_length = 0;
+
+    for c in line.chars() {
+        if line_length + c.len_utf8() > MAX_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            line_length = 1;
+        }
+
+        folded.push(c);
+        line_length += c.len_utf8();
+    }
+
+    folded + "\r\n"
+}
+
+/// Writes the events as an iCalendar file, stamped as created on the given date.
+pub(crate) fn write_calendar(events: &[CalendarEvent], stamp_date: LocalDate) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//vested//vesting schedule//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    for event in events {
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}", escape_text(&event.uid)),
+            format!("DTSTAMP:{}T000000Z", stamp_date.format("%Y%m%d")),
+            format!("DTSTART;VALUE=DATE:{}", event.date.format("%Y%m%d")),
+            format!("SUMMARY:{}", escape_text(&event.summary)),
+            "TRANSP:TRANSPARENT".to_string(),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+    lines.iter().map(|line| fold_line(line)).collect()
+}
+
+impl VestingSchedule {
+    /// Writes the schedule as an iCalendar file with an all-day event on each vesting
+    /// date, for subscribing to in a calendar app. Event UIDs are derived from the
+    /// schedule's start date, so re-exporting an updated schedule replaces its events.
+    pub fn to_ics(&self) -> String {
+        let events: Vec<CalendarEvent> = self
+            .periods
+            .iter()
+            .filter(|period| period.vested_this_period > 0)
+            .map(|period| CalendarEvent {
+                date: period.date,
+                uid: format!(
+                    "{}-{}@vested",
+                    self.from_date.format("%Y%m%d"),
+                    period.date.format("%Y%m%d")
+                ),
+                summary: format!("{} shares vest", group_thousands(period.vested_this_period)),
+            })
+            .collect();
+
+        write_calendar(&events, self.from_date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Grant, LocalDate, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_export_a_schedule_as_icalendar() {
+        let grant = Grant::for_test(
+            10_000,
+            LocalDate::from_ymd(2021, 2, 6),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        );
+
+        let ics = grant.calculate_vesting_schedule().to_ics();
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 37);
+        assert!(ics.contains(
+            "BEGIN:VEVENT\r\n\
+             UID:20210206-20220206@vested\r\n\
+             DTSTAMP:20210206T000000Z\r\n\
+             DTSTART;VALUE=DATE:20220206\r\n\
+             SUMMARY:2\\,500 shares vest\r\n"
+        ));
+    }
+}
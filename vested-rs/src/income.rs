@@ -0,0 +1,226 @@
+use chrono::Duration;
+
+use crate::{EightyThreeBElection, GrantLedger, GrantType, LocalDate};
+
+/// How the income from a vest or exercise is taxed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IncomeCharacter {
+    /// Compensation reported on the W-2 and withheld on as supplemental wages, e.g. an
+    /// RSU vest or an NSO exercise.
+    SupplementalWages,
+    /// The spread on an ISO exercise, counted towards the AMT but not regular income.
+    AmtPreference,
+    /// Income already recognized under an 83(b) election, so any growth is taxed as a
+    /// capital gain when the shares are sold.
+    CapitalGain,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IncomeEventKind {
+    Vest,
+    Exercise,
+}
+
+/// Shares vesting or exercised on one date, tagged with how the income is taxed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IncomeEvent {
+    pub date: LocalDate,
+    pub kind: IncomeEventKind,
+    pub shares: i64,
+    /// `None` if the event isn't taxable, like an option vesting before it's exercised.
+    pub character: Option<IncomeCharacter>,
+}
+
+impl GrantLedger {
+    /// Character of the income recognized when options are exercised, or when shares
+    /// exercised early vest without an 83(b) election.
+    fn exercise_income_character(&self) -> Option<IncomeCharacter> {
+        match self.grant.grant_type {
+            GrantType::Iso { .. } => Some(IncomeCharacter::AmtPreference),
+            _ => Some(IncomeCharacter::SupplementalWages),
+        }
+    }
+
+    /// Lists the grant's vests and recorded exercises in date order, tagged with the
+    /// character of the income each one produces under the given 83(b) elections.
+    ///
+    /// Restricted stock and RSUs are taxed as they vest, unless an election was made for
+    /// restricted stock on its grant date. Options are taxed when exercised, except that
+    /// shares exercised early are taxed as they vest if no election was made for them.
+    /// Events that are partly taxable are split in two.
+    pub fn calculate_income_events(&self, elections: &[EightyThreeBElection]) -> Vec<IncomeEvent> {
+        let elected_on = |date: LocalDate| {
+            elections
+                .iter()
+                .any(|election| election.transfer_date == date && election.is_effective())
+        };
+        let elected_by = |date: LocalDate| {
+            elections
+                .iter()
+                .any(|election| election.transfer_date <= date && election.is_effective())
+        };
+
+        let mut events = Vec::new();
+        let mut push = |date, kind, shares, character| {
+            if shares > 0 {
+                events.push(IncomeEvent {
+                    date,
+                    kind,
+                    shares,
+                    character,
+                });
+            }
+        };
+
+        for vest in self.grant.vest_events() {
+            match self.grant.grant_type {
+                GrantType::Rsa { .. } if elected_on(self.grant.grant_date) => push(
+                    vest.date,
+                    IncomeEventKind::Vest,
+                    vest.amount,
+                    Some(IncomeCharacter::CapitalGain),
+                ),
+                GrantType::Rsu { .. } | GrantType::Rsa { .. } => push(
+                    vest.date,
+                    IncomeEventKind::Vest,
+                    vest.amount,
+                    Some(IncomeCharacter::SupplementalWages),
+                ),
+                _ => {
+                    let exercised_early = (self
+                        .repurchaseable_shares_on(vest.date - Duration::days(1))
+                        - self.repurchaseable_shares_on(vest.date))
+                    .clamp(0, vest.amount);
+                    let character = if elected_by(vest.date) {
+                        Some(IncomeCharacter::CapitalGain)
+                    } else {
+                        self.exercise_income_character()
+                    };
+
+                    push(vest.date, IncomeEventKind::Vest, exercised_early, character);
+                    push(
+                        vest.date,
+                        IncomeEventKind::Vest,
+                        vest.amount - exercised_early,
+                        None,
+                    );
+                }
+            }
+        }
+
+        for exercise in self.exercises() {
+            let unvested = self
+                .repurchaseable_shares_on(exercise.date)
+                .min(exercise.quantity);
+            let unvested_character = if elected_on(exercise.date) {
+                self.exercise_income_character()
+            } else {
+                None
+            };
+
+            push(
+                exercise.date,
+                IncomeEventKind::Exercise,
+                exercise.quantity - unvested,
+                self.exercise_income_character(),
+            );
+            push(
+                exercise.date,
+                IncomeEventKind::Exercise,
+                unvested,
+                unvested_character,
+            );
+        }
+
+        events.sort_by_key(|event| event.date);
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IncomeCharacter, IncomeEvent, IncomeEventKind, LocalDate};
+    use crate::{usd_price, Grant, GrantLedger, GrantType, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_classify_vest_and_exercise_income() {
+        let grant = Grant::for_test(
+            4_800,
+            LocalDate::from_ymd(2021, 1, 1),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        );
+
+        let rsu_events = GrantLedger::new(grant.clone()).calculate_income_events(&[]);
+
+        assert_eq!(rsu_events.len(), 37);
+        assert!(rsu_events
+            .iter()
+            .all(|event| event.kind == IncomeEventKind::Vest
+                && event.character == Some(IncomeCharacter::SupplementalWages)));
+
+        let mut ledger = GrantLedger::new(Grant {
+            grant_type: GrantType::Iso {
+                strike_price: usd_price(1.0),
+            },
+            ..grant
+        });
+        ledger.early_exercise = true;
+        ledger
+            .record_exercise(LocalDate::from_ymd(2022, 1, 1), 2_000)
+            .unwrap();
+
+        /*
+         * 1200 of the exercised shares had vested, the other 800 vest over the next 8
+         * months without an 83(b) election, counting towards the AMT as they do.
+         */
+
+        let events = ledger.calculate_income_events(&[]);
+
+        assert_eq!(
+            &events[..3],
+            &[
+                IncomeEvent {
+                    date: LocalDate::from_ymd(2022, 1, 1),
+                    kind: IncomeEventKind::Vest,
+                    shares: 1_200,
+                    character: None,
+                },
+                IncomeEvent {
+                    date: LocalDate::from_ymd(2022, 1, 1),
+                    kind: IncomeEventKind::Exercise,
+                    shares: 1_200,
+                    character: Some(IncomeCharacter::AmtPreference),
+                },
+                IncomeEvent {
+                    date: LocalDate::from_ymd(2022, 1, 1),
+                    kind: IncomeEventKind::Exercise,
+                    shares: 800,
+                    character: None,
+                },
+            ]
+        );
+        assert_eq!(
+            events[3],
+            IncomeEvent {
+                date: LocalDate::from_ymd(2022, 2, 1),
+                kind: IncomeEventKind::Vest,
+                shares: 100,
+                character: Some(IncomeCharacter::AmtPreference),
+            }
+        );
+
+        /*
+         * With a timely election, the early shares are taxed on exercise instead.
+         */
+
+        let mut election = ledger
+            .eighty_three_b_election(&ledger.exercises()[0], usd_price(1.0))
+            .unwrap();
+        election.filed_date = Some(LocalDate::from_ymd(2022, 1, 10));
+
+        let events = ledger.calculate_income_events(&[election]);
+
+        assert_eq!(events[2].character, Some(IncomeCharacter::AmtPreference));
+        assert_eq!(events[3].character, Some(IncomeCharacter::CapitalGain));
+    }
+}
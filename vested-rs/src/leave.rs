@@ -0,0 +1,279 @@
+use chrono::Duration;
+
+use crate::{vested_amount, Grant, LocalDate, VestingPeriod, VestingSchedule};
+
+/// How a leave of absence spanning the cliff date affects vesting.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LeaveCliffPolicy {
+    /// Vesting is suspended for the leave, so the cliff and every later vesting date
+    /// move out by the length of the leave.
+    ShiftCliff,
+    /// The cliff stays on its original date but vests only the part of the cliff amount
+    /// earned while not on leave. The shares held back vest on the final vesting date.
+    ProrateCliff,
+}
+
+/// A leave of absence from `start_date` up to but not including `end_date`.
+pub struct LeaveOfAbsence {
+    pub start_date: LocalDate,
+    pub end_date: LocalDate,
+    pub cliff_policy: LeaveCliffPolicy,
+}
+
+impl LeaveOfAbsence {
+    fn duration(&self) -> Duration {
+        self.end_date - self.start_date
+    }
+}
+
+impl Grant {
+    fn cliff_date(&self) -> LocalDate {
+        self.vesting_schedule.interval_date(
+            self.vesting_commencement_date(),
+            self.vesting_schedule.cliff_intervals,
+        )
+    }
+
+    /// Checks if the leave is underway on the cliff date. Leaves that don't span the
+    /// cliff leave the schedule unchanged.
+    fn leave_spans_cliff(&self, leave: &LeaveOfAbsence) -> bool {
+        let cliff_date = self.cliff_date();
+
+        leave.start_date <= cliff_date && cliff_date < leave.end_date
+    }
+
+    /// Shares held back at the cliff under `LeaveCliffPolicy::ProrateCliff`, in
+    /// proportion to the time between the vesting commencement date and the cliff spent on leave.
+    fn prorated_cliff_shortfall(&self, leave: &LeaveOfAbsence) -> f32 {
+        let cliff_date = self.cliff_date();
+        let cliff_days = (cliff_date - self.vesting_commencement_date()).num_days();
+        let leave_days =
+            (cliff_date - leave.start_date.max(self.vesting_commencement_date())).num_days();
+
+        if cliff_days <= 0 {
+            return 0.0;
+        }
+
+        self.vesting_schedule.cliff_vested_amount(self.amount) * leave_days as f32
+            / cliff_days as f32
+    }
+
+    /// Calculates the vested amount on the given date for a grant holder who took the
+    /// leave of absence.
+    pub fn calculate_vested_amount_with_leave(
+        &self,
+        leave: &LeaveOfAbsence,
+        date: LocalDate,
+    ) -> f32 {
+        if !self.leave_spans_cliff(leave) {
+            return self.calculate_vested_amount(date);
+        }
+
+        let date = match self.termination_date {
+            Some(termination_date) if termination_date < date => termination_date,
+            _ => date,
+        };
+        let vested_on = |date| {
+            vested_amount(
+                &self.vesting_schedule,
+                self.amount,
+                self.vesting_commencement_date(),
+                date,
+            )
+        };
+
+        match leave.cliff_policy {
+            LeaveCliffPolicy::ShiftCliff if date < leave.end_date => vested_on(leave.start_date),
+            LeaveCliffPolicy::ShiftCliff => vested_on(date - leave.duration()),
+            LeaveCliffPolicy::ProrateCliff => {
+                let end_date = self.vesting_schedule.interval_date(
+                    self.vesting_commencement_date(),
+                    self.vesting_schedule.total_intervals,
+                );
+
+                if date < self.cliff_date() || date >= end_date {
+                    vested_on(date)
+                } else {
+                    (vested_on(date) - self.prorated_cliff_shortfall(leave)).max(0.0)
+                }
+            }
+        }
+    }
+
+    /// Recomputes the vesting schedule for a grant holder who took the leave of absence.
+    pub fn apply_leave(&self, leave: &LeaveOfAbsence) -> VestingSchedule {
+        let mut vesting_schedule = self.calculate_vesting_schedule();
+
+        if !self.leave_spans_cliff(leave) {
+            return vesting_schedule;
+        }
+
+        match leave.cliff_policy {
+            LeaveCliffPolicy::ShiftCliff => {
+                // Vesting dates move before the termination date cuts the schedule short,
+                // so shift the schedule of the grant as if it were still active.
+                let mut vesting_schedule = Grant {
+                    termination_date: None,
+                    ..self.clone()
+                }
+                .calculate_vesting_schedule();
+
+                for period in vesting_schedule.periods.iter_mut() {
+                    if period.date >= leave.start_date {
+                        period.date = period.date + leave.duration();
+                    }
+                }
+
+                vesting_schedule.to_date = vesting_schedule.to_date + leave.duration();
+
+                if let Some(termination_date) = self.termination_date {
+                    vesting_schedule
+                        .periods
+                        .retain(|period| period.date <= termination_date);
+                    vesting_schedule.to_date = vesting_schedule.to_date.min(termination_date);
+                    vesting_schedule.forfeited_amount = self.amount
+                        - self
+                            .calculate_vested_amount_with_leave(leave, termination_date)
+                            .floor() as i64;
+                }
+
+                vesting_schedule
+            }
+            LeaveCliffPolicy::ProrateCliff => {
+                for period in vesting_schedule.periods.iter_mut() {
+                    let vested_amount = self.calculate_vested_amount_with_leave(leave, period.date);
+
+                    period.cumulative_vested_amount = vested_amount.floor() as i64;
+                    period.precise_cumulative_amount = vested_amount as f64;
+                }
+
+                VestingPeriod::update_vested_this_period(&mut vesting_schedule.periods);
+
+                if let Some(termination_date) = self.termination_date {
+                    vesting_schedule.forfeited_amount = self.amount
+                        - self
+                            .calculate_vested_amount_with_leave(leave, termination_date)
+                            .floor() as i64;
+                }
+
+                vesting_schedule
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LeaveCliffPolicy, LeaveOfAbsence, LocalDate};
+    use crate::{Grant, VestingPeriod, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_handle_a_leave_spanning_the_cliff() {
+        let grant = Grant::for_test(
+            4_800,
+            LocalDate::from_ymd(2020, 1, 1),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        );
+
+        let leave = |cliff_policy| LeaveOfAbsence {
+            start_date: LocalDate::from_ymd(2020, 11, 1),
+            end_date: LocalDate::from_ymd(2021, 2, 1),
+            cliff_policy,
+        };
+
+        /*
+         * A 92 day leave from 2020/11/1 spans the 2021/1/1 cliff.
+         * - shifted: the cliff moves to 2021/4/3, vesting 1200
+         * - prorated: 61 of the 366 days before the cliff were on leave, so 200 of the
+         *   1200 cliff shares are held back until 2024/1/1
+         */
+
+        let shifted = leave(LeaveCliffPolicy::ShiftCliff);
+
+        assert_eq!(
+            grant.calculate_vested_amount_with_leave(&shifted, LocalDate::from_ymd(2021, 4, 2)),
+            0.0
+        );
+        assert_eq!(
+            grant.calculate_vested_amount_with_leave(&shifted, LocalDate::from_ymd(2021, 4, 3)),
+            1200.0
+        );
+
+        let vesting_schedule = grant.apply_leave(&shifted);
+
+        assert_eq!(
+            vesting_schedule.periods[12],
+            VestingPeriod {
+                date: LocalDate::from_ymd(2021, 4, 3),
+                cumulative_vested_amount: 1200,
+                vested_this_period: 1200,
+                precise_cumulative_amount: 1200.0,
+            }
+        );
+        assert_eq!(vesting_schedule.to_date, LocalDate::from_ymd(2024, 4, 2));
+
+        let prorated = leave(LeaveCliffPolicy::ProrateCliff);
+
+        assert_eq!(
+            grant.calculate_vested_amount_with_leave(&prorated, LocalDate::from_ymd(2021, 1, 1)),
+            1000.0
+        );
+        assert_eq!(
+            grant.calculate_vested_amount_with_leave(&prorated, LocalDate::from_ymd(2021, 2, 1)),
+            1100.0
+        );
+
+        let vesting_schedule = grant.apply_leave(&prorated);
+
+        assert_eq!(vesting_schedule.periods[12].cumulative_vested_amount, 1000);
+        assert_eq!(
+            vesting_schedule.periods[12].precise_cumulative_amount,
+            1000.0
+        );
+        assert_eq!(
+            vesting_schedule.periods.last(),
+            Some(&VestingPeriod {
+                date: LocalDate::from_ymd(2024, 1, 1),
+                cumulative_vested_amount: 4800,
+                vested_this_period: 300,
+                precise_cumulative_amount: 4800.0,
+            })
+        );
+    }
+
+    #[test]
+    fn it_catches_up_a_shifted_schedule_commencing_before_the_grant_date() {
+        let grant = Grant::for_test(
+            4_800,
+            LocalDate::from_ymd(2020, 4, 1),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        )
+        .with_vesting_commencement_date(LocalDate::from_ymd(2020, 1, 1));
+        let leave = LeaveOfAbsence {
+            start_date: LocalDate::from_ymd(2020, 11, 1),
+            end_date: LocalDate::from_ymd(2021, 2, 1),
+            cliff_policy: LeaveCliffPolicy::ShiftCliff,
+        };
+
+        /*
+         * Vesting commenced three months before the 2020/4/1 grant date, so the
+         * schedule starts on the grant date and the shifted cliff is its tenth period.
+         */
+
+        let vesting_schedule = grant.apply_leave(&leave);
+
+        assert_eq!(
+            vesting_schedule.periods[0].date,
+            LocalDate::from_ymd(2020, 4, 1)
+        );
+        assert_eq!(
+            vesting_schedule.periods[9],
+            VestingPeriod {
+                date: LocalDate::from_ymd(2021, 4, 3),
+                cumulative_vested_amount: 1200,
+                vested_this_period: 1200,
+                precise_cumulative_amount: 1200.0,
+            }
+        );
+    }
+}
@@ -1,258 +1,2386 @@
-use chrono::{Date, Datelike, Utc};
-use chronoutil::{DateRule, RelativeDuration};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::ops::RangeInclusive;
 
+use chrono::{Datelike, Duration};
+use chronoutil::RelativeDuration;
+
+mod acceleration;
+mod analytics;
+mod anonymize;
+mod answer;
+mod approval;
+mod batch;
+mod builder;
+mod burndown;
+mod business_day;
+mod cap_table;
+mod company;
+mod cost_basis;
+#[cfg(feature = "csv")]
+mod csv;
+mod date;
+mod differential;
+mod digest;
+mod duration;
+mod earnout;
+mod election;
+mod error;
+mod espp;
+mod evergreen;
+mod exercise;
+mod exit;
+#[cfg(feature = "price-fetch")]
+mod fetch;
+mod golden;
+mod grant_type;
+mod hiring;
+mod ics;
+mod income;
+mod leave;
+mod money;
+mod narrative;
+mod negotiation;
+mod notification;
+mod option_tax;
+mod payroll;
+mod plan;
+mod portfolio;
+mod reconcile;
+mod refresh;
+mod repurchase;
+mod resample;
+mod retention;
+mod rif;
+#[cfg(feature = "scheduler")]
+mod scheduler;
+mod sellable;
+mod simulation;
+mod sparkline;
+mod table;
+mod tax_export;
+mod tax_forms;
+mod timeline;
+mod tranche;
+mod valuation;
+mod warning;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+pub use acceleration::AccelerationPolicy;
+pub use analytics::{analyze_vesting, VestingAnalytics};
+pub use anonymize::anonymize;
+pub use answer::{Answer, AnswerDetails, Question};
+pub use approval::{GrantApproval, ValidationMode};
+pub use batch::{calculate_vested_amounts, try_calculate_vested_amounts, vested_amounts};
+pub use builder::GrantBuilder;
+pub use burndown::{aggregate_unvested_balances, UnvestedBalance};
+pub use business_day::{
+    BusinessDayConvention, BusinessDayError, HolidayCalendar, HolidayList, WeekendCalendar,
+};
+pub use cap_table::CapTable;
+pub use company::{Company, PlanBreakdown};
+pub use cost_basis::{
+    reconcile_cost_basis, BasisAdjustment, BrokerCsvError, BrokerSale, CostBasisReport,
+};
+pub use date::LocalDate;
+pub use differential::{find_divergences, Divergence};
+pub use digest::{DigestEvent, VestDigest};
+pub use duration::{CliffSpec, ProrationPolicy, StubPolicy, VestingDuration};
+pub use earnout::{EarnOut, EarnOutCondition, EarnOutError, EarnOutTranche};
+pub use election::{EightyThreeBElection, ElectionStatus};
+pub use error::VestingError;
+pub use espp::{EsppOffering, EsppPurchase};
+pub use evergreen::{EvergreenProvision, ReserveForecast};
+pub use exercise::{
+    ExercisablePeriod, Exercise, ExerciseError, ExerciseWindow, GrantLedger, LedgerPeriod,
+};
+pub use exit::{
+    EscrowError, EscrowRelease, Exit, ExitScenario, ExitScenarioError, ExpectedValuePoint,
+    ProceedsPayment, ProceedsSchedule,
+};
+#[cfg(feature = "price-fetch")]
+pub use fetch::{FetchError, PriceFetcher};
+pub use golden::{golden_vectors, GoldenVector, GoldenVectorMismatch};
+pub use grant_type::{GrantKind, GrantType};
+pub use hiring::{forecast_dilution, DilutionForecast, Hire, HiringForecast, HiringPlan};
+pub use income::{IncomeCharacter, IncomeEvent, IncomeEventKind};
+pub use leave::{LeaveCliffPolicy, LeaveOfAbsence};
+pub use money::{Currency, Money, Price};
+pub use negotiation::{
+    compare_acceleration, standard_acceleration_clauses, AccelerationClause,
+    AccelerationComparison, AccelerationOutcome, ExitTiming,
+};
+#[cfg(feature = "webhook")]
+pub use notification::WebhookSink;
+pub use notification::{DeliveryError, Notification, NotificationSink, WriterSink};
+pub use option_tax::{
+    compare_iso_nso, ExerciseAndSale, IsoNsoComparison, OptionTaxOutcome, TaxAssumptions,
+};
+pub use payroll::{
+    vest_income_records, PayFrequency, PaySchedule, PayrollError, PayrollField, PayrollLayout,
+    VestIncomeRecord,
+};
+pub use plan::{EquityPlan, GrantTemplate, PlanError};
+#[cfg(feature = "serde")]
+pub use portfolio::ImportedGrant;
+pub use portfolio::{GrantBreakdown, Portfolio};
+pub use reconcile::{Discrepancy, ReconciliationReport, ReportedVestEvent};
+pub use refresh::{allocate_refresh_budget, RefreshCandidate, RefreshProposal, RefreshRules};
+pub use repurchase::RepurchaseNotice;
+pub use resample::{Granularity, Interpolation, SchedulePoint};
+pub use retention::{retention_report, Holder, RetentionCriteria, RetentionEntry};
+pub use rif::{rif_report, DepartmentImpact, RifEmployee, RifReport};
+#[cfg(feature = "scheduler")]
+pub use scheduler::ReminderScheduler;
+pub use sellable::{BlackoutWindow, SaleRestrictions, SellableVest};
+pub use simulation::{simulate_outcomes, PercentileBand, SimulationParameters};
+pub use tax_export::{TaxExportError, TaxExportLayout, TaxLot, TaxLotField};
+pub use tax_forms::{
+    form_3921_records, form_3922_records, EsppTransfer, Form3921Record, Form3922Record, IsoExercise,
+};
+pub use timeline::{GrantEvent, GrantEventKind, Timeline};
+pub use tranche::{TrancheValue, TrancheValueReport};
+pub use valuation::{PriceTable, ValuationProvider};
+pub use warning::{VestingWarning, Warnings};
+#[cfg(feature = "wasm")]
+pub use wasm::{WasmGrant, WasmVestingPeriod};
+
+/// How far the weights of a weighted schedule may stray from 1.0 due to rounding.
+const WEIGHT_TOLERANCE: f32 = 0.0001;
+
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VestingPeriod {
+    pub date: LocalDate,
+    /// Whole shares vested by this date, rounded under the schedule's rounding strategy.
+    pub cumulative_vested_amount: i64,
+    /// Shares vesting on this date, i.e. the increase over the previous period.
+    pub vested_this_period: i64,
+    /// Amount vested by this date before rounding to whole shares, e.g. 2,708.33 of a
+    /// 10,000 share grant 13 months into a four year schedule.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub precise_cumulative_amount: f64,
+}
+
+impl VestingPeriod {
+    /// Makes a period from the precise cumulative amount and its rounding to whole
+    /// shares. The amount vesting in the period is left at zero for
+    /// `update_vested_this_period` to fill in.
+    pub fn new(
+        date: LocalDate,
+        precise_cumulative_amount: f64,
+        cumulative_vested_amount: i64,
+    ) -> VestingPeriod {
+        VestingPeriod {
+            date,
+            cumulative_vested_amount,
+            vested_this_period: 0,
+            precise_cumulative_amount,
+        }
+    }
+
+    /// Recalculates each period's `vested_this_period` from the cumulative amounts, after
+    /// periods have been added or their cumulative amounts changed.
+    pub(crate) fn update_vested_this_period(periods: &mut [VestingPeriod]) {
+        let mut previous_cumulative_amount = 0;
+
+        for period in periods {
+            period.vested_this_period =
+                period.cumulative_vested_amount - previous_cumulative_amount;
+            previous_cumulative_amount = period.cumulative_vested_amount;
+        }
+    }
+}
+
+/// Shares vesting on a single date, as opposed to the cumulative amount in a `VestingPeriod`.
 #[derive(Debug, PartialEq, PartialOrd)]
-struct VestingPeriod {
-    date: Date<Utc>,
-    cumulative_vested_amount: i32,
+pub struct VestEvent {
+    pub date: LocalDate,
+    pub amount: i64,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VestingSchedule {
+    pub from_date: LocalDate,
+    pub to_date: LocalDate,
+    pub periods: Vec<VestingPeriod>,
+    /// Shares that will never vest because the grant was terminated.
+    pub forfeited_amount: i64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum VestingInterval {
+    Weekly,
+    BiWeekly,
+    Monthly,
+    Quarterly,
+    Annual,
+}
+
+/// Length of a single vesting interval, counted in calendar months or, for intervals
+/// shorter than a month, days.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum IntervalLength {
+    Months(i32),
+    Days(i32),
+}
+
+/// Days in a year of weekly vesting intervals, used to convert between weeks and months.
+pub(crate) const DAYS_PER_WEEKLY_YEAR: i32 = 52 * 7;
+
+impl VestingInterval {
+    pub(crate) const fn length(&self) -> IntervalLength {
+        match self {
+            VestingInterval::Weekly => IntervalLength::Days(7),
+            VestingInterval::BiWeekly => IntervalLength::Days(14),
+            VestingInterval::Monthly => IntervalLength::Months(1),
+            VestingInterval::Quarterly => IntervalLength::Months(3),
+            VestingInterval::Annual => IntervalLength::Months(12),
+        }
+    }
+
+    /// Converts a number of intervals into whole months, counting 52 weeks a year for
+    /// weekly intervals.
+    const fn to_months(self, intervals: i32) -> i32 {
+        match self.length() {
+            IntervalLength::Months(months) => intervals * months,
+            IntervalLength::Days(days) => intervals * days * 12 / DAYS_PER_WEEKLY_YEAR,
+        }
+    }
+}
+
+/// Revision of the vesting math. Fixes to rounding or date handling ship as new
+/// versions so that results already reported under an older version never change.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum CalculationVersion {
+    /// Counts elapsed months by calendar month, ignoring the day of month.
+    #[default]
+    V1,
+    /// Counts a month as elapsed only once its monthly anniversary has been reached,
+    /// clamping to the last day of shorter months.
+    V2,
+}
+
+/// Where monthly vesting dates fall in months too short to have the grant date's day,
+/// e.g. the 31st.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum MonthEndPolicy {
+    /// Vests on the month's last day, returning to the grant date's day in longer
+    /// months: Jan 31 vests on Feb 28, then Mar 31.
+    #[default]
+    ClampToLastDay,
+    /// Vests on the first day of the next month: Jan 31 vests on Mar 1, then Mar 31.
+    RollForward,
+    /// Vests on the month's last day and stays on that day from then on, as when each
+    /// vesting date is counted from the one before: Jan 31 vests on Feb 28, then Mar 28.
+    RollBackward,
+}
+
+impl MonthEndPolicy {
+    /// Adds calendar months to a date, handling a day the resulting month doesn't have
+    /// according to the policy.
+    pub fn add_months(self, date: LocalDate, months: i32) -> LocalDate {
+        match self {
+            MonthEndPolicy::ClampToLastDay => date + RelativeDuration::months(months),
+            MonthEndPolicy::RollForward => {
+                let clamped_date = date + RelativeDuration::months(months);
+
+                if clamped_date.day() < date.day() {
+                    clamped_date + Duration::days(1)
+                } else {
+                    clamped_date
+                }
+            }
+            MonthEndPolicy::RollBackward if months > 0 => {
+                (0..months).fold(date, |date, _| date + RelativeDuration::months(1))
+            }
+            MonthEndPolicy::RollBackward => date + RelativeDuration::months(months),
+        }
+    }
+}
+
+/// How fractional shares are rounded to whole shares when a period's cumulative vested
+/// amount doesn't come out even. Whatever the strategy, the last period vests the full
+/// grant.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum RoundingStrategy {
+    #[default]
+    Floor,
+    Ceil,
+    /// Rounds to the nearest share, with halves rounding up.
+    RoundHalfUp,
+    /// Rounds to the nearest share, with halves rounding to the even share.
+    Bankers,
+    /// Every period after the cliff vests the same whole amount and the last one vests
+    /// the leftover shares.
+    BackLoaded,
+    /// For plans that track fractional shares, which `vested_amount` and
+    /// `calculate_vested_amount` report as is. Anything counted in whole shares, such as
+    /// schedule periods, is floored.
+    FractionalAllowed,
+}
+
+impl RoundingStrategy {
+    /// Decides whether a fractional amount rounds up to the next whole share, given
+    /// its whole part, whether there's a fraction at all and how the fraction compares
+    /// to one half.
+    const fn rounds_up(self, whole: i64, has_fraction: bool, fraction_to_half: Ordering) -> bool {
+        match self {
+            RoundingStrategy::Floor
+            | RoundingStrategy::BackLoaded
+            | RoundingStrategy::FractionalAllowed => false,
+            RoundingStrategy::Ceil => has_fraction,
+            RoundingStrategy::RoundHalfUp => !matches!(fraction_to_half, Ordering::Less),
+            RoundingStrategy::Bankers => match fraction_to_half {
+                Ordering::Less => false,
+                Ordering::Equal => whole % 2 == 1,
+                Ordering::Greater => true,
+            },
+        }
+    }
+
+    /// Divides to a whole number of shares.
+    const fn divide(self, numerator: i128, denominator: i128) -> i64 {
+        let quotient = numerator.div_euclid(denominator) as i64;
+        let remainder = numerator.rem_euclid(denominator);
+        let fraction_to_half = if remainder * 2 < denominator {
+            Ordering::Less
+        } else if remainder * 2 == denominator {
+            Ordering::Equal
+        } else {
+            Ordering::Greater
+        };
+
+        quotient + self.rounds_up(quotient, remainder > 0, fraction_to_half) as i64
+    }
+
+    /// Rounds a non-negative fractional amount to a whole number of shares.
+    const fn round(self, amount: f64) -> i64 {
+        let whole = amount as i64;
+        let fraction = amount - whole as f64;
+        let fraction_to_half = if fraction < 0.5 {
+            Ordering::Less
+        } else if fraction == 0.5 {
+            Ordering::Equal
+        } else {
+            Ordering::Greater
+        };
+
+        whole + self.rounds_up(whole, fraction > 0.0, fraction_to_half) as i64
+    }
+}
+
+/// The start of a schedule during which nothing vests, after which a share of the grant
+/// vests at once.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cliff {
+    /// Vesting intervals until the cliff is reached.
+    pub intervals: i32,
+    /// Fraction of the grant (0.0 - 1.0) vesting on the cliff date.
+    pub percentage: f32,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "VestingScheduleConfigurationDocument")
+)]
+pub struct VestingScheduleConfiguration {
+    interval: VestingInterval,
+    cliff_percentage: f32,
+    /// Vesting intervals until the cliff, i.e. months on a monthly schedule.
+    cliff_intervals: i32,
+    /// Vesting intervals until fully vested, i.e. months on a monthly schedule.
+    total_intervals: i32,
+    /// Share of the grant vesting at the end of each interval, for schedules that
+    /// aren't linear. Empty when vesting linearly after the cliff.
+    weights: Cow<'static, [f32]>,
+    rounding_strategy: RoundingStrategy,
+    /// Vests continuously by day between vesting dates after the cliff.
+    prorate_daily: bool,
+    calculation_version: CalculationVersion,
+    month_end_policy: MonthEndPolicy,
+    /// Months in the shorter interval when the length isn't a whole number of
+    /// intervals, or zero when every interval is the same length.
+    stub_months: i32,
+    stub_policy: StubPolicy,
+}
+
+/// A `VestingScheduleConfiguration` as read from a document, which must pass
+/// `validate` before it becomes one.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct VestingScheduleConfigurationDocument {
+    interval: VestingInterval,
+    cliff_percentage: f32,
+    #[serde(alias = "cliff", alias = "cliff_months")]
+    cliff_intervals: i32,
+    #[serde(alias = "length", alias = "total_vesting_months")]
+    total_intervals: i32,
+    #[serde(default)]
+    weights: Vec<f32>,
+    #[serde(default)]
+    rounding_strategy: RoundingStrategy,
+    #[serde(default)]
+    prorate_daily: bool,
+    #[serde(default)]
+    calculation_version: CalculationVersion,
+    #[serde(default)]
+    month_end_policy: MonthEndPolicy,
+    #[serde(default)]
+    stub_months: i32,
+    #[serde(default)]
+    stub_policy: StubPolicy,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<VestingScheduleConfigurationDocument> for VestingScheduleConfiguration {
+    type Error = VestingError;
+
+    fn try_from(
+        document: VestingScheduleConfigurationDocument,
+    ) -> Result<VestingScheduleConfiguration, VestingError> {
+        let configuration = VestingScheduleConfiguration {
+            interval: document.interval,
+            cliff_percentage: document.cliff_percentage,
+            cliff_intervals: document.cliff_intervals,
+            total_intervals: document.total_intervals,
+            weights: Cow::Owned(document.weights),
+            rounding_strategy: document.rounding_strategy,
+            prorate_daily: document.prorate_daily,
+            calculation_version: document.calculation_version,
+            month_end_policy: document.month_end_policy,
+            stub_months: document.stub_months,
+            stub_policy: document.stub_policy,
+        };
+
+        configuration.validate()?;
+
+        Ok(configuration)
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "GrantDocument"))]
+pub struct Grant {
+    amount: i64,
+    grant_date: LocalDate,
+    vesting_schedule: VestingScheduleConfiguration,
+    /// Vesting stops on this date and the unvested shares are forfeited.
+    termination_date: Option<LocalDate>,
+    /// Date vesting is measured from, when it differs from the grant date, e.g. a start
+    /// date before the board approved the grant.
+    vesting_commencement_date: Option<LocalDate>,
+    /// Restricted stock units settling on vest unless given.
+    grant_type: GrantType,
+}
+
+/// A `Grant` as read from a document, which must pass `validate` before it becomes
+/// one.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct GrantDocument {
+    amount: i64,
+    grant_date: LocalDate,
+    vesting_schedule: VestingScheduleConfiguration,
+    #[serde(default)]
+    termination_date: Option<LocalDate>,
+    #[serde(default)]
+    vesting_commencement_date: Option<LocalDate>,
+    #[serde(default)]
+    grant_type: GrantType,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<GrantDocument> for Grant {
+    type Error = VestingError;
+
+    fn try_from(document: GrantDocument) -> Result<Grant, VestingError> {
+        let grant = Grant {
+            amount: document.amount,
+            grant_date: document.grant_date,
+            vesting_schedule: document.vesting_schedule,
+            termination_date: document.termination_date,
+            vesting_commencement_date: document.vesting_commencement_date,
+            grant_type: document.grant_type,
+        };
+
+        grant.validate()?;
+
+        Ok(grant)
+    }
+}
+
+/// Calculates the difference of calendar months between two dates, ignoring the day of month.
+pub(crate) fn months_between(from_date: LocalDate, to_date: LocalDate) -> i32 {
+    let year_difference = to_date.year() - from_date.year();
+    (year_difference * 12) + (to_date.month() as i32 - from_date.month() as i32)
+}
+
+impl VestingScheduleConfiguration {
+    /// Creates a vesting schedule with the cliff and length counted in vesting intervals,
+    /// e.g. a 4 quarter cliff on a 16 quarter schedule.
+    pub const fn new(
+        interval: VestingInterval,
+        cliff: i32,
+        cliff_percentage: f32,
+        length: i32,
+    ) -> Self {
+        VestingScheduleConfiguration {
+            interval,
+            cliff_percentage,
+            cliff_intervals: cliff,
+            total_intervals: length,
+            weights: Cow::Borrowed(&[]),
+            rounding_strategy: RoundingStrategy::Floor,
+            prorate_daily: false,
+            calculation_version: CalculationVersion::V1,
+            month_end_policy: MonthEndPolicy::ClampToLastDay,
+            stub_months: 0,
+            stub_policy: StubPolicy::Trailing,
+        }
+    }
+
+    /// Creates a schedule with no cliff, vesting evenly from the first interval.
+    pub const fn without_cliff(interval: VestingInterval, length: i32) -> Self {
+        VestingScheduleConfiguration::new(interval, 0, 0.0, length)
+    }
+
+    /// Returns the schedule's cliff, or `None` if vesting starts with the first
+    /// interval. A cliff of zero intervals is no cliff, so its percentage is ignored.
+    pub const fn cliff(&self) -> Option<Cliff> {
+        if self.cliff_intervals > 0 {
+            Some(Cliff {
+                intervals: self.cliff_intervals,
+                percentage: self.cliff_percentage,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Creates a schedule vesting a different share of the grant at the end of each
+    /// interval, e.g. `[0.05, 0.15, 0.4, 0.4]` annually for a back-loaded 5/15/40/40
+    /// schedule. The weights are fractions of the grant and should sum to 1.0.
+    pub const fn weighted(interval: VestingInterval, weights: &'static [f32]) -> Self {
+        VestingScheduleConfiguration {
+            interval,
+            cliff_percentage: 0.0,
+            cliff_intervals: 0,
+            total_intervals: weights.len() as i32,
+            weights: Cow::Borrowed(weights),
+            rounding_strategy: RoundingStrategy::Floor,
+            prorate_daily: false,
+            calculation_version: CalculationVersion::V1,
+            month_end_policy: MonthEndPolicy::ClampToLastDay,
+            stub_months: 0,
+            stub_policy: StubPolicy::Trailing,
+        }
+    }
+
+    /// Creates a monthly vesting schedule. Usable in const contexts for compile-time fixtures.
+    pub const fn monthly(
+        cliff_months: i32,
+        cliff_percentage: f32,
+        total_vesting_months: i32,
+    ) -> Self {
+        VestingScheduleConfiguration::new(
+            VestingInterval::Monthly,
+            cliff_months,
+            cliff_percentage,
+            total_vesting_months,
+        )
+    }
+
+    /// Months from the grant date until the cliff is reached. Weekly schedules count
+    /// 52 weeks a year, rounding down to whole months.
+    pub const fn cliff_months(&self) -> i32 {
+        self.months_after_intervals(self.cliff_intervals)
+    }
+
+    /// Months from the grant date until the grant is fully vested. Weekly schedules
+    /// count 52 weeks a year, rounding down to whole months.
+    pub const fn total_vesting_months(&self) -> i32 {
+        self.months_after_intervals(self.total_intervals)
+    }
+
+    /// Converts a number of intervals into whole months, counting a stub as the months
+    /// it covers and 52 weeks a year for weekly intervals.
+    const fn months_after_intervals(&self, intervals: i32) -> i32 {
+        match self.interval.length() {
+            IntervalLength::Months(months) => self.stub_months_after_intervals(intervals, months),
+            IntervalLength::Days(_) => self.interval.to_months(intervals),
+        }
+    }
+
+    /// Months from the grant date to the end of the given number of month-based
+    /// intervals, where a stub interval covers only its own months.
+    const fn stub_months_after_intervals(&self, intervals: i32, months: i32) -> i32 {
+        if self.stub_months == 0 || intervals <= 0 {
+            return intervals * months;
+        }
+
+        match self.stub_policy {
+            StubPolicy::Leading => self.stub_months + (intervals - 1) * months,
+            StubPolicy::Trailing if intervals >= self.total_intervals => {
+                (self.total_intervals - 1) * months + self.stub_months
+            }
+            StubPolicy::Trailing => intervals * months,
+        }
+    }
+
+    /// Whole month-based intervals elapsed after the given number of months, where a
+    /// stub interval ends once its own months have elapsed.
+    const fn stub_intervals_after_months(&self, months_elapsed: i32, months: i32) -> i32 {
+        if self.stub_months == 0 || months_elapsed < 0 {
+            return months_elapsed.div_euclid(months);
+        }
+
+        match self.stub_policy {
+            StubPolicy::Leading if months_elapsed < self.stub_months => 0,
+            StubPolicy::Leading => 1 + (months_elapsed - self.stub_months) / months,
+            StubPolicy::Trailing
+                if months_elapsed
+                    >= self.stub_months_after_intervals(self.total_intervals, months) =>
+            {
+                self.total_intervals
+            }
+            StubPolicy::Trailing => months_elapsed / months,
+        }
+    }
+
+    /// Progress through the schedule after the given number of intervals, counted in
+    /// months when a stub makes the intervals uneven so that the stub vests in
+    /// proportion to its length, and in intervals otherwise.
+    const fn progress_after_intervals(&self, intervals: i32) -> i64 {
+        if self.stub_months > 0 {
+            self.months_after_intervals(intervals) as i64
+        } else {
+            intervals as i64
+        }
+    }
+
+    /// Months from the grant date until the grant is fully vested.
+    #[deprecated(note = "use `total_vesting_months` instead")]
+    pub const fn length(&self) -> i32 {
+        self.total_vesting_months()
+    }
+
+    /// Rounds vested shares according to the equity plan's rules instead of flooring.
+    pub const fn with_rounding_strategy(mut self, rounding_strategy: RoundingStrategy) -> Self {
+        self.rounding_strategy = rounding_strategy;
+        self
+    }
+
+    /// Vests continuously by day after the cliff, so the vested amount grows between
+    /// vesting dates instead of jumping on each of them. Whole share counts, such as
+    /// schedule periods, still change only on vesting dates. Weighted schedules aren't
+    /// prorated.
+    pub const fn with_daily_proration(mut self, prorate_daily: bool) -> Self {
+        self.prorate_daily = prorate_daily;
+        self
+    }
+
+    /// Opts the schedule into a specific revision of the vesting math.
+    pub const fn with_calculation_version(
+        mut self,
+        calculation_version: CalculationVersion,
+    ) -> Self {
+        self.calculation_version = calculation_version;
+        self
+    }
+
+    /// Sets where vesting dates fall in months without the grant date's day.
+    pub const fn with_month_end_policy(mut self, month_end_policy: MonthEndPolicy) -> Self {
+        self.month_end_policy = month_end_policy;
+        self
+    }
+
+    /// Calculates the number of months elapsed since the grant date under the
+    /// configured calculation version. Month-end policies other than the default
+    /// count months by their anniversaries, as under `V2`, so they agree with the
+    /// vesting dates.
+    fn months_elapsed(&self, grant_date: LocalDate, date: LocalDate) -> i32 {
+        let months = months_between(grant_date, date);
+
+        match (self.calculation_version, self.month_end_policy) {
+            (CalculationVersion::V1, MonthEndPolicy::ClampToLastDay) => months,
+            _ => {
+                if self.month_end_policy.add_months(grant_date, months) > date {
+                    months - 1
+                } else {
+                    months
+                }
+            }
+        }
+    }
+
+    /// Checks that the schedule's terms are meaningful, e.g. that the cliff doesn't
+    /// come after the end of the schedule.
+    pub fn validate(&self) -> Result<(), VestingError> {
+        if self.total_intervals < 0 {
+            return Err(VestingError::NegativeLength(self.total_intervals));
+        }
+
+        if self.cliff_intervals < 0 {
+            return Err(VestingError::NegativeCliff(self.cliff_intervals));
+        }
+
+        if self.cliff_intervals > self.total_intervals {
+            return Err(VestingError::CliffExceedsLength {
+                cliff: self.cliff_intervals,
+                length: self.total_intervals,
+            });
+        }
+
+        if !(0.0..=1.0).contains(&self.cliff_percentage) {
+            return Err(VestingError::CliffPercentageOutOfRange(
+                self.cliff_percentage,
+            ));
+        }
+
+        let months_per_interval = match self.interval.length() {
+            IntervalLength::Months(months) => months,
+            IntervalLength::Days(_) => 1,
+        };
+
+        if !(0..months_per_interval).contains(&self.stub_months) {
+            return Err(VestingError::UnevenDuration(VestingDuration::Months(
+                self.stub_months,
+            )));
+        }
+
+        if !self.weights().is_empty() {
+            let total: f32 = self.weights().iter().sum();
+
+            if (total - 1.0).abs() > WEIGHT_TOLERANCE {
+                return Err(VestingError::WeightsDoNotSumToOne(total));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Calculates the number of whole vesting intervals elapsed since the grant date.
+    fn intervals_elapsed(&self, grant_date: LocalDate, date: LocalDate) -> i32 {
+        match self.interval.length() {
+            IntervalLength::Months(months) => {
+                self.stub_intervals_after_months(self.months_elapsed(grant_date, date), months)
+            }
+            IntervalLength::Days(days) => {
+                (date - grant_date).num_days().div_euclid(days as i64) as i32
+            }
+        }
+    }
+
+    /// Date the given number of vesting intervals after the grant date, following the
+    /// month-end policy.
+    pub(crate) fn interval_date(&self, grant_date: LocalDate, intervals: i32) -> LocalDate {
+        match self.interval.length() {
+            IntervalLength::Months(months) => self.month_end_policy.add_months(
+                grant_date,
+                self.stub_months_after_intervals(intervals, months),
+            ),
+            IntervalLength::Days(days) => grant_date + Duration::days((intervals * days) as i64),
+        }
+    }
+
+    /// Calculates the amount vested on the given date without rounding to whole shares.
+    pub(crate) fn precise_vested_amount_on(
+        &self,
+        amount: i64,
+        grant_date: LocalDate,
+        date: LocalDate,
+    ) -> f64 {
+        let intervals_elapsed = self.intervals_elapsed(grant_date, date).max(0);
+        let amount = amount as f64;
+
+        if !self.weights().is_empty() {
+            return match self.weighted_percentage(intervals_elapsed) {
+                Some(percentage) => amount * percentage,
+                None => amount,
+            };
+        }
+
+        if self.is_before_cliff(intervals_elapsed) {
+            return 0.0;
+        } else if intervals_elapsed >= self.total_intervals {
+            return amount;
+        }
+
+        let (cliff_intervals, cliff_amount) = match self.cliff() {
+            Some(cliff) => (cliff.intervals, amount * cliff.percentage as f64),
+            None => (0, 0.0),
+        };
+
+        let progress_past_cliff = self.progress_after_intervals(intervals_elapsed)
+            - self.progress_after_intervals(cliff_intervals);
+        let progress_after_cliff = self.progress_after_intervals(self.total_intervals)
+            - self.progress_after_intervals(cliff_intervals);
+
+        cliff_amount
+            + (amount - cliff_amount) * progress_past_cliff as f64 / progress_after_cliff as f64
+    }
+
+    /// Calculates the whole number of shares vested on the given date.
+    pub(crate) fn vested_shares_on(
+        &self,
+        amount: i64,
+        grant_date: LocalDate,
+        date: LocalDate,
+    ) -> i64 {
+        self.vested_shares_after_intervals(amount, self.intervals_elapsed(grant_date, date))
+    }
+
+    /// Calculates the whole number of shares vested after the given number of elapsed
+    /// months, rounded according to the rounding strategy. Weekly schedules count 52
+    /// weeks a year. Past the cliff this uses exact integer arithmetic, so the final
+    /// period always vests the full grant and it can be evaluated in const contexts.
+    pub const fn vested_shares(&self, amount: i64, months_elapsed: i32) -> i64 {
+        let intervals_elapsed = match self.interval.length() {
+            IntervalLength::Months(months) => {
+                self.stub_intervals_after_months(months_elapsed, months)
+            }
+            IntervalLength::Days(days) => {
+                (months_elapsed * DAYS_PER_WEEKLY_YEAR / 12).div_euclid(days)
+            }
+        };
+
+        self.vested_shares_after_intervals(amount, intervals_elapsed)
+    }
+
+    /// Calculates the whole number of shares vested after the given number of elapsed
+    /// vesting intervals.
+    const fn vested_shares_after_intervals(&self, amount: i64, intervals_elapsed: i32) -> i64 {
+        let rounding_strategy = self.rounding_strategy;
+        // Nothing has vested before the schedule starts, even without a cliff.
+        let intervals_elapsed = if intervals_elapsed < 0 {
+            0
+        } else {
+            intervals_elapsed
+        };
+
+        if !self.weights().is_empty() {
+            return match self.weighted_percentage(intervals_elapsed) {
+                Some(percentage) => rounding_strategy.round(amount as f64 * percentage),
+                None => amount,
+            };
+        }
+
+        if self.is_before_cliff(intervals_elapsed) {
+            return 0;
+        } else if intervals_elapsed >= self.total_intervals {
+            return amount;
+        }
+
+        let (cliff_intervals, cliff_shares) = match self.cliff() {
+            Some(cliff) => (
+                cliff.intervals,
+                rounding_strategy.round(amount as f64 * cliff.percentage as f64),
+            ),
+            None => (0, 0),
+        };
+        let remaining_shares = amount - cliff_shares;
+        let progress_past_cliff = self.progress_after_intervals(intervals_elapsed)
+            - self.progress_after_intervals(cliff_intervals);
+        let progress_after_cliff = self.progress_after_intervals(self.total_intervals)
+            - self.progress_after_intervals(cliff_intervals);
+
+        let vested_after_cliff = match rounding_strategy {
+            RoundingStrategy::BackLoaded => {
+                remaining_shares / progress_after_cliff * progress_past_cliff
+            }
+            _ => rounding_strategy.divide(
+                remaining_shares as i128 * progress_past_cliff as i128,
+                progress_after_cliff as i128,
+            ),
+        };
+
+        cliff_shares + vested_after_cliff
+    }
+
+    /// Share of the grant vesting at the end of each interval, empty when vesting
+    /// linearly after the cliff.
+    pub const fn weights(&self) -> &[f32] {
+        match &self.weights {
+            Cow::Borrowed(weights) => weights,
+            Cow::Owned(weights) => weights.as_slice(),
+        }
+    }
+
+    /// Sums the weights of the intervals elapsed so far, or returns `None` once every
+    /// weighted interval has elapsed and the grant is fully vested.
+    const fn weighted_percentage(&self, intervals_elapsed: i32) -> Option<f64> {
+        if intervals_elapsed >= self.weights().len() as i32 {
+            return None;
+        }
+
+        let mut percentage = 0.0;
+        let mut interval = 0;
+
+        while interval < intervals_elapsed {
+            percentage += self.weights()[interval as usize] as f64;
+            interval += 1;
+        }
+
+        Some(percentage)
+    }
+
+    /// Checks if the given number of elapsed intervals is still in the cliff period.
+    const fn is_before_cliff(&self, intervals_elapsed: i32) -> bool {
+        match self.cliff() {
+            Some(cliff) => intervals_elapsed < cliff.intervals,
+            None => false,
+        }
+    }
+
+    /// Returns the amount of vested equity when cliff period has been reached, or
+    /// nothing without a cliff.
+    fn cliff_vested_amount(&self, amount: i64) -> f32 {
+        self.cliff()
+            .map_or(0.0, |cliff| amount as f32 * cliff.percentage)
+    }
+}
+
+/// Calculates the vested amount on a given future date from borrowed grant terms,
+/// for callers that don't hold an owned `Grant`.
+pub fn vested_amount(
+    configuration: &VestingScheduleConfiguration,
+    amount: i64,
+    grant_date: LocalDate,
+    future_date: LocalDate,
+) -> f32 {
+    if future_date < grant_date {
+        return 0.0;
+    }
+
+    let intervals_elapsed = configuration.intervals_elapsed(grant_date, future_date);
+
+    if !configuration.weights().is_empty() {
+        return match configuration.weighted_percentage(intervals_elapsed) {
+            Some(percentage) => (amount as f64 * percentage) as f32,
+            None => amount as f32,
+        };
+    }
+
+    if configuration.is_before_cliff(intervals_elapsed) {
+        0.0
+    } else if intervals_elapsed >= configuration.total_intervals {
+        amount as f32
+    } else {
+        let cliff_intervals = configuration.cliff().map_or(0, |cliff| cliff.intervals);
+        let progress_past_cliff = configuration.progress_after_intervals(intervals_elapsed)
+            - configuration.progress_after_intervals(cliff_intervals);
+        let remaining_amount_after_cliff: f32 =
+            amount as f32 - configuration.cliff_vested_amount(amount);
+        let vested_per_progress: f32 = remaining_amount_after_cliff
+            / (configuration.progress_after_intervals(configuration.total_intervals)
+                - configuration.progress_after_intervals(cliff_intervals)) as f32;
+
+        let prorated_interval = if configuration.prorate_daily
+            && intervals_elapsed < configuration.total_intervals
+        {
+            let interval_progress = configuration.progress_after_intervals(intervals_elapsed + 1)
+                - configuration.progress_after_intervals(intervals_elapsed);
+
+            vested_per_progress
+                * interval_progress as f32
+                * elapsed_interval_fraction(
+                    configuration,
+                    grant_date,
+                    intervals_elapsed,
+                    future_date,
+                )
+        } else {
+            0.0
+        };
+
+        let vested_after_cliff: f32 = vested_per_progress * progress_past_cliff as f32;
+
+        configuration.cliff_vested_amount(amount) + vested_after_cliff + prorated_interval
+    }
+}
+
+/// Fraction of the current vesting interval elapsed by the given date, between 0.0 and
+/// 1.0, used to prorate vesting by day.
+fn elapsed_interval_fraction(
+    configuration: &VestingScheduleConfiguration,
+    grant_date: LocalDate,
+    intervals_elapsed: i32,
+    date: LocalDate,
+) -> f32 {
+    let interval_start = configuration.interval_date(grant_date, intervals_elapsed);
+    let interval_end = configuration.interval_date(grant_date, intervals_elapsed + 1);
+    let elapsed_days = (date - interval_start).num_days();
+    let interval_days = (interval_end - interval_start).num_days();
+
+    (elapsed_days as f32 / interval_days as f32).clamp(0.0, 1.0)
+}
+
+/// Calls the visitor with each vesting period in date order from borrowed grant terms,
+/// without collecting the periods into a schedule first. An unvalidated configuration
+/// with a negative length visits no periods.
+pub fn visit_vesting_periods<F>(
+    configuration: &VestingScheduleConfiguration,
+    amount: i64,
+    grant_date: LocalDate,
+    mut visitor: F,
+) where
+    F: FnMut(VestingPeriod),
+{
+    let mut previous_cumulative_amount = 0;
+
+    for interval in 0..=configuration.total_intervals {
+        let date = configuration.interval_date(grant_date, interval);
+        let cumulative_vested_amount = configuration.vested_shares_on(amount, grant_date, date);
+
+        visitor(VestingPeriod {
+            date,
+            cumulative_vested_amount,
+            vested_this_period: cumulative_vested_amount - previous_cumulative_amount,
+            precise_cumulative_amount: configuration
+                .precise_vested_amount_on(amount, grant_date, date),
+        });
+
+        previous_cumulative_amount = cumulative_vested_amount;
+    }
+}
+
+/// Calculates the cumulative whole shares vested at each of the first `N` months,
+/// so simple schedules can be computed in const contexts. Keep the configuration in
+/// a `static` to borrow it there, since a `const` one would be dropped at compile time.
+pub const fn vested_shares_by_month<const N: usize>(
+    configuration: &VestingScheduleConfiguration,
+    amount: i64,
+) -> [i64; N] {
+    let mut vested_shares = [0; N];
+    let mut month = 0;
+
+    while month < N {
+        vested_shares[month] = configuration.vested_shares(amount, month as i32);
+        month += 1;
+    }
+
+    vested_shares
+}
+
+/// Calculate a full vesting schedule from borrowed grant terms, listing the vested
+/// amounts per vesting period. An unvalidated configuration with a negative length
+/// yields a schedule without periods rather than panicking.
+pub fn vesting_schedule(
+    configuration: &VestingScheduleConfiguration,
+    amount: i64,
+    grant_date: LocalDate,
+) -> VestingSchedule {
+    let to_date = configuration.interval_date(grant_date, configuration.total_intervals);
+    let capacity =
+        usize::try_from(configuration.total_intervals).map_or(0, |intervals| intervals + 1);
+    let mut periods = Vec::with_capacity(capacity);
+
+    visit_vesting_periods(configuration, amount, grant_date, |period| {
+        periods.push(period)
+    });
+
+    VestingSchedule {
+        periods,
+        from_date: grant_date,
+        to_date,
+        forfeited_amount: 0,
+    }
+}
+
+impl Grant {
+    /// Checks that the grant's terms produce a meaningful schedule.
+    pub fn validate(&self) -> Result<(), VestingError> {
+        if self.amount < 0 {
+            return Err(VestingError::NegativeAmount(self.amount));
+        }
+
+        if self.amount == 0 {
+            return Err(VestingError::ZeroAmount);
+        }
+
+        self.vesting_schedule.validate()
+    }
+
+    /// Terminates the grant on the given date. Nothing vests after it and the
+    /// remaining unvested shares are forfeited.
+    pub fn terminate(&mut self, termination_date: LocalDate) {
+        self.termination_date = Some(termination_date);
+    }
+
+    /// Date vesting is measured from: the vesting commencement date if given, otherwise
+    /// the grant date.
+    pub fn vesting_commencement_date(&self) -> LocalDate {
+        self.vesting_commencement_date.unwrap_or(self.grant_date)
+    }
+
+    /// Measures vesting from the given date instead of the grant date. Shares that
+    /// vested between the two by the schedule vest all at once on the grant date.
+    pub fn with_vesting_commencement_date(mut self, vesting_commencement_date: LocalDate) -> Grant {
+        self.vesting_commencement_date = Some(vesting_commencement_date);
+        self
+    }
+
+    /// Checks if the grant has been terminated on or before the given date.
+    pub(crate) fn is_terminated_on(&self, date: LocalDate) -> bool {
+        self.termination_date
+            .is_some_and(|termination_date| termination_date <= date)
+    }
+
+    /// Calculates the vested amount on a given future date.
+    pub fn calculate_vested_amount(&self, future_date: LocalDate) -> f32 {
+        let date = match self.termination_date {
+            Some(termination_date) if termination_date < future_date => termination_date,
+            _ => future_date,
+        };
+
+        if date < self.grant_date || date < self.vesting_commencement_date() {
+            return 0.0;
+        }
+
+        vested_amount(
+            &self.vesting_schedule,
+            self.amount,
+            self.vesting_commencement_date(),
+            date,
+        )
+    }
+
+    /// Calculates the whole number of shares vested on a given date.
+    pub fn calculate_vested_shares(&self, date: LocalDate) -> i64 {
+        let date = match self.termination_date {
+            Some(termination_date) if termination_date < date => termination_date,
+            _ => date,
+        };
+
+        if date < self.grant_date || date < self.vesting_commencement_date() {
+            return 0;
+        }
+
+        self.vesting_schedule
+            .vested_shares_on(self.amount, self.vesting_commencement_date(), date)
+    }
+
+    /// Lists the dates on which shares vest along with the amount vesting on each.
+    pub(crate) fn vest_events(&self) -> Vec<VestEvent> {
+        self.calculate_vesting_schedule()
+            .periods
+            .into_iter()
+            .filter(|period| period.vested_this_period > 0)
+            .map(|period| VestEvent {
+                date: period.date,
+                amount: period.vested_this_period,
+            })
+            .collect()
+    }
+
+    /// Calls the visitor with each vesting period in date order, without collecting
+    /// the periods into a schedule first.
+    pub fn visit_vesting_periods<F>(&self, visitor: F)
+    where
+        F: FnMut(VestingPeriod),
+    {
+        self.vesting_events().for_each(visitor)
+    }
+
+    /// Lazily yields each vesting period in date order, ending on the termination date
+    /// of a terminated grant.
+    pub fn vesting_events(&self) -> VestingPeriods<'_> {
+        VestingPeriods {
+            grant: self,
+            intervals: (0..=self.vesting_schedule.total_intervals).peekable(),
+            previous_cumulative_amount: 0,
+        }
+    }
+
+    /// Calculate a full vesting schedule, listing the vested amounts per vesting period.
+    /// A terminated grant's schedule ends on the termination date, and periods before
+    /// the grant date are caught up in a single period on it.
+    pub fn calculate_vesting_schedule(&self) -> VestingSchedule {
+        let mut vesting_schedule = vesting_schedule(
+            &self.vesting_schedule,
+            self.amount,
+            self.vesting_commencement_date(),
+        );
+
+        let catch_up_index = vesting_schedule
+            .periods
+            .partition_point(|period| period.date < self.grant_date);
+
+        if catch_up_index > 0 {
+            let periods = &mut vesting_schedule.periods;
+            let caught_up_period = periods[catch_up_index - 1].clone();
+            periods.drain(..catch_up_index);
+
+            if periods
+                .first()
+                .is_none_or(|period| period.date > self.grant_date)
+            {
+                periods.insert(
+                    0,
+                    VestingPeriod {
+                        date: self.grant_date,
+                        ..caught_up_period
+                    },
+                );
+            }
+
+            VestingPeriod::update_vested_this_period(periods);
+        }
+
+        if let Some(termination_date) = self.termination_date {
+            vesting_schedule
+                .periods
+                .retain(|period| period.date <= termination_date);
+            vesting_schedule.to_date = vesting_schedule.to_date.min(termination_date);
+            vesting_schedule.forfeited_amount =
+                self.amount - self.calculate_vested_shares(termination_date);
+        }
+
+        vesting_schedule
+    }
+
+    /// Calculates the percentage of the grant vested on the given date, from 0 to 100.
+    pub fn percent_vested_on(&self, date: LocalDate) -> f64 {
+        if self.amount == 0 {
+            return 0.0;
+        }
+
+        self.calculate_vested_shares(date) as f64 / self.amount as f64 * 100.0
+    }
+
+    /// Finds the first period after the given date in which shares vest, or `None` if
+    /// nothing vests after it.
+    pub fn next_vesting_event(&self, after_date: LocalDate) -> Option<VestingPeriod> {
+        self.calculate_vesting_schedule()
+            .periods
+            .into_iter()
+            .find(|period| period.date > after_date && period.vested_this_period > 0)
+    }
+
+    /// Returns the date the whole grant has vested, or `None` if it's terminated first.
+    pub fn fully_vested_date(&self) -> Option<LocalDate> {
+        self.calculate_vesting_schedule()
+            .periods
+            .into_iter()
+            .find(|period| period.cumulative_vested_amount >= self.amount)
+            .map(|period| period.date)
+    }
+}
+
+/// Iterator over a grant's vesting periods, created by `Grant::vesting_events`.
+pub struct VestingPeriods<'a> {
+    grant: &'a Grant,
+    intervals: Peekable<RangeInclusive<i32>>,
+    previous_cumulative_amount: i64,
+}
+
+impl Iterator for VestingPeriods<'_> {
+    type Item = VestingPeriod;
+
+    fn next(&mut self) -> Option<VestingPeriod> {
+        let configuration = &self.grant.vesting_schedule;
+        let commencement_date = self.grant.vesting_commencement_date();
+        let grant_date = self.grant.grant_date;
+        let mut date = configuration.interval_date(commencement_date, self.intervals.next()?);
+
+        if date < grant_date {
+            // Periods before the grant date are caught up on it.
+            while let Some(interval) = self.intervals.next_if(|interval| {
+                configuration.interval_date(commencement_date, *interval) <= grant_date
+            }) {
+                date = configuration.interval_date(commencement_date, interval);
+            }
+
+            date = date.max(grant_date);
+        }
+
+        if self
+            .grant
+            .termination_date
+            .is_some_and(|termination_date| date > termination_date)
+        {
+            return None;
+        }
+
+        let cumulative_vested_amount =
+            configuration.vested_shares_on(self.grant.amount, commencement_date, date);
+        let vested_this_period = cumulative_vested_amount - self.previous_cumulative_amount;
+        self.previous_cumulative_amount = cumulative_vested_amount;
+
+        Some(VestingPeriod {
+            date,
+            cumulative_vested_amount,
+            vested_this_period,
+            precise_cumulative_amount: configuration.precise_vested_amount_on(
+                self.grant.amount,
+                commencement_date,
+                date,
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+impl Grant {
+    /// Creates an active restricted stock unit grant vesting from its grant date, for
+    /// tests to override only the terms they exercise with struct update syntax.
+    pub(crate) fn for_test(
+        amount: i64,
+        grant_date: LocalDate,
+        vesting_schedule: VestingScheduleConfiguration,
+    ) -> Grant {
+        Grant {
+            amount,
+            grant_date,
+            vesting_schedule,
+            termination_date: None,
+            vesting_commencement_date: None,
+            grant_type: GrantType::default(),
+        }
+    }
+}
+
+/// Dollars for test amounts, e.g. `usd(12.5)` for $12.50.
+#[cfg(test)]
+pub(crate) fn usd(amount: f64) -> Money {
+    Money::from_major_units(amount, Currency::Usd)
 }
 
-struct VestingSchedule {
-    from_date: Date<Utc>,
-    to_date: Date<Utc>,
-    periods: Vec<VestingPeriod>,
-}
+/// Dollars per share for test prices, keeping sub-cent amounts like `usd_price(0.0001)`.
+#[cfg(test)]
+pub(crate) fn usd_price(amount: f64) -> Price {
+    Price::from_major_units(amount, Currency::Usd)
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use crate::VestingPeriod;
+
+    use super::{
+        vested_amount, vested_shares_by_month, CalculationVersion, Cliff, Grant, LocalDate,
+        MonthEndPolicy, RoundingStrategy, VestingError, VestingInterval,
+        VestingScheduleConfiguration,
+    };
+
+    #[test]
+    fn it_can_calculate_vested_amounts_for_given_dates() {
+        let grant = Grant::for_test(
+            10_000,
+            LocalDate::from_ymd(2020, 2, 6),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        );
+
+        /*
+         * 10,000 stock options, 25% cliff after 12 months, 48 month vesting schedule.
+         * - 2021/2/6: 25% options vest, 2500 options vested
+         * - 2021/3/6: +208 options vest, 2708 options vested
+         * - 2022/2/6: 50% options vested, 5000 options vested
+         */
+
+        assert_relative_eq!(
+            grant.calculate_vested_amount(LocalDate::from_ymd(2020, 8, 6)),
+            0.0,
+            max_relative = 0.005
+        );
+        assert_relative_eq!(
+            grant.calculate_vested_amount(LocalDate::from_ymd(2020, 12, 6)),
+            0.0,
+            max_relative = 0.005
+        );
+        assert_relative_eq!(
+            grant.calculate_vested_amount(LocalDate::from_ymd(2021, 2, 6)),
+            2500.0,
+            max_relative = 0.005
+        );
+        assert_relative_eq!(
+            grant.calculate_vested_amount(LocalDate::from_ymd(2021, 3, 6)),
+            2708.33,
+            max_relative = 0.005
+        );
+        assert_relative_eq!(
+            grant.calculate_vested_amount(LocalDate::from_ymd(2022, 2, 6)),
+            5000.0,
+            max_relative = 0.005
+        );
+        assert_relative_eq!(
+            grant.calculate_vested_amount(LocalDate::from_ymd(2022, 3, 6)),
+            5208.33,
+            max_relative = 0.005
+        );
+        assert_relative_eq!(
+            grant.calculate_vested_amount(LocalDate::from_ymd(2022, 4, 6)),
+            5416.66,
+            max_relative = 0.005
+        );
+        assert_relative_eq!(
+            grant.calculate_vested_amount(LocalDate::from_ymd(2023, 2, 6)),
+            7500.00,
+            max_relative = 0.005
+        );
+        assert_relative_eq!(
+            grant.calculate_vested_amount(LocalDate::from_ymd(2023, 3, 6)),
+            7708.33,
+            max_relative = 0.005
+        );
+        assert_relative_eq!(
+            grant.calculate_vested_amount(LocalDate::from_ymd(2024, 3, 6)),
+            10000.00,
+            max_relative = 0.005
+        );
+    }
+
+    #[test]
+    fn it_can_calculate_full_vesting_schedule() {
+        let grant = Grant::for_test(
+            10_000,
+            LocalDate::from_ymd(2020, 2, 6),
+            VestingScheduleConfiguration::monthly(6, 0.25, 12),
+        );
+
+        let vesting_schedule = grant.calculate_vesting_schedule();
+
+        assert_eq!(vesting_schedule.from_date, grant.grant_date);
+        assert_eq!(vesting_schedule.to_date, LocalDate::from_ymd(2021, 2, 6));
+
+        let periods = vec![
+            VestingPeriod {
+                date: LocalDate::from_ymd(2020, 2, 6),
+                cumulative_vested_amount: 0,
+                vested_this_period: 0,
+                precise_cumulative_amount: 0.0,
+            },
+            VestingPeriod {
+                date: LocalDate::from_ymd(2020, 3, 6),
+                cumulative_vested_amount: 0,
+                vested_this_period: 0,
+                precise_cumulative_amount: 0.0,
+            },
+            VestingPeriod {
+                date: LocalDate::from_ymd(2020, 4, 6),
+                cumulative_vested_amount: 0,
+                vested_this_period: 0,
+                precise_cumulative_amount: 0.0,
+            },
+            VestingPeriod {
+                date: LocalDate::from_ymd(2020, 5, 6),
+                cumulative_vested_amount: 0,
+                vested_this_period: 0,
+                precise_cumulative_amount: 0.0,
+            },
+            VestingPeriod {
+                date: LocalDate::from_ymd(2020, 6, 6),
+                cumulative_vested_amount: 0,
+                vested_this_period: 0,
+                precise_cumulative_amount: 0.0,
+            },
+            VestingPeriod {
+                date: LocalDate::from_ymd(2020, 7, 6),
+                cumulative_vested_amount: 0,
+                vested_this_period: 0,
+                precise_cumulative_amount: 0.0,
+            },
+            VestingPeriod {
+                date: LocalDate::from_ymd(2020, 8, 6),
+                cumulative_vested_amount: 2500,
+                vested_this_period: 2500,
+                precise_cumulative_amount: 2500.0,
+            },
+            VestingPeriod {
+                date: LocalDate::from_ymd(2020, 9, 6),
+                cumulative_vested_amount: 3750,
+                vested_this_period: 1250,
+                precise_cumulative_amount: 3750.0,
+            },
+            VestingPeriod {
+                date: LocalDate::from_ymd(2020, 10, 6),
+                cumulative_vested_amount: 5000,
+                vested_this_period: 1250,
+                precise_cumulative_amount: 5000.0,
+            },
+            VestingPeriod {
+                date: LocalDate::from_ymd(2020, 11, 6),
+                cumulative_vested_amount: 6250,
+                vested_this_period: 1250,
+                precise_cumulative_amount: 6250.0,
+            },
+            VestingPeriod {
+                date: LocalDate::from_ymd(2020, 12, 6),
+                cumulative_vested_amount: 7500,
+                vested_this_period: 1250,
+                precise_cumulative_amount: 7500.0,
+            },
+            VestingPeriod {
+                date: LocalDate::from_ymd(2021, 1, 6),
+                cumulative_vested_amount: 8750,
+                vested_this_period: 1250,
+                precise_cumulative_amount: 8750.0,
+            },
+            VestingPeriod {
+                date: LocalDate::from_ymd(2021, 2, 6),
+                cumulative_vested_amount: 10000,
+                vested_this_period: 1250,
+                precise_cumulative_amount: 10000.0,
+            },
+        ];
+
+        assert_eq!(vesting_schedule.periods, periods)
+    }
+
+    #[test]
+    fn it_can_calculate_vested_amounts_from_borrowed_terms() {
+        let configuration = VestingScheduleConfiguration::monthly(12, 0.25, 48);
+
+        assert_relative_eq!(
+            vested_amount(
+                &configuration,
+                10_000,
+                LocalDate::from_ymd(2020, 2, 6),
+                LocalDate::from_ymd(2021, 3, 6)
+            ),
+            2708.33,
+            max_relative = 0.005
+        );
+        assert_relative_eq!(
+            vested_amount(
+                &configuration,
+                4_000,
+                LocalDate::from_ymd(2021, 5, 1),
+                LocalDate::from_ymd(2022, 5, 1)
+            ),
+            1000.0,
+            max_relative = 0.005
+        );
+    }
+
+    #[test]
+    fn it_can_opt_into_anniversary_based_month_counting() {
+        let v1 = Grant::for_test(
+            1_200,
+            LocalDate::from_ymd(2020, 1, 31),
+            VestingScheduleConfiguration::monthly(0, 0.0, 4),
+        );
+        let v2 = Grant::for_test(
+            1_200,
+            LocalDate::from_ymd(2020, 1, 31),
+            VestingScheduleConfiguration::monthly(0, 0.0, 4)
+                .with_calculation_version(CalculationVersion::V2),
+        );
+
+        assert_eq!(
+            v1.calculate_vested_amount(LocalDate::from_ymd(2020, 2, 1)),
+            300.0
+        );
+        assert_eq!(
+            v2.calculate_vested_amount(LocalDate::from_ymd(2020, 2, 1)),
+            0.0
+        );
+        assert_eq!(
+            v2.calculate_vested_amount(LocalDate::from_ymd(2020, 2, 28)),
+            0.0
+        );
+        assert_eq!(
+            v2.calculate_vested_amount(LocalDate::from_ymd(2020, 2, 29)),
+            300.0
+        );
+        assert_eq!(
+            v2.calculate_vested_amount(LocalDate::from_ymd(2020, 5, 31)),
+            1_200.0
+        );
+    }
+
+    #[test]
+    fn it_can_calculate_vested_shares_at_compile_time() {
+        static CONFIGURATION: VestingScheduleConfiguration =
+            VestingScheduleConfiguration::monthly(6, 0.25, 12);
+        const VESTED_SHARES: [i64; 13] = vested_shares_by_month(&CONFIGURATION, 10_000);
+
+        assert_eq!(
+            VESTED_SHARES,
+            [0, 0, 0, 0, 0, 0, 2500, 3750, 5000, 6250, 7500, 8750, 10000]
+        );
+        assert_eq!(CONFIGURATION.vested_shares(10_000, 60), 10_000);
+    }
+
+    #[test]
+    fn it_can_round_vested_shares() {
+        let vested = |rounding_strategy| {
+            (1..=4)
+                .map(|months| {
+                    VestingScheduleConfiguration::monthly(0, 0.0, 4)
+                        .with_rounding_strategy(rounding_strategy)
+                        .vested_shares(10, months)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        /*
+         * 2.5 shares vest each month.
+         */
+
+        assert_eq!(vested(RoundingStrategy::Floor), [2, 5, 7, 10]);
+        assert_eq!(vested(RoundingStrategy::Ceil), [3, 5, 8, 10]);
+        assert_eq!(vested(RoundingStrategy::RoundHalfUp), [3, 5, 8, 10]);
+        assert_eq!(vested(RoundingStrategy::Bankers), [2, 5, 8, 10]);
+        assert_eq!(vested(RoundingStrategy::BackLoaded), [2, 4, 6, 10]);
+        assert_eq!(vested(RoundingStrategy::FractionalAllowed), [2, 5, 7, 10]);
+
+        let grant = Grant::for_test(
+            100_000_001,
+            LocalDate::from_ymd(2020, 1, 1),
+            VestingScheduleConfiguration::monthly(0, 0.0, 3)
+                .with_rounding_strategy(RoundingStrategy::Ceil),
+        );
+        let schedule = grant.calculate_vesting_schedule();
+
+        assert_eq!(
+            schedule
+                .periods
+                .iter()
+                .map(|period| period.cumulative_vested_amount)
+                .collect::<Vec<_>>(),
+            [0, 33_333_334, 66_666_668, 100_000_001]
+        );
+
+        /*
+         * 100,000,007 isn't representable as an f32, which would round half of it up
+         * to 50,000,004.
+         */
+
+        let weighted = VestingScheduleConfiguration::weighted(VestingInterval::Annual, &[0.5, 0.5]);
+
+        assert_eq!(weighted.vested_shares(100_000_007, 12), 50_000_003);
+    }
+
+    #[test]
+    fn it_keeps_precise_amounts_alongside_whole_shares() {
+        let grant = Grant::for_test(
+            10,
+            LocalDate::from_ymd(2020, 1, 1),
+            VestingScheduleConfiguration::monthly(0, 0.0, 4),
+        );
+        let periods = grant.calculate_vesting_schedule().periods;
+
+        assert_eq!(
+            periods
+                .iter()
+                .map(|period| period.cumulative_vested_amount)
+                .collect::<Vec<_>>(),
+            [0, 2, 5, 7, 10]
+        );
+        assert_eq!(
+            periods
+                .iter()
+                .map(|period| period.precise_cumulative_amount)
+                .collect::<Vec<_>>(),
+            [0.0, 2.5, 5.0, 7.5, 10.0]
+        );
+    }
+
+    #[test]
+    fn it_vests_token_grants_larger_than_an_i32() {
+        let grant = Grant::for_test(
+            10_000_000_000,
+            LocalDate::from_ymd(2020, 1, 1),
+            VestingScheduleConfiguration::monthly(0, 0.0, 4),
+        );
+        let periods = grant.calculate_vesting_schedule().periods;
+
+        assert_eq!(periods[1].cumulative_vested_amount, 2_500_000_000);
+        assert_eq!(periods[4].vested_this_period, 2_500_000_000);
+        assert_eq!(
+            grant.calculate_vested_shares(LocalDate::from_ymd(2020, 5, 1)),
+            10_000_000_000
+        );
+    }
+
+    #[test]
+    fn it_can_answer_convenience_queries() {
+        let mut grant = Grant::for_test(
+            4_800,
+            LocalDate::from_ymd(2021, 2, 6),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        );
+
+        assert_eq!(
+            grant.percent_vested_on(LocalDate::from_ymd(2022, 1, 15)),
+            0.0
+        );
+        assert_eq!(
+            grant.percent_vested_on(LocalDate::from_ymd(2023, 2, 15)),
+            50.0
+        );
+        assert_eq!(
+            grant.next_vesting_event(LocalDate::from_ymd(2021, 6, 1)),
+            Some(VestingPeriod {
+                date: LocalDate::from_ymd(2022, 2, 6),
+                cumulative_vested_amount: 1_200,
+                vested_this_period: 1_200,
+                precise_cumulative_amount: 1_200.0,
+            })
+        );
+        assert_eq!(
+            grant.fully_vested_date(),
+            Some(LocalDate::from_ymd(2025, 2, 6))
+        );
+
+        grant.terminate(LocalDate::from_ymd(2023, 2, 20));
 
-enum VestingInterval {
-    Monthly,
-}
+        assert_eq!(
+            grant.next_vesting_event(LocalDate::from_ymd(2023, 2, 6)),
+            None
+        );
+        assert_eq!(grant.fully_vested_date(), None);
+    }
 
-struct VestingScheduleConfiguration {
-    interval: VestingInterval,
-    cliff_percentage: f32,
-    cliff: i32,
-    length: i32,
-}
+    #[test]
+    fn it_can_visit_each_vesting_period() {
+        let grant = Grant::for_test(
+            10_000,
+            LocalDate::from_ymd(2020, 2, 6),
+            VestingScheduleConfiguration::monthly(6, 0.25, 12),
+        );
 
-struct Grant {
-    amount: i32,
-    grant_date: Date<Utc>,
-    vesting_schedule: VestingScheduleConfiguration,
-}
+        let mut visited = 0;
+        let mut last_period = None;
 
-impl Grant {
-    /// Calculates the difference of months between the grant date and the given future date.
-    fn months_difference(&self, future_date: Date<Utc>) -> i32 {
-        let year_difference = future_date.year() - self.grant_date.year();
-        let months_difference =
-            (year_difference * 12) + (future_date.month() as i32 - self.grant_date.month() as i32);
+        grant.visit_vesting_periods(|period| {
+            visited += 1;
+            last_period = Some(period);
+        });
 
-        return months_difference;
+        assert_eq!(visited, 13);
+        assert_eq!(
+            last_period,
+            Some(VestingPeriod {
+                date: LocalDate::from_ymd(2021, 2, 6),
+                cumulative_vested_amount: 10000,
+                vested_this_period: 1250,
+                precise_cumulative_amount: 10000.0,
+            })
+        );
     }
 
-    /// Checks if the given future date is still in the cliff period.
-    fn is_before_cliff(&self, future_date: Date<Utc>) -> bool {
-        return self.months_difference(future_date) < self.vesting_schedule.cliff as i32;
-    }
+    #[test]
+    fn it_can_lazily_yield_vesting_events() {
+        let mut grant = Grant::for_test(
+            10_000,
+            LocalDate::from_ymd(2020, 2, 6),
+            VestingScheduleConfiguration::monthly(6, 0.25, 12),
+        );
+
+        assert_eq!(
+            grant.vesting_events().collect::<Vec<_>>(),
+            grant.calculate_vesting_schedule().periods
+        );
+        assert_eq!(
+            grant
+                .vesting_events()
+                .find(|period| period.vested_this_period > 0)
+                .map(|period| period.date),
+            Some(LocalDate::from_ymd(2020, 8, 6))
+        );
+
+        grant.terminate(LocalDate::from_ymd(2020, 9, 1));
 
-    /// Returns the amount of vested equity when cliff period has been reached.
-    fn cliff_vested_amount(&self) -> f32 {
-        return self.amount as f32 * self.vesting_schedule.cliff_percentage;
+        assert_eq!(grant.vesting_events().count(), 7);
     }
 
-    /// Calculates the vested amount on a given future date.
-    pub fn calculate_vested_amount(&self, future_date: Date<Utc>) -> f32 {
-        match self.vesting_schedule.interval {
-            VestingInterval::Monthly => {
-                if self.is_before_cliff(future_date) {
-                    return 0.0;
-                } else if self.months_difference(future_date) > self.vesting_schedule.length {
-                    return self.amount as f32;
-                } else {
-                    let months_past_cliff =
-                        self.months_difference(future_date) - self.vesting_schedule.cliff;
+    #[test]
+    fn it_can_vest_quarterly_and_annually() {
+        let quarterly = Grant::for_test(
+            10_000,
+            LocalDate::from_ymd(2020, 2, 6),
+            VestingScheduleConfiguration::new(VestingInterval::Quarterly, 4, 0.25, 16),
+        );
+
+        /*
+         * 10,000 RSUs, 25% cliff after 4 quarters, vesting quarterly over 16 quarters.
+         * - 2021/2/6: 25% vests at the cliff, 2500 vested
+         * - 2021/4/6: still inside the first quarter after the cliff, 2500 vested
+         * - 2021/5/6: +625 vest, 3125 vested
+         */
 
-                    if months_past_cliff == 0 {
-                        return self.cliff_vested_amount();
-                    }
+        assert_eq!(
+            quarterly.calculate_vested_amount(LocalDate::from_ymd(2021, 2, 6)),
+            2500.0
+        );
+        assert_eq!(
+            quarterly.calculate_vested_amount(LocalDate::from_ymd(2021, 4, 6)),
+            2500.0
+        );
+        assert_eq!(
+            quarterly.calculate_vested_amount(LocalDate::from_ymd(2021, 5, 6)),
+            3125.0
+        );
 
-                    let remaining_amount_after_cliff: f32 =
-                        (self.amount as f32 - self.cliff_vested_amount()).into();
-                    let vested_per_month: f32 = remaining_amount_after_cliff
-                        / (self.vesting_schedule.length - self.vesting_schedule.cliff) as f32;
-                    let vested_after_cliff: f32 = vested_per_month * months_past_cliff as f32;
+        let vesting_schedule = quarterly.calculate_vesting_schedule();
 
-                    return self.cliff_vested_amount() + vested_after_cliff;
-                }
+        assert_eq!(vesting_schedule.periods.len(), 17);
+        assert_eq!(vesting_schedule.to_date, LocalDate::from_ymd(2024, 2, 6));
+        assert_eq!(
+            vesting_schedule.periods[5],
+            VestingPeriod {
+                date: LocalDate::from_ymd(2021, 5, 6),
+                cumulative_vested_amount: 3125,
+                vested_this_period: 625,
+                precise_cumulative_amount: 3125.0,
             }
-        }
-    }
+        );
 
-    /// Calculate a full vesting schedule, listing the vested amounts per vesting period.
-    pub fn calculate_vesting_schedule(&self) -> VestingSchedule {
-        let duration = RelativeDuration::months(self.vesting_schedule.length);
-        let to_date = self.grant_date + duration;
-        let rule = DateRule::monthly(self.grant_date)
-            .with_count(self.vesting_schedule.length as usize + 1);
-        let periods = rule
-            .map(|month| VestingPeriod {
-                date: month,
-                cumulative_vested_amount: self.calculate_vested_amount(month).floor() as i32,
-            })
-            .collect();
+        let annual = Grant::for_test(
+            10_000,
+            LocalDate::from_ymd(2020, 2, 6),
+            VestingScheduleConfiguration::new(VestingInterval::Annual, 1, 0.25, 4),
+        );
 
-        return VestingSchedule {
-            periods,
-            from_date: self.grant_date,
-            to_date,
-        };
+        assert_eq!(
+            annual.calculate_vested_amount(LocalDate::from_ymd(2022, 1, 6)),
+            2500.0
+        );
+        assert_eq!(
+            annual
+                .calculate_vesting_schedule()
+                .periods
+                .iter()
+                .map(|period| period.cumulative_vested_amount)
+                .collect::<Vec<_>>(),
+            vec![0, 2500, 5000, 7500, 10000]
+        );
+        assert_eq!(annual.vesting_schedule.vested_shares(10_000, 35), 5000);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use approx::assert_relative_eq;
-    use chrono::TimeZone;
+    #[test]
+    fn it_can_vest_weekly_and_bi_weekly() {
+        let weekly = Grant::builder()
+            .amount(5_200)
+            .grant_date(LocalDate::from_ymd(2021, 1, 4))
+            .weekly()
+            .cliff_months(12)
+            .cliff_percentage(0.25)
+            .length_months(48)
+            .build()
+            .unwrap();
 
-    use crate::VestingPeriod;
+        /*
+         * 52 week cliff vesting 1300, then 3900 over the remaining 156 weeks, 25 a week.
+         * - 2022/1/2: the day before the cliff, nothing vested
+         * - 2022/1/3: 1300 vested at the cliff
+         * - 2022/1/17: two weeks later, 1350 vested
+         */
+
+        assert_eq!(
+            weekly.calculate_vested_amount(LocalDate::from_ymd(2022, 1, 2)),
+            0.0
+        );
+        assert_eq!(
+            weekly.calculate_vested_amount(LocalDate::from_ymd(2022, 1, 3)),
+            1300.0
+        );
+        assert_eq!(
+            weekly.calculate_vested_shares(LocalDate::from_ymd(2022, 1, 17)),
+            1350
+        );
+
+        let vesting_schedule = weekly.calculate_vesting_schedule();
+
+        assert_eq!(vesting_schedule.periods.len(), 209);
+        assert_eq!(vesting_schedule.to_date, LocalDate::from_ymd(2024, 12, 30));
+        assert_eq!(
+            vesting_schedule.periods[53],
+            VestingPeriod {
+                date: LocalDate::from_ymd(2022, 1, 10),
+                cumulative_vested_amount: 1325,
+                vested_this_period: 25,
+                precise_cumulative_amount: 1325.0,
+            }
+        );
+
+        let bi_weekly = Grant::for_test(
+            2_600,
+            LocalDate::from_ymd(2021, 1, 4),
+            VestingScheduleConfiguration::new(VestingInterval::BiWeekly, 0, 0.0, 26),
+        );
+
+        assert_eq!(
+            bi_weekly.calculate_vested_shares(LocalDate::from_ymd(2021, 2, 1)),
+            200
+        );
+        assert_eq!(bi_weekly.vesting_schedule.total_vesting_months(), 12);
+
+        #[allow(deprecated)]
+        let length = bi_weekly.vesting_schedule.length();
 
-    use super::{Grant, Utc, VestingInterval, VestingScheduleConfiguration};
+        assert_eq!(length, 12);
+    }
 
     #[test]
-    fn it_can_calculate_vested_amounts_for_given_dates() {
-        let grant = Grant {
-            amount: 10_000,
-            grant_date: Utc.ymd(2020, 2, 6),
-            vesting_schedule: VestingScheduleConfiguration {
-                interval: VestingInterval::Monthly,
-                cliff: 12,
-                cliff_percentage: 0.25,
-                length: 48,
-            },
-        };
+    fn it_can_prorate_vesting_daily() {
+        let grant = Grant::for_test(
+            4_800,
+            LocalDate::from_ymd(2020, 1, 1),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48).with_daily_proration(true),
+        );
 
         /*
-         * 10,000 stock options, 25% cliff after 12 months, 48 month vesting schedule.
-         * - 2021/2/6: 25% options vest, 2500 options vested
-         * - 2021/3/6: +208 options vest, 2708 options vested
-         * - 2022/2/6: 50% options vested, 5000 options vested
+         * 100 shares vest each month after the cliff, accruing daily.
+         * - 2020/12/31: before the cliff, nothing vested
+         * - 2021/1/16: 1200 at the cliff plus 15 of January's 31 days
+         * - 2023/12/16: 4700 plus 15 of December's 31 days
          */
 
-        assert_relative_eq!(
-            grant.calculate_vested_amount(Utc.ymd(2020, 8, 6)),
-            0.0,
-            max_relative = 0.005
+        assert_eq!(
+            grant.calculate_vested_amount(LocalDate::from_ymd(2020, 12, 31)),
+            0.0
         );
         assert_relative_eq!(
-            grant.calculate_vested_amount(Utc.ymd(2020, 12, 6)),
-            0.0,
-            max_relative = 0.005
+            grant.calculate_vested_amount(LocalDate::from_ymd(2021, 1, 16)),
+            1200.0 + 100.0 * 15.0 / 31.0
         );
         assert_relative_eq!(
-            grant.calculate_vested_amount(Utc.ymd(2021, 2, 6)),
-            2500.0,
-            max_relative = 0.005
+            grant.calculate_vested_amount(LocalDate::from_ymd(2023, 12, 16)),
+            4700.0 + 100.0 * 15.0 / 31.0
         );
-        assert_relative_eq!(
-            grant.calculate_vested_amount(Utc.ymd(2021, 3, 6)),
-            2708.33,
-            max_relative = 0.005
+        assert_eq!(
+            grant.calculate_vested_amount(LocalDate::from_ymd(2024, 1, 1)),
+            4800.0
         );
-        assert_relative_eq!(
-            grant.calculate_vested_amount(Utc.ymd(2022, 2, 6)),
-            5000.0,
-            max_relative = 0.005
+        assert_eq!(
+            grant.calculate_vested_shares(LocalDate::from_ymd(2021, 1, 16)),
+            1200
         );
-        assert_relative_eq!(
-            grant.calculate_vested_amount(Utc.ymd(2022, 3, 6)),
-            5208.33,
-            max_relative = 0.005
+    }
+
+    #[test]
+    fn it_can_calculate_back_loaded_schedules() {
+        let grant = Grant::for_test(
+            10_000,
+            LocalDate::from_ymd(2020, 2, 6),
+            VestingScheduleConfiguration::weighted(
+                VestingInterval::Annual,
+                &[0.05, 0.15, 0.4, 0.4],
+            ),
         );
+
+        assert_eq!(grant.validate(), Ok(()));
         assert_relative_eq!(
-            grant.calculate_vested_amount(Utc.ymd(2022, 4, 6)),
-            5416.66,
+            grant.calculate_vested_amount(LocalDate::from_ymd(2022, 3, 6)),
+            2000.0,
             max_relative = 0.005
         );
-        assert_relative_eq!(
-            grant.calculate_vested_amount(Utc.ymd(2023, 2, 6)),
-            7500.00,
-            max_relative = 0.005
+        assert_eq!(
+            grant
+                .calculate_vesting_schedule()
+                .periods
+                .iter()
+                .map(|period| period.cumulative_vested_amount)
+                .collect::<Vec<_>>(),
+            vec![0, 500, 2000, 6000, 10000]
         );
-        assert_relative_eq!(
-            grant.calculate_vested_amount(Utc.ymd(2023, 3, 6)),
-            7708.33,
-            max_relative = 0.005
+
+        let grant = Grant::for_test(
+            10_000,
+            LocalDate::from_ymd(2020, 2, 6),
+            VestingScheduleConfiguration::weighted(VestingInterval::Annual, &[0.05, 0.15, 0.4]),
         );
-        assert_relative_eq!(
-            grant.calculate_vested_amount(Utc.ymd(2024, 3, 6)),
-            10000.00,
-            max_relative = 0.005
+
+        assert_eq!(
+            grant.validate(),
+            Err(VestingError::WeightsDoNotSumToOne(0.6))
         );
     }
 
     #[test]
-    fn it_can_calculate_full_vesting_schedule() {
-        let grant = Grant {
-            amount: 10_000,
-            grant_date: Utc.ymd(2020, 2, 6),
-            vesting_schedule: VestingScheduleConfiguration {
-                interval: VestingInterval::Monthly,
-                cliff: 6,
-                cliff_percentage: 0.25,
-                length: 12,
-            },
+    fn it_vests_grants_without_a_cliff_from_the_first_interval() {
+        let grant = Grant::for_test(
+            4_800,
+            LocalDate::from_ymd(2021, 1, 1),
+            VestingScheduleConfiguration::without_cliff(VestingInterval::Monthly, 48),
+        );
+
+        assert_eq!(grant.vesting_schedule.cliff(), None);
+        assert_eq!(
+            grant.calculate_vested_amount(LocalDate::from_ymd(2021, 1, 1)),
+            0.0
+        );
+        assert_eq!(
+            grant.calculate_vested_amount(LocalDate::from_ymd(2021, 2, 1)),
+            100.0
+        );
+        assert_eq!(
+            grant.calculate_vested_shares(LocalDate::from_ymd(2021, 2, 1)),
+            100
+        );
+        assert_eq!(
+            grant.calculate_vested_shares(LocalDate::from_ymd(2025, 1, 1)),
+            4_800
+        );
+
+        /*
+         * A cliff of zero months is no cliff, so nothing vests up front whatever its
+         * percentage.
+         */
+
+        let zero_month_cliff = VestingScheduleConfiguration::monthly(0, 0.25, 48);
+
+        assert_eq!(zero_month_cliff.cliff(), None);
+        assert_eq!(zero_month_cliff.vested_shares(4_800, 0), 0);
+        assert_eq!(zero_month_cliff.vested_shares(4_800, 1), 100);
+        assert_eq!(
+            VestingScheduleConfiguration::monthly(12, 0.25, 48).cliff(),
+            Some(Cliff {
+                intervals: 12,
+                percentage: 0.25,
+            })
+        );
+    }
+
+    #[test]
+    fn it_catches_up_vesting_from_an_earlier_commencement_date() {
+        let grant = Grant::builder()
+            .amount(4_800)
+            .grant_date(LocalDate::from_ymd(2022, 3, 15))
+            .vesting_commencement_date(LocalDate::from_ymd(2021, 1, 1))
+            .monthly()
+            .cliff_months(12)
+            .cliff_percentage(0.25)
+            .length_months(48)
+            .build()
+            .unwrap();
+
+        /*
+         * The cliff and the vests on 2022/2/1 and 2022/3/1 all fall before the grant
+         * date, so 1400 shares vest on it at once.
+         */
+
+        assert_eq!(
+            grant.calculate_vested_shares(LocalDate::from_ymd(2022, 3, 14)),
+            0
+        );
+        assert_eq!(
+            grant.calculate_vested_shares(LocalDate::from_ymd(2022, 3, 15)),
+            1_400
+        );
+
+        let vesting_schedule = grant.calculate_vesting_schedule();
+
+        assert_eq!(vesting_schedule.from_date, LocalDate::from_ymd(2021, 1, 1));
+        assert_eq!(
+            vesting_schedule.periods[..2],
+            [
+                VestingPeriod {
+                    date: LocalDate::from_ymd(2022, 3, 15),
+                    cumulative_vested_amount: 1_400,
+                    vested_this_period: 1_400,
+                    precise_cumulative_amount: 1_400.0,
+                },
+                VestingPeriod {
+                    date: LocalDate::from_ymd(2022, 4, 1),
+                    cumulative_vested_amount: 1_500,
+                    vested_this_period: 100,
+                    precise_cumulative_amount: 1_500.0,
+                },
+            ]
+        );
+        assert_eq!(
+            grant.vesting_events().collect::<Vec<_>>(),
+            vesting_schedule.periods
+        );
+    }
+
+    #[test]
+    fn it_vests_nothing_before_a_later_commencement_date() {
+        let grant = Grant::builder()
+            .amount(4_800)
+            .grant_date(LocalDate::from_ymd(2021, 3, 6))
+            .vesting_commencement_date(LocalDate::from_ymd(2021, 5, 6))
+            .monthly()
+            .length_months(48)
+            .build()
+            .unwrap();
+        let before_commencement = LocalDate::from_ymd(2021, 4, 6);
+
+        assert_eq!(grant.calculate_vested_shares(before_commencement), 0);
+        assert_eq!(grant.calculate_vested_amount(before_commencement), 0.0);
+        assert_eq!(grant.percent_vested_on(before_commencement), 0.0);
+        assert_eq!(grant.calculate_unvested_amount(before_commencement), 4_800);
+        assert_eq!(
+            vested_amount(
+                &grant.vesting_schedule,
+                4_800,
+                LocalDate::from_ymd(2021, 5, 6),
+                before_commencement,
+            ),
+            0.0
+        );
+        assert_eq!(
+            grant.calculate_vested_shares(LocalDate::from_ymd(2021, 6, 6)),
+            100
+        );
+        assert_eq!(
+            grant.calculate_vesting_schedule().periods[0].date,
+            LocalDate::from_ymd(2021, 5, 6)
+        );
+    }
+
+    #[test]
+    fn it_applies_the_month_end_policy_to_vesting_dates() {
+        let grant = |month_end_policy| {
+            Grant::builder()
+                .amount(400)
+                .grant_date(LocalDate::from_ymd(2021, 1, 31))
+                .monthly()
+                .length_months(4)
+                .month_end_policy(month_end_policy)
+                .build()
+                .unwrap()
         };
+        let vesting_dates = |grant: &Grant| {
+            grant
+                .calculate_vesting_schedule()
+                .periods
+                .into_iter()
+                .skip(1)
+                .map(|period| period.date)
+                .collect::<Vec<_>>()
+        };
+
+        let clamped = grant(MonthEndPolicy::ClampToLastDay);
+        let rolled_forward = grant(MonthEndPolicy::RollForward);
+        let rolled_backward = grant(MonthEndPolicy::RollBackward);
+
+        assert_eq!(
+            vesting_dates(&clamped),
+            vec![
+                LocalDate::from_ymd(2021, 2, 28),
+                LocalDate::from_ymd(2021, 3, 31),
+                LocalDate::from_ymd(2021, 4, 30),
+                LocalDate::from_ymd(2021, 5, 31),
+            ]
+        );
+        assert_eq!(
+            vesting_dates(&rolled_forward),
+            vec![
+                LocalDate::from_ymd(2021, 3, 1),
+                LocalDate::from_ymd(2021, 3, 31),
+                LocalDate::from_ymd(2021, 5, 1),
+                LocalDate::from_ymd(2021, 5, 31),
+            ]
+        );
+        assert_eq!(
+            vesting_dates(&rolled_backward),
+            vec![
+                LocalDate::from_ymd(2021, 2, 28),
+                LocalDate::from_ymd(2021, 3, 28),
+                LocalDate::from_ymd(2021, 4, 28),
+                LocalDate::from_ymd(2021, 5, 28),
+            ]
+        );
+
+        assert_eq!(
+            rolled_forward.calculate_vested_amount(LocalDate::from_ymd(2021, 2, 28)),
+            0.0
+        );
+        assert_eq!(
+            rolled_forward.calculate_vested_amount(LocalDate::from_ymd(2021, 3, 1)),
+            100.0
+        );
+        assert_eq!(
+            rolled_backward.calculate_vested_amount(LocalDate::from_ymd(2021, 3, 27)),
+            100.0
+        );
+        assert_eq!(
+            rolled_backward.calculate_vested_amount(LocalDate::from_ymd(2021, 3, 28)),
+            200.0
+        );
+    }
+
+    #[test]
+    fn it_stops_vesting_at_the_termination_date() {
+        let mut grant = Grant::for_test(
+            10_000,
+            LocalDate::from_ymd(2020, 2, 6),
+            VestingScheduleConfiguration::monthly(6, 0.25, 12),
+        );
+
+        grant.terminate(LocalDate::from_ymd(2020, 9, 20));
+
+        assert_eq!(
+            grant.calculate_vested_amount(LocalDate::from_ymd(2021, 2, 6)),
+            3750.0
+        );
 
         let vesting_schedule = grant.calculate_vesting_schedule();
 
-        assert_eq!(vesting_schedule.from_date, grant.grant_date);
-        assert_eq!(vesting_schedule.to_date, Utc.ymd(2021, 2, 6));
+        assert_eq!(vesting_schedule.periods.len(), 8);
+        assert_eq!(vesting_schedule.to_date, LocalDate::from_ymd(2020, 9, 20));
+        assert_eq!(vesting_schedule.forfeited_amount, 6250);
+        assert_eq!(
+            grant.calculate_unvested_amount(LocalDate::from_ymd(2020, 10, 6)),
+            0
+        );
+    }
 
-        let periods = vec![
-            VestingPeriod {
-                date: Utc.ymd(2020, 2, 6),
-                cumulative_vested_amount: 0,
-            },
-            VestingPeriod {
-                date: Utc.ymd(2020, 3, 6),
-                cumulative_vested_amount: 0,
-            },
-            VestingPeriod {
-                date: Utc.ymd(2020, 4, 6),
-                cumulative_vested_amount: 0,
-            },
-            VestingPeriod {
-                date: Utc.ymd(2020, 5, 6),
-                cumulative_vested_amount: 0,
-            },
-            VestingPeriod {
-                date: Utc.ymd(2020, 6, 6),
-                cumulative_vested_amount: 0,
-            },
-            VestingPeriod {
-                date: Utc.ymd(2020, 7, 6),
-                cumulative_vested_amount: 0,
-            },
-            VestingPeriod {
-                date: Utc.ymd(2020, 8, 6),
-                cumulative_vested_amount: 2500,
-            },
-            VestingPeriod {
-                date: Utc.ymd(2020, 9, 6),
-                cumulative_vested_amount: 3750,
-            },
-            VestingPeriod {
-                date: Utc.ymd(2020, 10, 6),
-                cumulative_vested_amount: 5000,
-            },
-            VestingPeriod {
-                date: Utc.ymd(2020, 11, 6),
-                cumulative_vested_amount: 6250,
-            },
-            VestingPeriod {
-                date: Utc.ymd(2020, 12, 6),
-                cumulative_vested_amount: 7500,
-            },
-            VestingPeriod {
-                date: Utc.ymd(2021, 1, 6),
-                cumulative_vested_amount: 8750,
-            },
-            VestingPeriod {
-                date: Utc.ymd(2021, 2, 6),
-                cumulative_vested_amount: 10000,
-            },
-        ];
+    #[test]
+    fn it_rejects_nonsensical_configurations() {
+        assert_eq!(
+            VestingScheduleConfiguration::monthly(12, 0.25, 48).validate(),
+            Ok(())
+        );
+        assert_eq!(
+            VestingScheduleConfiguration::monthly(-1, 0.0, 48).validate(),
+            Err(VestingError::NegativeCliff(-1))
+        );
+        assert_eq!(
+            VestingScheduleConfiguration::monthly(12, 1.5, 48).validate(),
+            Err(VestingError::CliffPercentageOutOfRange(1.5))
+        );
 
-        assert_eq!(vesting_schedule.periods, periods)
+        let grant = Grant::for_test(
+            0,
+            LocalDate::from_ymd(2020, 2, 6),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        );
+
+        assert_eq!(grant.validate(), Err(VestingError::ZeroAmount));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn it_can_load_grants_from_json() {
+        let grant: Grant = serde_json::from_str(
+            r#"{
+                "amount": 10000,
+                "grant_date": "2020-02-06",
+                "vesting_schedule": {
+                    "interval": "quarterly",
+                    "cliff_percentage": 0.25,
+                    "cliff": 4,
+                    "length": 16
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            grant.calculate_vested_amount(LocalDate::from_ymd(2021, 5, 6)),
+            3125.0
+        );
+
+        let vesting_schedule = serde_json::to_value(grant.calculate_vesting_schedule()).unwrap();
+
+        assert_eq!(vesting_schedule["to_date"], "2024-02-06");
+        assert_eq!(
+            vesting_schedule["periods"][5],
+            serde_json::json!({
+                "date": "2021-05-06",
+                "cumulative_vested_amount": 3125,
+                "vested_this_period": 625,
+                "precise_cumulative_amount": 3125.0
+            })
+        );
+
+        let weighted = Grant::for_test(
+            10_000,
+            LocalDate::from_ymd(2020, 2, 6),
+            VestingScheduleConfiguration::weighted(
+                VestingInterval::Annual,
+                &[0.05, 0.15, 0.4, 0.4],
+            ),
+        );
+        let serialized = serde_json::to_value(&weighted).unwrap();
+
+        assert_eq!(serialized["vesting_schedule"]["total_intervals"], 4);
+        assert_eq!(serialized["vesting_schedule"]["cliff_intervals"], 0);
+
+        let round_tripped: Grant = serde_json::from_value(serialized).unwrap();
+
+        assert_eq!(
+            round_tripped.vesting_schedule.weights(),
+            [0.05, 0.15, 0.4, 0.4]
+        );
+        assert_relative_eq!(
+            round_tripped.calculate_vested_amount(LocalDate::from_ymd(2022, 3, 6)),
+            2000.0
+        );
+
+        /* Documents written before the fields counted intervals still load. */
+        let legacy: VestingScheduleConfiguration = serde_json::from_str(
+            r#"{
+                "interval": "quarterly",
+                "cliff_percentage": 0.25,
+                "cliff_months": 4,
+                "total_vesting_months": 16
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(legacy.cliff_months(), 12);
+        assert_eq!(legacy.total_vesting_months(), 48);
+    }
+
+    #[test]
+    fn it_lists_no_periods_for_a_negative_length() {
+        let configuration = VestingScheduleConfiguration::monthly(0, 0.0, -3);
+        let grant_date = LocalDate::from_ymd(2021, 1, 1);
+        let grant = Grant::for_test(1_200, grant_date, configuration.clone());
+
+        assert!(crate::vesting_schedule(&configuration, 1_200, grant_date)
+            .periods
+            .is_empty());
+        assert!(grant.calculate_vesting_schedule().periods.is_empty());
+        assert_eq!(grant.vest_events(), Vec::new());
+
+        let mut visited = 0;
+        grant.visit_vesting_periods(|_| visited += 1);
+
+        assert_eq!(visited, 0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn it_rejects_invalid_grants_when_deserializing() {
+        let grant = |amount: i32, cliff: i32, length: i32| {
+            serde_json::from_value::<Grant>(serde_json::json!({
+                "amount": amount,
+                "grant_date": "2020-02-06",
+                "vesting_schedule": {
+                    "interval": "monthly",
+                    "cliff_percentage": 0.25,
+                    "cliff": cliff,
+                    "length": length
+                }
+            }))
+            .map_err(|error| error.to_string())
+        };
+
+        assert!(grant(10_000, 12, 48).is_ok());
+        assert_eq!(
+            grant(10_000, 0, -3).err(),
+            Some("vesting length must not be negative, got -3".to_string())
+        );
+        assert_eq!(
+            grant(10_000, 60, 48).err(),
+            Some("cliff of 60 periods exceeds vesting length of 48 periods".to_string())
+        );
+        assert_eq!(
+            grant(0, 12, 48).err(),
+            Some("grant amount must not be zero".to_string())
+        );
     }
 }
@@ -0,0 +1,445 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Currency {
+    Usd,
+    Eur,
+    Gbp,
+    Cad,
+    Jpy,
+}
+
+impl Currency {
+    /// ISO 4217 currency code, e.g. `USD`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::Usd => "USD",
+            Currency::Eur => "EUR",
+            Currency::Gbp => "GBP",
+            Currency::Cad => "CAD",
+            Currency::Jpy => "JPY",
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Currency::Usd => "$",
+            Currency::Eur => "€",
+            Currency::Gbp => "£",
+            Currency::Cad => "CA$",
+            Currency::Jpy => "¥",
+        }
+    }
+
+    /// Number of decimal places of the currency's minor unit, e.g. 2 for cents.
+    pub fn decimal_places(&self) -> u32 {
+        match self {
+            Currency::Jpy => 0,
+            _ => 2,
+        }
+    }
+
+    fn minor_units_per_major_unit(&self) -> i64 {
+        10_i64.pow(self.decimal_places())
+    }
+}
+
+/// Millionths of a major unit in a `Price`, enough for sub-cent prices like $0.0001.
+const MICRO_UNITS_PER_MAJOR_UNIT: i64 = 1_000_000;
+
+fn micro_units_per_minor_unit(currency: Currency) -> i128 {
+    (MICRO_UNITS_PER_MAJOR_UNIT / currency.minor_units_per_major_unit()) as i128
+}
+
+/// An amount of money, stored as a whole number of the currency's minor units so that
+/// sums never drift.
+///
+/// Arithmetic between amounts in different currencies is a bug and panics; use
+/// `checked_add` when the currencies aren't known to match.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Money {
+    minor_units: i64,
+    currency: Currency,
+}
+
+impl Money {
+    pub fn from_minor_units(minor_units: i64, currency: Currency) -> Money {
+        Money {
+            minor_units,
+            currency,
+        }
+    }
+
+    /// Makes an amount from major units, e.g. dollars, rounding to the nearest minor unit.
+    pub fn from_major_units(amount: f64, currency: Currency) -> Money {
+        Money::from_minor_units(
+            (amount * currency.minor_units_per_major_unit() as f64).round() as i64,
+            currency,
+        )
+    }
+
+    pub fn zero(currency: Currency) -> Money {
+        Money::from_minor_units(0, currency)
+    }
+
+    pub fn minor_units(&self) -> i64 {
+        self.minor_units
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// The amount in major units, e.g. dollars, for charting or further math.
+    pub fn to_major_units(&self) -> f64 {
+        self.minor_units as f64 / self.currency.minor_units_per_major_unit() as f64
+    }
+
+    /// Adds two amounts, returning `None` if their currencies differ.
+    pub fn checked_add(self, other: Money) -> Option<Money> {
+        (self.currency == other.currency)
+            .then(|| Money::from_minor_units(self.minor_units + other.minor_units, self.currency))
+    }
+
+    /// Multiplies the amount by a fraction or rate, rounding to the nearest minor unit.
+    pub fn scale(self, factor: f64) -> Money {
+        Money::from_minor_units(
+            (self.minor_units as f64 * factor).round() as i64,
+            self.currency,
+        )
+    }
+
+    /// Sums the amounts, all of which must be in the given currency.
+    pub fn sum<I: IntoIterator<Item = Money>>(currency: Currency, amounts: I) -> Money {
+        amounts.into_iter().fold(Money::zero(currency), Add::add)
+    }
+
+    fn assert_same_currency(&self, other: &Money) {
+        assert_eq!(
+            self.currency, other.currency,
+            "cannot combine amounts in different currencies"
+        );
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        self.assert_same_currency(&other);
+        Money::from_minor_units(self.minor_units + other.minor_units, self.currency)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        self.assert_same_currency(&other);
+        Money::from_minor_units(self.minor_units - other.minor_units, self.currency)
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money::from_minor_units(-self.minor_units, self.currency)
+    }
+}
+
+/// Multiplies the amount by a whole number, e.g. a fee charged per trade.
+impl Mul<i64> for Money {
+    type Output = Money;
+
+    fn mul(self, quantity: i64) -> Money {
+        Money::from_minor_units(self.minor_units * quantity, self.currency)
+    }
+}
+
+/// Amounts in different currencies aren't comparable.
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Money) -> Option<Ordering> {
+        (self.currency == other.currency).then(|| self.minor_units.cmp(&other.minor_units))
+    }
+}
+
+/// A price per share, stored in millionths of the currency's major unit so that
+/// sub-cent prices, such as a $0.0001 founder price, aren't rounded away before
+/// they're multiplied by a number of shares.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Price {
+    micro_units: i64,
+    currency: Currency,
+}
+
+impl Price {
+    pub fn from_micro_units(micro_units: i64, currency: Currency) -> Price {
+        Price {
+            micro_units,
+            currency,
+        }
+    }
+
+    /// Makes a price from major units, e.g. dollars, rounding to the nearest millionth.
+    pub fn from_major_units(amount: f64, currency: Currency) -> Price {
+        Price::from_micro_units(
+            (amount * MICRO_UNITS_PER_MAJOR_UNIT as f64).round() as i64,
+            currency,
+        )
+    }
+
+    pub fn zero(currency: Currency) -> Price {
+        Price::from_micro_units(0, currency)
+    }
+
+    pub fn micro_units(&self) -> i64 {
+        self.micro_units
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// The price in major units, e.g. dollars, for charting or further math.
+    pub fn to_major_units(&self) -> f64 {
+        self.micro_units as f64 / MICRO_UNITS_PER_MAJOR_UNIT as f64
+    }
+
+    /// Spreads a total over a number of shares, e.g. a company valuation over its fully
+    /// diluted shares, rounding to the nearest millionth.
+    pub fn per_share(total: Money, shares: i64) -> Price {
+        let micro_units =
+            total.minor_units() as i128 * micro_units_per_minor_unit(total.currency());
+        let half = shares as i128 / 2 * micro_units.signum();
+
+        Price::from_micro_units(
+            ((micro_units + half) / shares as i128) as i64,
+            total.currency(),
+        )
+    }
+
+    /// Whole shares the budget buys at this price, or none at a price of zero.
+    pub fn shares_for(self, budget: Money) -> i64 {
+        self.assert_same_currency_as(&budget);
+
+        match self.micro_units {
+            0 => 0,
+            micro_units => {
+                (budget.minor_units() as i128 * micro_units_per_minor_unit(self.currency)
+                    / micro_units as i128) as i64
+            }
+        }
+    }
+
+    /// Multiplies the price by a fraction or rate, rounding to the nearest millionth.
+    pub fn scale(self, factor: f64) -> Price {
+        Price::from_micro_units(
+            (self.micro_units as f64 * factor).round() as i64,
+            self.currency,
+        )
+    }
+
+    /// The price in major units without a currency symbol or separators, keeping
+    /// sub-cent digits when there are any, e.g. `1250.50` or `0.0001`.
+    pub fn to_decimal_string(&self) -> String {
+        let sign = if self.micro_units < 0 { "-" } else { "" };
+
+        format!(
+            "{}{}{}",
+            sign,
+            self.micro_units.abs() / MICRO_UNITS_PER_MAJOR_UNIT,
+            self.decimal_fraction()
+        )
+    }
+
+    /// Decimal point and the digits after it, at least as many as the currency's minor
+    /// unit has.
+    fn decimal_fraction(&self) -> String {
+        let fraction = format!("{:06}", self.micro_units.abs() % MICRO_UNITS_PER_MAJOR_UNIT);
+        let decimal_places = fraction
+            .trim_end_matches('0')
+            .len()
+            .max(self.currency.decimal_places() as usize);
+
+        match decimal_places {
+            0 => String::new(),
+            _ => format!(".{}", &fraction[..decimal_places]),
+        }
+    }
+
+    fn assert_same_currency(&self, other: &Price) {
+        assert_eq!(
+            self.currency, other.currency,
+            "cannot combine prices in different currencies"
+        );
+    }
+
+    fn assert_same_currency_as(&self, amount: &Money) {
+        assert_eq!(
+            self.currency, amount.currency,
+            "cannot combine prices and amounts in different currencies"
+        );
+    }
+}
+
+impl Sub for Price {
+    type Output = Price;
+
+    fn sub(self, other: Price) -> Price {
+        self.assert_same_currency(&other);
+        Price::from_micro_units(self.micro_units - other.micro_units, self.currency)
+    }
+}
+
+/// Multiplies the price by a number of shares, rounding the total to the nearest
+/// minor unit, half away from zero.
+impl Mul<i64> for Price {
+    type Output = Money;
+
+    fn mul(self, shares: i64) -> Money {
+        let micro_units_per_minor_unit = micro_units_per_minor_unit(self.currency);
+        let total = self.micro_units as i128 * shares as i128;
+        let half = micro_units_per_minor_unit / 2 * total.signum();
+
+        Money::from_minor_units(
+            ((total + half) / micro_units_per_minor_unit) as i64,
+            self.currency,
+        )
+    }
+}
+
+/// Prices in different currencies aren't comparable.
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Price) -> Option<Ordering> {
+        (self.currency == other.currency).then(|| self.micro_units.cmp(&other.micro_units))
+    }
+}
+
+/// Formats the price with the currency symbol and thousands separators, keeping
+/// sub-cent digits when there are any, e.g. `$1,250.50` or `$0.0001`.
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.micro_units < 0 {
+            write!(f, "-")?;
+        }
+
+        write!(
+            f,
+            "{}{}{}",
+            self.currency.symbol(),
+            group_thousands(self.micro_units.abs() / MICRO_UNITS_PER_MAJOR_UNIT),
+            self.decimal_fraction()
+        )
+    }
+}
+
+/// Formats a count with thousands separators, e.g. `-1,234,567`.
+pub(crate) fn group_thousands(count: i64) -> String {
+    let digits = count.unsigned_abs().to_string();
+    let mut grouped = if count < 0 {
+        "-".to_string()
+    } else {
+        String::new()
+    };
+
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    grouped
+}
+
+/// Formats the amount with the currency symbol and thousands separators, e.g. `-$1,234.56`.
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let scale = self.currency.minor_units_per_major_unit();
+        let grouped = group_thousands(self.minor_units.abs() / scale);
+
+        if self.minor_units < 0 {
+            write!(f, "-")?;
+        }
+
+        write!(f, "{}{}", self.currency.symbol(), grouped)?;
+
+        match self.currency.decimal_places() {
+            0 => Ok(()),
+            decimal_places => write!(
+                f,
+                ".{:0width$}",
+                self.minor_units.abs() % scale,
+                width = decimal_places as usize
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Currency, Money, Price};
+
+    #[test]
+    fn it_can_add_and_format_money() {
+        let price = Money::from_major_units(12.346, Currency::Usd);
+
+        assert_eq!(price.minor_units(), 1235);
+        assert_eq!((price * 1_000).to_string(), "$12,350.00");
+        assert_eq!(
+            (Money::from_minor_units(5, Currency::Usd) - price).to_string(),
+            "-$12.30"
+        );
+        assert_eq!(
+            Money::from_major_units(1_234_567.0, Currency::Jpy).to_string(),
+            "¥1,234,567"
+        );
+        assert_eq!(price.scale(1.0 / 3.0).minor_units(), 412);
+        assert_eq!(price.checked_add(Money::zero(Currency::Eur)), None);
+        assert_eq!(
+            Money::sum(Currency::Usd, vec![price, price]),
+            Money::from_minor_units(2470, Currency::Usd)
+        );
+    }
+
+    #[test]
+    fn it_keeps_sub_cent_prices_until_multiplied_by_shares() {
+        let price = Price::from_major_units(0.0001, Currency::Usd);
+
+        assert_eq!(price.micro_units(), 100);
+        assert_eq!(price.to_string(), "$0.0001");
+        assert_eq!(price * 3_100, Money::from_minor_units(31, Currency::Usd));
+        assert_eq!(price * 49, Money::from_minor_units(0, Currency::Usd));
+        assert_eq!(price * 50, Money::from_minor_units(1, Currency::Usd));
+        assert_eq!(
+            Price::from_major_units(12.5, Currency::Usd).to_string(),
+            "$12.50"
+        );
+        assert_eq!(
+            Price::from_major_units(1_500.0, Currency::Jpy).to_string(),
+            "¥1,500"
+        );
+        assert_eq!(
+            Price::from_major_units(1_500.25, Currency::Jpy) * 2,
+            Money::from_minor_units(3_001, Currency::Jpy)
+        );
+    }
+}
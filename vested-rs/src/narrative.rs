@@ -0,0 +1,129 @@
+use crate::money::group_thousands;
+use crate::{Grant, LocalDate, VestingInterval};
+
+pub(crate) fn shares(amount: i64) -> String {
+    match amount {
+        1 => "1 share".to_string(),
+        amount => format!("{} shares", group_thousands(amount)),
+    }
+}
+
+pub(crate) fn long_date(date: LocalDate) -> String {
+    date.format("%b %-d, %Y").to_string()
+}
+
+impl VestingInterval {
+    const fn adverb(&self) -> &'static str {
+        match self {
+            VestingInterval::Weekly => "weekly",
+            VestingInterval::BiWeekly => "every two weeks",
+            VestingInterval::Monthly => "monthly",
+            VestingInterval::Quarterly => "quarterly",
+            VestingInterval::Annual => "yearly",
+        }
+    }
+}
+
+/// Describes a run of vests of about the same size, e.g. `about 208 shares`. Returns
+/// `None` if the amounts differ by more than rounding.
+fn regular_amount(amounts: &[i64]) -> Option<String> {
+    let min = *amounts.iter().min()?;
+    let max = *amounts.iter().max()?;
+
+    match max - min {
+        0 => Some(shares(min)),
+        1 => {
+            let average = amounts.iter().sum::<i64>() as f64 / amounts.len() as f64;
+            Some(format!("about {}", shares(average.round() as i64)))
+        }
+        _ => None,
+    }
+}
+
+impl Grant {
+    /// Describes the vesting schedule in a sentence or two of plain language, e.g. for
+    /// screen readers or chat bots: `You vest 2,500 shares on Feb 6, 2021, then about
+    /// 208 shares monthly until Feb 6, 2024.`
+    pub fn describe_vesting(&self) -> String {
+        let vesting_schedule = self.calculate_vesting_schedule();
+        let events = self.vest_events();
+        let amounts: Vec<i64> = events.iter().map(|event| event.amount).collect();
+        let adverb = self.vesting_schedule.interval.adverb();
+
+        let mut description = match events.as_slice() {
+            [] => "No shares vest.".to_string(),
+            [event] => format!(
+                "You vest {} on {}.",
+                shares(event.amount),
+                long_date(event.date)
+            ),
+            [first, .., last] => match (regular_amount(&amounts), regular_amount(&amounts[1..])) {
+                (Some(amount), _) => format!(
+                    "You vest {} {} from {} until {}.",
+                    amount,
+                    adverb,
+                    long_date(first.date),
+                    long_date(last.date)
+                ),
+                (None, Some(amount)) if events.len() > 2 => format!(
+                    "You vest {} on {}, then {} {} until {}.",
+                    shares(first.amount),
+                    long_date(first.date),
+                    amount,
+                    adverb,
+                    long_date(last.date)
+                ),
+                _ => format!(
+                    "You vest {} on {}, then {} more over {} vests until {}.",
+                    shares(first.amount),
+                    long_date(first.date),
+                    shares(amounts[1..].iter().sum()),
+                    events.len() - 1,
+                    long_date(last.date)
+                ),
+            },
+        };
+
+        if vesting_schedule.forfeited_amount > 0 {
+            description += &format!(
+                " The other {} are forfeited.",
+                shares(vesting_schedule.forfeited_amount)
+            );
+        }
+
+        description
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Grant, LocalDate, VestingInterval, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_describe_vesting_in_plain_language() {
+        let grant = Grant::for_test(
+            10_000,
+            LocalDate::from_ymd(2020, 2, 6),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        );
+
+        assert_eq!(
+            grant.describe_vesting(),
+            "You vest 2,500 shares on Feb 6, 2021, then about 208 shares monthly until \
+             Feb 6, 2024."
+        );
+
+        let mut grant = Grant::for_test(
+            1_200,
+            LocalDate::from_ymd(2020, 1, 1),
+            VestingScheduleConfiguration::without_cliff(VestingInterval::Quarterly, 12),
+        );
+        grant.terminate(LocalDate::from_ymd(2020, 12, 1));
+
+        assert_eq!(
+            grant.describe_vesting(),
+            "You vest 100 shares quarterly from Apr 1, 2020 until Oct 1, 2020. The other \
+             900 shares are forfeited."
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use std::fmt;
+
+use crate::{AccelerationPolicy, Grant, LocalDate, Money, Price};
+
+/// An acceleration clause that could be negotiated into an offer.
+pub struct AccelerationClause {
+    pub name: String,
+    pub policy: AccelerationPolicy,
+}
+
+/// When a possible exit happens and whether the holder is let go afterwards.
+pub struct ExitTiming {
+    pub exit_date: LocalDate,
+    pub price_per_share: Price,
+    /// Date the holder is terminated following the exit, if at all.
+    pub termination_date: Option<LocalDate>,
+}
+
+/// The holder's position under one clause and exit timing, measured on the termination
+/// date or, if they stay on, the exit date.
+#[derive(Debug, PartialEq)]
+pub struct AccelerationOutcome {
+    pub clause: String,
+    pub exit_date: LocalDate,
+    pub termination_date: Option<LocalDate>,
+    pub vested_shares: i64,
+    /// Shares vested on top of what would have vested without any acceleration.
+    pub accelerated_shares: i64,
+    pub value: Money,
+}
+
+/// Outcomes of every clause under every exit timing, ordered by exit timing, then clause.
+pub struct AccelerationComparison {
+    pub outcomes: Vec<AccelerationOutcome>,
+}
+
+/// The clauses most commonly offered: none, 6 months single trigger, and half or full
+/// double trigger acceleration.
+pub fn standard_acceleration_clauses() -> Vec<AccelerationClause> {
+    vec![
+        AccelerationClause {
+            name: "None".to_string(),
+            policy: AccelerationPolicy::None,
+        },
+        AccelerationClause {
+            name: "6 month single trigger".to_string(),
+            policy: AccelerationPolicy::SingleTriggerMonths(6),
+        },
+        AccelerationClause {
+            name: "50% double trigger".to_string(),
+            policy: AccelerationPolicy::DoubleTrigger(0.5),
+        },
+        AccelerationClause {
+            name: "Full double trigger".to_string(),
+            policy: AccelerationPolicy::DoubleTrigger(1.0),
+        },
+    ]
+}
+
+/// Compares what the grant is worth under each acceleration clause across several exit
+/// timings.
+pub fn compare_acceleration(
+    grant: &Grant,
+    clauses: &[AccelerationClause],
+    timings: &[ExitTiming],
+) -> AccelerationComparison {
+    let mut outcomes = Vec::with_capacity(clauses.len() * timings.len());
+
+    for timing in timings {
+        let mut grant = grant.clone();
+        grant.termination_date = timing.termination_date;
+
+        let date = timing.termination_date.unwrap_or(timing.exit_date);
+        let vested_shares = |policy| {
+            grant
+                .calculate_accelerated_vested_amount(timing.exit_date, policy, date)
+                .floor() as i64
+        };
+        let unaccelerated_shares = vested_shares(AccelerationPolicy::None);
+
+        for clause in clauses {
+            let vested_shares = vested_shares(clause.policy);
+
+            outcomes.push(AccelerationOutcome {
+                clause: clause.name.clone(),
+                exit_date: timing.exit_date,
+                termination_date: timing.termination_date,
+                vested_shares,
+                accelerated_shares: vested_shares - unaccelerated_shares,
+                value: timing.price_per_share * vested_shares,
+            });
+        }
+    }
+
+    AccelerationComparison { outcomes }
+}
+
+impl fmt::Display for AccelerationComparison {
+    /// Formats the comparison as a plain text table, one row per outcome.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let clause_width = self
+            .outcomes
+            .iter()
+            .map(|outcome| outcome.clause.len())
+            .max()
+            .unwrap_or(0)
+            .max("Clause".len());
+
+        writeln!(
+            f,
+            "{:<clause_width$}  {:<10}  {:<10}  {:>10}  {:>11}  {:>16}",
+            "Clause", "Exit", "Terminated", "Vested", "Accelerated", "Value"
+        )?;
+
+        for outcome in &self.outcomes {
+            let termination_date = outcome
+                .termination_date
+                .map(|date| date.to_string())
+                .unwrap_or_else(|| "-".to_string());
+
+            writeln!(
+                f,
+                "{:<clause_width$}  {:<10}  {:<10}  {:>10}  {:>11}  {:>16}",
+                outcome.clause,
+                outcome.exit_date.to_string(),
+                termination_date,
+                outcome.vested_shares,
+                outcome.accelerated_shares,
+                outcome.value.to_string()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compare_acceleration, standard_acceleration_clauses, ExitTiming, LocalDate};
+    use crate::{usd, usd_price, Grant, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_compare_acceleration_clauses_across_exit_timings() {
+        let grant = Grant::for_test(
+            4_800,
+            LocalDate::from_ymd(2020, 1, 1),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        );
+
+        /*
+         * - 2021/6/1, stays on: 1700 vested, 6 months single trigger vests 600 more
+         * - 2022/1/1, let go a month later: 2500 vested, 6 months single trigger vests
+         *   through 2022/7/1, double trigger vests half or all of the remaining 2300
+         */
+
+        let comparison = compare_acceleration(
+            &grant,
+            &standard_acceleration_clauses(),
+            &[
+                ExitTiming {
+                    exit_date: LocalDate::from_ymd(2021, 6, 1),
+                    price_per_share: usd_price(10.0),
+                    termination_date: None,
+                },
+                ExitTiming {
+                    exit_date: LocalDate::from_ymd(2022, 1, 1),
+                    price_per_share: usd_price(20.0),
+                    termination_date: Some(LocalDate::from_ymd(2022, 2, 1)),
+                },
+            ],
+        );
+
+        assert_eq!(
+            comparison
+                .outcomes
+                .iter()
+                .map(|outcome| (outcome.vested_shares, outcome.accelerated_shares))
+                .collect::<Vec<_>>(),
+            vec![
+                (1700, 0),
+                (2300, 600),
+                (1700, 0),
+                (1700, 0),
+                (2500, 0),
+                (3000, 500),
+                (3650, 1150),
+                (4800, 2300),
+            ]
+        );
+        assert_eq!(comparison.outcomes[7].value, usd(96_000.0));
+        assert_eq!(
+            comparison.to_string().lines().nth(2).unwrap(),
+            "6 month single trigger  2021-06-01  -                 2300          600        $23,000.00"
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Stdout, Write};
+use std::path::Path;
+
+use chrono::Duration;
+
+use crate::money::group_thousands;
+use crate::{Holder, LocalDate};
+
+/// A reminder that one of a holder's grants vests soon.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notification {
+    pub holder: String,
+    pub grant_date: LocalDate,
+    pub vest_date: LocalDate,
+    pub amount: i64,
+}
+
+#[derive(Debug)]
+pub enum DeliveryError {
+    Io(io::Error),
+    #[cfg(feature = "webhook")]
+    Http(Box<ureq::Error>),
+}
+
+/// A channel notifications are delivered to, e.g. a chat webhook or a log file.
+pub trait NotificationSink {
+    fn deliver(&mut self, notification: &Notification) -> Result<(), DeliveryError>;
+}
+
+/// Writes each notification as a line of text, e.g. to stdout or a log file.
+pub struct WriterSink<W: Write> {
+    writer: W,
+}
+
+/// Posts each notification as JSON to an HTTP endpoint, like a Slack-compatible
+/// incoming webhook. The `text` field carries the message.
+#[cfg(feature = "webhook")]
+pub struct WebhookSink {
+    url: String,
+}
+
+impl fmt::Display for DeliveryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DeliveryError::Io(error) => write!(f, "could not write notification: {}", error),
+            #[cfg(feature = "webhook")]
+            DeliveryError::Http(error) => write!(f, "webhook request failed: {}", error),
+        }
+    }
+}
+
+impl Error for DeliveryError {}
+
+impl fmt::Display for Notification {
+    /// Formats the notification as a sentence, e.g. `Jane Doe: 1,200 shares vest on
+    /// 2022-01-15`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} shares vest on {}",
+            self.holder,
+            group_thousands(self.amount),
+            self.vest_date
+        )
+    }
+}
+
+impl Holder {
+    /// Lists reminders for the holder's vests from the given date through the given
+    /// number of days after it, in date order.
+    pub fn upcoming_vest_notifications(
+        &self,
+        as_of: LocalDate,
+        days_ahead: i64,
+    ) -> Vec<Notification> {
+        let until = as_of + Duration::days(days_ahead);
+        let mut notifications: Vec<Notification> = self
+            .grants
+            .iter()
+            .flat_map(|grant| {
+                grant
+                    .vest_events()
+                    .into_iter()
+                    .filter(|event| event.date >= as_of && event.date <= until)
+                    .map(|event| Notification {
+                        holder: self.name.clone(),
+                        grant_date: grant.grant_date,
+                        vest_date: event.date,
+                        amount: event.amount,
+                    })
+            })
+            .collect();
+
+        notifications.sort_by_key(|notification| notification.vest_date);
+        notifications
+    }
+}
+
+impl<W: Write> WriterSink<W> {
+    pub fn new(writer: W) -> WriterSink<W> {
+        WriterSink { writer }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl WriterSink<Stdout> {
+    pub fn stdout() -> WriterSink<Stdout> {
+        WriterSink::new(io::stdout())
+    }
+}
+
+impl WriterSink<File> {
+    /// Appends notifications to the file, creating it if needed.
+    pub fn append_to_file<P: AsRef<Path>>(path: P) -> io::Result<WriterSink<File>> {
+        Ok(WriterSink::new(
+            OpenOptions::new().create(true).append(true).open(path)?,
+        ))
+    }
+}
+
+impl<W: Write> NotificationSink for WriterSink<W> {
+    fn deliver(&mut self, notification: &Notification) -> Result<(), DeliveryError> {
+        writeln!(self.writer, "{}", notification).map_err(DeliveryError::Io)?;
+        self.writer.flush().map_err(DeliveryError::Io)
+    }
+}
+
+/// Quotes the text as a JSON string.
+#[cfg(feature = "webhook")]
+fn json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+
+    json + "\""
+}
+
+#[cfg(feature = "webhook")]
+impl WebhookSink {
+    pub fn new<S: Into<String>>(url: S) -> WebhookSink {
+        WebhookSink { url: url.into() }
+    }
+
+    fn body(notification: &Notification) -> String {
+        format!(
+            "{{\"text\":{},\"holder\":{},\"grant_date\":\"{}\",\"vest_date\":\"{}\",\"amount\":{}}}",
+            json_string(&notification.to_string()),
+            json_string(&notification.holder),
+            notification.grant_date,
+            notification.vest_date,
+            notification.amount
+        )
+    }
+}
+
+#[cfg(feature = "webhook")]
+impl NotificationSink for WebhookSink {
+    fn deliver(&mut self, notification: &Notification) -> Result<(), DeliveryError> {
+        ureq::post(&self.url)
+            .set("Content-Type", "application/json")
+            .send_string(&WebhookSink::body(notification))
+            .map_err(|error| DeliveryError::Http(Box::new(error)))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LocalDate, NotificationSink, WriterSink};
+    use crate::{Grant, Holder, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_deliver_upcoming_vest_notifications() {
+        let holder = Holder {
+            name: "Jane Doe".to_string(),
+            grants: vec![Grant::for_test(
+                4_800,
+                LocalDate::from_ymd(2021, 1, 15),
+                VestingScheduleConfiguration::monthly(12, 0.25, 48),
+            )],
+        };
+
+        let notifications = holder.upcoming_vest_notifications(LocalDate::from_ymd(2022, 1, 1), 45);
+        let mut sink = WriterSink::new(Vec::new());
+
+        for notification in &notifications {
+            sink.deliver(notification).unwrap();
+        }
+
+        assert_eq!(
+            String::from_utf8(sink.into_inner()).unwrap(),
+            "Jane Doe: 1,200 shares vest on 2022-01-15\n\
+             Jane Doe: 100 shares vest on 2022-02-15\n"
+        );
+    }
+
+    #[cfg(feature = "webhook")]
+    #[test]
+    fn it_can_format_webhook_payloads() {
+        let notification = super::Notification {
+            holder: "Jane \"JD\" Doe".to_string(),
+            grant_date: LocalDate::from_ymd(2021, 1, 15),
+            vest_date: LocalDate::from_ymd(2022, 1, 15),
+            amount: 1_200,
+        };
+
+        assert_eq!(
+            super::WebhookSink::body(&notification),
+            "{\"text\":\"Jane \\\"JD\\\" Doe: 1,200 shares vest on 2022-01-15\",\
+             \"holder\":\"Jane \\\"JD\\\" Doe\",\"grant_date\":\"2021-01-15\",\
+             \"vest_date\":\"2022-01-15\",\"amount\":1200}"
+        );
+    }
+}
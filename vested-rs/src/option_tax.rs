@@ -0,0 +1,211 @@
+use chrono::Datelike;
+use chronoutil::RelativeDuration;
+
+use crate::{LocalDate, Money, Price};
+
+/// Flat tax rates to compare option outcomes under.
+pub struct TaxAssumptions {
+    /// Rate on ordinary income and short-term capital gains.
+    pub ordinary_income_rate: f64,
+    pub long_term_capital_gains_rate: f64,
+    pub amt_rate: f64,
+    /// Part of the AMT exemption left over after the holder's other income, which the
+    /// ISO preference has to exceed before any AMT is owed.
+    pub amt_exemption: Money,
+}
+
+/// Options exercised and the resulting shares sold.
+pub struct ExerciseAndSale {
+    pub grant_date: LocalDate,
+    pub shares: i64,
+    pub strike_price: Price,
+    pub exercise_date: LocalDate,
+    /// Fair market value per share on the exercise date.
+    pub exercise_fair_market_value: Price,
+    pub sale_date: LocalDate,
+    pub sale_price: Price,
+}
+
+/// The tax owed on an exercise and sale under one kind of option.
+#[derive(Debug, PartialEq)]
+pub struct OptionTaxOutcome {
+    pub ordinary_income: Money,
+    /// Gain taxed at the long-term rate. Short-term gains are counted as ordinary income.
+    pub long_term_capital_gain: Money,
+    /// Spread at exercise counted towards the AMT, for ISO shares held past the end of
+    /// the year.
+    pub amt_preference: Money,
+    pub total_tax: Money,
+    /// Sale proceeds less the exercise cost and the tax.
+    pub net_proceeds: Money,
+}
+
+/// The same exercise and sale taxed as an ISO and as an NSO.
+#[derive(Debug, PartialEq)]
+pub struct IsoNsoComparison {
+    pub iso: OptionTaxOutcome,
+    pub nso: OptionTaxOutcome,
+    /// How much more the holder keeps with ISO treatment, negative if less.
+    pub iso_benefit: Money,
+}
+
+fn positive(amount: Money) -> Money {
+    if amount.minor_units() > 0 {
+        amount
+    } else {
+        Money::zero(amount.currency())
+    }
+}
+
+impl ExerciseAndSale {
+    fn held_over_a_year(&self) -> bool {
+        self.sale_date > self.exercise_date + RelativeDuration::years(1)
+    }
+
+    /// Checks if an ISO sale meets the holding periods for capital gains treatment: 2
+    /// years from the grant and 1 year from the exercise.
+    pub fn is_qualifying_disposition(&self) -> bool {
+        self.held_over_a_year() && self.sale_date > self.grant_date + RelativeDuration::years(2)
+    }
+
+    fn outcome(
+        &self,
+        assumptions: &TaxAssumptions,
+        ordinary_income: Money,
+        capital_gain: Money,
+        amt_preference: Money,
+    ) -> OptionTaxOutcome {
+        let (ordinary_income, long_term_capital_gain) = if self.held_over_a_year() {
+            (ordinary_income, capital_gain)
+        } else {
+            (
+                ordinary_income + capital_gain,
+                Money::zero(capital_gain.currency()),
+            )
+        };
+
+        let amt = positive(amt_preference - assumptions.amt_exemption).scale(assumptions.amt_rate);
+        let total_tax = positive(ordinary_income).scale(assumptions.ordinary_income_rate)
+            + positive(long_term_capital_gain).scale(assumptions.long_term_capital_gains_rate)
+            + amt;
+        let shares = self.shares;
+
+        OptionTaxOutcome {
+            ordinary_income,
+            long_term_capital_gain,
+            amt_preference,
+            total_tax,
+            net_proceeds: (self.sale_price - self.strike_price) * shares - total_tax,
+        }
+    }
+
+    /// Taxes the spread at exercise as ordinary income and the gain after it as a
+    /// capital gain.
+    pub fn nso_outcome(&self, assumptions: &TaxAssumptions) -> OptionTaxOutcome {
+        let shares = self.shares;
+        let spread = (self.exercise_fair_market_value - self.strike_price) * shares;
+        let capital_gain = (self.sale_price - self.exercise_fair_market_value) * shares;
+        let currency = self.strike_price.currency();
+
+        self.outcome(assumptions, spread, capital_gain, Money::zero(currency))
+    }
+
+    /// Taxes the whole gain as a long-term capital gain for a qualifying disposition,
+    /// with the spread at exercise subject to the AMT if the shares were held into a
+    /// later year. Otherwise the spread, up to the actual gain, is ordinary income.
+    pub fn iso_outcome(&self, assumptions: &TaxAssumptions) -> OptionTaxOutcome {
+        let shares = self.shares;
+        let spread = positive((self.exercise_fair_market_value - self.strike_price) * shares);
+        let gain = (self.sale_price - self.strike_price) * shares;
+        let currency = self.strike_price.currency();
+
+        let amt_preference = if self.sale_date.year() > self.exercise_date.year() {
+            spread
+        } else {
+            Money::zero(currency)
+        };
+
+        if self.is_qualifying_disposition() {
+            return self.outcome(assumptions, Money::zero(currency), gain, amt_preference);
+        }
+
+        let ordinary_income = if gain < spread {
+            positive(gain)
+        } else {
+            spread
+        };
+
+        self.outcome(
+            assumptions,
+            ordinary_income,
+            gain - ordinary_income,
+            amt_preference,
+        )
+    }
+}
+
+/// Runs the exercise and sale through ISO and NSO tax treatment side by side.
+pub fn compare_iso_nso(
+    scenario: &ExerciseAndSale,
+    assumptions: &TaxAssumptions,
+) -> IsoNsoComparison {
+    let iso = scenario.iso_outcome(assumptions);
+    let nso = scenario.nso_outcome(assumptions);
+    let iso_benefit = iso.net_proceeds - nso.net_proceeds;
+
+    IsoNsoComparison {
+        iso,
+        nso,
+        iso_benefit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compare_iso_nso, ExerciseAndSale, LocalDate, TaxAssumptions};
+    use crate::{usd, usd_price};
+
+    #[test]
+    fn it_can_compare_iso_and_nso_tax_outcomes() {
+        let assumptions = TaxAssumptions {
+            ordinary_income_rate: 0.4,
+            long_term_capital_gains_rate: 0.2,
+            amt_rate: 0.28,
+            amt_exemption: usd(50_000.0),
+        };
+        let scenario = ExerciseAndSale {
+            grant_date: LocalDate::from_ymd(2020, 1, 1),
+            shares: 10_000,
+            strike_price: usd_price(1.0),
+            exercise_date: LocalDate::from_ymd(2022, 6, 1),
+            exercise_fair_market_value: usd_price(11.0),
+            sale_date: LocalDate::from_ymd(2023, 7, 1),
+            sale_price: usd_price(21.0),
+        };
+
+        /*
+         * - ISO: $200000 long-term gain taxed at 20%, plus 28% AMT on the $50000 of
+         *   the $100000 spread above the exemption
+         * - NSO: $100000 spread at 40%, then a $100000 long-term gain at 20%
+         */
+
+        let comparison = compare_iso_nso(&scenario, &assumptions);
+
+        assert_eq!(comparison.iso.total_tax, usd(54_000.0));
+        assert_eq!(comparison.nso.total_tax, usd(60_000.0));
+        assert_eq!(comparison.iso_benefit, usd(6_000.0));
+
+        /*
+         * Selling in the year of exercise disqualifies the ISO, taxing it like the NSO.
+         */
+
+        let disqualified = ExerciseAndSale {
+            sale_date: LocalDate::from_ymd(2022, 12, 1),
+            ..scenario
+        };
+        let comparison = compare_iso_nso(&disqualified, &assumptions);
+
+        assert_eq!(comparison.iso, comparison.nso);
+        assert_eq!(comparison.iso.ordinary_income, usd(200_000.0));
+    }
+}
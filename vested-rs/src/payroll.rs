@@ -0,0 +1,310 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use chrono::{Datelike, Duration};
+use chronoutil::RelativeDuration;
+
+use crate::{Holder, LocalDate, Money, ValuationProvider};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PayFrequency {
+    Weekly,
+    BiWeekly,
+    /// The 1st through the 15th and the 16th through the end of each month.
+    SemiMonthly,
+    Monthly,
+}
+
+/// How an employer's pay periods fall on the calendar.
+pub struct PaySchedule {
+    pub frequency: PayFrequency,
+    /// First day of any weekly or bi-weekly pay period. Ignored by the other frequencies.
+    pub anchor_date: LocalDate,
+}
+
+/// Vest income for one employee within one pay period.
+#[derive(Debug, PartialEq)]
+pub struct VestIncomeRecord {
+    pub employee: String,
+    pub period_start: LocalDate,
+    pub period_end: LocalDate,
+    pub shares: i64,
+    pub income: Money,
+}
+
+/// A column of a payroll export.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PayrollField {
+    Employee,
+    PeriodStart,
+    PeriodEnd,
+    Shares,
+    /// Income in major units without a currency symbol, e.g. `1234.50`.
+    Income,
+    /// ISO 4217 code of the income's currency.
+    Currency,
+    /// The same value on every row, e.g. an earnings code expected by the payroll system.
+    Constant(String),
+}
+
+/// Column order, headers and formatting of a payroll export.
+pub struct PayrollLayout {
+    /// Header and field of each column, in order.
+    pub columns: Vec<(String, PayrollField)>,
+    pub delimiter: char,
+    /// strftime-style format used for dates.
+    pub date_format: String,
+    pub include_header: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PayrollError {
+    /// No fair market value is available for a vest date.
+    MissingPrice { employee: String, date: LocalDate },
+}
+
+impl fmt::Display for PayrollError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PayrollError::MissingPrice { employee, date } => write!(
+                f,
+                "no fair market value for {}'s vest on {}",
+                employee, date
+            ),
+        }
+    }
+}
+
+impl Error for PayrollError {}
+
+impl PaySchedule {
+    /// Returns the first and last day of the pay period containing the date.
+    pub fn period_containing(&self, date: LocalDate) -> (LocalDate, LocalDate) {
+        match self.frequency {
+            PayFrequency::Weekly | PayFrequency::BiWeekly => {
+                let length = if self.frequency == PayFrequency::Weekly {
+                    7
+                } else {
+                    14
+                };
+                let offset = (date - self.anchor_date).num_days().div_euclid(length);
+                let start = self.anchor_date + Duration::days(offset * length);
+
+                (start, start + Duration::days(length - 1))
+            }
+            PayFrequency::SemiMonthly if date.day() <= 15 => {
+                (date.with_day(1).unwrap(), date.with_day(15).unwrap())
+            }
+            PayFrequency::SemiMonthly => (date.with_day(16).unwrap(), last_day_of_month(date)),
+            PayFrequency::Monthly => (date.with_day(1).unwrap(), last_day_of_month(date)),
+        }
+    }
+}
+
+fn last_day_of_month(date: LocalDate) -> LocalDate {
+    date.with_day(1).unwrap() + RelativeDuration::months(1) - Duration::days(1)
+}
+
+impl Default for PayrollLayout {
+    fn default() -> PayrollLayout {
+        PayrollLayout {
+            columns: vec![
+                ("Employee".to_string(), PayrollField::Employee),
+                ("Period Start".to_string(), PayrollField::PeriodStart),
+                ("Period End".to_string(), PayrollField::PeriodEnd),
+                ("Shares".to_string(), PayrollField::Shares),
+                ("Income".to_string(), PayrollField::Income),
+            ],
+            delimiter: ',',
+            date_format: "%Y-%m-%d".to_string(),
+            include_header: true,
+        }
+    }
+}
+
+impl PayrollLayout {
+    /// Quotes the value if it contains the delimiter, a quote or a line break.
+    fn escape(&self, value: &str) -> String {
+        if value.contains([self.delimiter, '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+
+    fn format_field(&self, field: &PayrollField, record: &VestIncomeRecord) -> String {
+        match field {
+            PayrollField::Employee => record.employee.clone(),
+            PayrollField::PeriodStart => record.period_start.format(&self.date_format).to_string(),
+            PayrollField::PeriodEnd => record.period_end.format(&self.date_format).to_string(),
+            PayrollField::Shares => record.shares.to_string(),
+            PayrollField::Income => format!(
+                "{:.*}",
+                record.income.currency().decimal_places() as usize,
+                record.income.to_major_units()
+            ),
+            PayrollField::Currency => record.income.currency().code().to_string(),
+            PayrollField::Constant(value) => value.clone(),
+        }
+    }
+
+    /// Writes the records as CSV, one line per record.
+    pub fn to_csv(&self, records: &[VestIncomeRecord]) -> String {
+        let delimiter = self.delimiter.to_string();
+        let mut lines = Vec::with_capacity(records.len() + 1);
+
+        if self.include_header {
+            lines.push(
+                self.columns
+                    .iter()
+                    .map(|(header, _)| self.escape(header))
+                    .collect::<Vec<_>>()
+                    .join(&delimiter),
+            );
+        }
+
+        for record in records {
+            lines.push(
+                self.columns
+                    .iter()
+                    .map(|(_, field)| self.escape(&self.format_field(field, record)))
+                    .collect::<Vec<_>>()
+                    .join(&delimiter),
+            );
+        }
+
+        lines.into_iter().map(|line| line + "\n").collect()
+    }
+}
+
+/// Totals each employee's vest income per pay period for vests between the two dates,
+/// inclusive, valuing shares at their fair market value on the vest date. Records are
+/// ordered by employee, then period.
+pub fn vest_income_records<V: ValuationProvider>(
+    holders: &[Holder],
+    pay_schedule: &PaySchedule,
+    provider: &V,
+    from_date: LocalDate,
+    to_date: LocalDate,
+) -> Result<Vec<VestIncomeRecord>, PayrollError> {
+    let mut records = Vec::new();
+
+    for holder in holders {
+        let mut periods: BTreeMap<(LocalDate, LocalDate), (i64, Money)> = BTreeMap::new();
+
+        for event in holder.grants.iter().flat_map(|grant| grant.vest_events()) {
+            if event.date < from_date || event.date > to_date {
+                continue;
+            }
+
+            let price = provider
+                .fmv_on(event.date)
+                .ok_or_else(|| PayrollError::MissingPrice {
+                    employee: holder.name.clone(),
+                    date: event.date,
+                })?;
+            let income = price * event.amount;
+
+            periods
+                .entry(pay_schedule.period_containing(event.date))
+                .and_modify(|(shares, total)| {
+                    *shares += event.amount;
+                    *total += income;
+                })
+                .or_insert((event.amount, income));
+        }
+
+        records.extend(periods.into_iter().map(
+            |((period_start, period_end), (shares, income))| VestIncomeRecord {
+                employee: holder.name.clone(),
+                period_start,
+                period_end,
+                shares,
+                income,
+            },
+        ));
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        vest_income_records, LocalDate, PayFrequency, PaySchedule, PayrollError, PayrollField,
+        PayrollLayout,
+    };
+    use crate::{Currency, Grant, Holder, Price, PriceTable, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_export_vest_income_for_payroll() {
+        let holders = vec![Holder {
+            name: "Doe, Jane".to_string(),
+            grants: vec![
+                Grant::for_test(
+                    300,
+                    LocalDate::from_ymd(2021, 1, 10),
+                    VestingScheduleConfiguration::monthly(0, 0.0, 3),
+                ),
+                Grant::for_test(
+                    100,
+                    LocalDate::from_ymd(2021, 1, 5),
+                    VestingScheduleConfiguration::monthly(0, 0.0, 1),
+                ),
+            ],
+        }];
+
+        let prices = PriceTable::new(vec![(
+            LocalDate::from_ymd(2021, 1, 1),
+            Price::from_major_units(12.5, Currency::Usd),
+        )]);
+        let pay_schedule = PaySchedule {
+            frequency: PayFrequency::SemiMonthly,
+            anchor_date: LocalDate::from_ymd(2021, 1, 1),
+        };
+
+        let records = vest_income_records(
+            &holders,
+            &pay_schedule,
+            &prices,
+            LocalDate::from_ymd(2021, 1, 1),
+            LocalDate::from_ymd(2021, 3, 1),
+        )
+        .unwrap();
+
+        /*
+         * - 2021/2/5 and 2021/2/10: 200 shares in the Feb 1 - 15 pay period
+         * - 2021/3/10: outside the export window
+         */
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].shares, 200);
+
+        let mut layout = PayrollLayout::default();
+        layout.columns.push((
+            "Earnings Code".to_string(),
+            PayrollField::Constant("RSU".to_string()),
+        ));
+
+        assert_eq!(
+            layout.to_csv(&records),
+            "Employee,Period Start,Period End,Shares,Income,Earnings Code\n\
+             \"Doe, Jane\",2021-02-01,2021-02-15,200,2500.00,RSU\n"
+        );
+
+        assert_eq!(
+            vest_income_records(
+                &holders,
+                &pay_schedule,
+                &PriceTable::default(),
+                LocalDate::from_ymd(2021, 1, 1),
+                LocalDate::from_ymd(2021, 3, 1),
+            ),
+            Err(PayrollError::MissingPrice {
+                employee: "Doe, Jane".to_string(),
+                date: LocalDate::from_ymd(2021, 2, 10),
+            })
+        );
+    }
+}
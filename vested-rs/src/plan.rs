@@ -0,0 +1,274 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{
+    EvergreenProvision, Grant, GrantKind, GrantType, LocalDate, VestingError,
+    VestingScheduleConfiguration,
+};
+
+/// Standard terms a plan issues grants with, e.g. a 4 year RSU grant with a 1 year cliff.
+pub struct GrantTemplate {
+    pub name: String,
+    pub grant_type: GrantType,
+    pub vesting_schedule: VestingScheduleConfiguration,
+}
+
+impl GrantTemplate {
+    /// Makes a grant with the template's terms.
+    pub fn grant(&self, amount: i64, grant_date: LocalDate) -> Grant {
+        Grant {
+            amount,
+            grant_date,
+            vesting_schedule: self.vesting_schedule.clone(),
+            termination_date: None,
+            vesting_commencement_date: None,
+            grant_type: self.grant_type.clone(),
+        }
+    }
+}
+
+/// An equity incentive plan, which issues grants out of a fixed share reserve.
+pub struct EquityPlan {
+    pub name: String,
+    /// Shares set aside for the plan.
+    pub share_reserve: i64,
+    /// Longest schedule, in months, a grant may vest over.
+    pub max_term_months: i32,
+    pub allowed_grant_types: Vec<GrantKind>,
+    pub templates: Vec<GrantTemplate>,
+    /// Automatic yearly increase to the reserve, if the plan has one.
+    pub evergreen: Option<EvergreenProvision>,
+    issued_grants: Vec<Grant>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PlanError {
+    InvalidGrant(VestingError),
+    GrantTypeNotAllowed(GrantKind),
+    /// The grant vests over more months than the plan allows.
+    TermTooLong {
+        months: i32,
+        max_months: i32,
+    },
+    /// The plan's remaining reserve can't cover the grant.
+    InsufficientReserve {
+        requested: i64,
+        remaining: i64,
+    },
+    UnknownTemplate(String),
+    UnknownPlan(String),
+    /// No grant was issued under the given index.
+    UnknownGrant(usize),
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlanError::InvalidGrant(error) => write!(f, "invalid grant: {}", error),
+            PlanError::GrantTypeNotAllowed(kind) => {
+                write!(f, "plan doesn't allow {:?} grants", kind)
+            }
+            PlanError::TermTooLong { months, max_months } => write!(
+                f,
+                "grant vests over {} months, longer than the plan's {} month maximum",
+                months, max_months
+            ),
+            PlanError::InsufficientReserve {
+                requested,
+                remaining,
+            } => write!(
+                f,
+                "grant of {} shares exceeds the {} shares remaining in the reserve",
+                requested, remaining
+            ),
+            PlanError::UnknownTemplate(name) => write!(f, "no grant template named {}", name),
+            PlanError::UnknownPlan(name) => write!(f, "no equity plan named {}", name),
+            PlanError::UnknownGrant(index) => write!(f, "no grant issued with index {}", index),
+        }
+    }
+}
+
+impl Error for PlanError {}
+
+impl From<VestingError> for PlanError {
+    fn from(error: VestingError) -> PlanError {
+        PlanError::InvalidGrant(error)
+    }
+}
+
+impl EquityPlan {
+    pub fn new(
+        name: &str,
+        share_reserve: i64,
+        max_term_months: i32,
+        allowed_grant_types: Vec<GrantKind>,
+    ) -> EquityPlan {
+        EquityPlan {
+            name: name.to_string(),
+            share_reserve,
+            max_term_months,
+            allowed_grant_types,
+            templates: Vec::new(),
+            evergreen: None,
+            issued_grants: Vec::new(),
+        }
+    }
+
+    pub fn issued_grants(&self) -> &[Grant] {
+        &self.issued_grants
+    }
+
+    /// Shares still available to grant. Shares forfeited by terminated grants return
+    /// to the reserve.
+    pub fn remaining_reserve(&self) -> i64 {
+        let outstanding: i64 = self
+            .issued_grants
+            .iter()
+            .map(|grant| match grant.termination_date {
+                Some(termination_date) => grant.calculate_vested_shares(termination_date),
+                None => grant.amount,
+            })
+            .sum();
+
+        self.share_reserve - outstanding
+    }
+
+    /// Checks that the plan could issue the grant without issuing it.
+    pub fn check(&self, grant: &Grant) -> Result<(), PlanError> {
+        grant.validate()?;
+
+        let kind = grant.grant_type.kind();
+
+        if !self.allowed_grant_types.contains(&kind) {
+            return Err(PlanError::GrantTypeNotAllowed(kind));
+        }
+
+        let months = grant.vesting_schedule.total_vesting_months();
+
+        if months > self.max_term_months {
+            return Err(PlanError::TermTooLong {
+                months,
+                max_months: self.max_term_months,
+            });
+        }
+
+        let remaining = self.remaining_reserve();
+
+        if grant.amount > remaining {
+            return Err(PlanError::InsufficientReserve {
+                requested: grant.amount,
+                remaining,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Issues the grant out of the reserve if it's within the plan's limits, returning
+    /// its index among the issued grants.
+    pub fn issue(&mut self, grant: Grant) -> Result<usize, PlanError> {
+        self.check(&grant)?;
+
+        self.issued_grants.push(grant);
+
+        Ok(self.issued_grants.len() - 1)
+    }
+
+    /// Issues a grant with the terms of the named template.
+    pub fn issue_from_template(
+        &mut self,
+        template_name: &str,
+        amount: i64,
+        grant_date: LocalDate,
+    ) -> Result<usize, PlanError> {
+        let grant = self.template(template_name)?.grant(amount, grant_date);
+
+        self.issue(grant)
+    }
+
+    pub fn template(&self, template_name: &str) -> Result<&GrantTemplate, PlanError> {
+        self.templates
+            .iter()
+            .find(|template| template.name == template_name)
+            .ok_or_else(|| PlanError::UnknownTemplate(template_name.to_string()))
+    }
+
+    /// Terminates an issued grant, returning its unvested shares to the reserve.
+    pub fn forfeit(&mut self, index: usize, termination_date: LocalDate) -> Result<(), PlanError> {
+        let grant = self
+            .issued_grants
+            .get_mut(index)
+            .ok_or(PlanError::UnknownGrant(index))?;
+
+        grant.terminate(termination_date);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EquityPlan, GrantKind, GrantTemplate, GrantType, LocalDate, PlanError};
+    use crate::{Currency, Grant, Price, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_issue_grants_within_plan_limits() {
+        let mut plan = EquityPlan::new("2021 Plan", 10_000, 48, vec![GrantKind::Rsu]);
+        plan.templates.push(GrantTemplate {
+            name: "Standard RSU".to_string(),
+            grant_type: GrantType::default(),
+            vesting_schedule: VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        });
+
+        let index = plan
+            .issue_from_template("Standard RSU", 4_800, LocalDate::from_ymd(2021, 1, 1))
+            .unwrap();
+
+        assert_eq!(plan.remaining_reserve(), 5_200);
+
+        let grant = |amount, total_vesting_months| {
+            Grant::for_test(
+                amount,
+                LocalDate::from_ymd(2021, 1, 1),
+                VestingScheduleConfiguration::monthly(0, 0.0, total_vesting_months),
+            )
+        };
+
+        let option = grant(1_000, 48).with_grant_type(GrantType::Iso {
+            strike_price: Price::from_major_units(1.0, Currency::Usd),
+        });
+
+        assert_eq!(
+            plan.issue(option),
+            Err(PlanError::GrantTypeNotAllowed(GrantKind::Iso))
+        );
+        assert_eq!(
+            plan.issue(grant(1_000, 60)),
+            Err(PlanError::TermTooLong {
+                months: 60,
+                max_months: 48,
+            })
+        );
+        assert_eq!(
+            plan.issue(grant(6_000, 48)),
+            Err(PlanError::InsufficientReserve {
+                requested: 6_000,
+                remaining: 5_200,
+            })
+        );
+
+        /*
+         * Leaving after 2 years returns the 2400 unvested shares to the reserve.
+         */
+
+        plan.forfeit(index, LocalDate::from_ymd(2023, 1, 1))
+            .unwrap();
+
+        assert_eq!(plan.remaining_reserve(), 7_600);
+        assert_eq!(plan.issue(grant(6_000, 48)), Ok(1));
+        assert_eq!(
+            plan.issue_from_template("Advisor", 100, LocalDate::from_ymd(2021, 1, 1)),
+            Err(PlanError::UnknownTemplate("Advisor".to_string()))
+        );
+    }
+}
@@ -0,0 +1,330 @@
+use std::collections::BTreeSet;
+
+#[cfg(feature = "serde")]
+use crate::VestingDuration;
+use crate::{Grant, LocalDate, VestingError, VestingPeriod, VestingSchedule};
+
+/// Any number of grants, each with its own grant date and schedule, tracked together.
+#[derive(Default)]
+pub struct Portfolio {
+    pub grants: Vec<Grant>,
+}
+
+/// A grant as read from a broker export, which may give when vesting commences as an
+/// offset from the grant date, e.g. `commencement_offset = { months = -2 }`, instead
+/// of a vesting commencement date.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+pub struct ImportedGrant {
+    #[serde(flatten)]
+    pub grant: Grant,
+    /// How long after the grant date vesting commences, negative when it commenced
+    /// before. Ignored if the grant gives a vesting commencement date.
+    #[serde(default)]
+    pub commencement_offset: Option<VestingDuration>,
+}
+
+#[cfg(feature = "serde")]
+impl ImportedGrant {
+    /// Applies the commencement offset, so shares that vested before the grant date
+    /// catch up on it.
+    pub fn into_grant(self) -> Grant {
+        match (
+            self.commencement_offset,
+            self.grant.vesting_commencement_date,
+        ) {
+            (Some(offset), None) => {
+                let commencement_date = self.grant.grant_date + offset.to_relative_duration();
+
+                self.grant.with_vesting_commencement_date(commencement_date)
+            }
+            _ => self.grant,
+        }
+    }
+}
+
+/// One grant's share of a portfolio on a given date.
+#[derive(Debug, PartialEq)]
+pub struct GrantBreakdown {
+    pub grant_date: LocalDate,
+    pub amount: i64,
+    pub vested_amount: i64,
+    pub unvested_amount: i64,
+}
+
+impl Portfolio {
+    pub fn new(grants: Vec<Grant>) -> Portfolio {
+        Portfolio { grants }
+    }
+
+    /// Makes a portfolio from imported grants, applying their commencement offsets.
+    #[cfg(feature = "serde")]
+    pub fn import(grants: Vec<ImportedGrant>) -> Portfolio {
+        Portfolio::new(grants.into_iter().map(ImportedGrant::into_grant).collect())
+    }
+
+    /// Calculates the whole shares vested across all grants on the given date, or an
+    /// error if the total is too large for an `i64`.
+    pub fn total_vested_on(&self, date: LocalDate) -> Result<i64, VestingError> {
+        checked_total(
+            self.grants
+                .iter()
+                .map(|grant| grant.calculate_vested_shares(date)),
+        )
+    }
+
+    /// Merges the vesting periods of every grant onto one timeline, with a period on
+    /// each date any grant vests. Returns `None` for an empty portfolio, or an error if
+    /// the combined amounts are too large for an `i64`.
+    pub fn combined_schedule(&self) -> Result<Option<VestingSchedule>, VestingError> {
+        let schedules: Vec<VestingSchedule> = self
+            .grants
+            .iter()
+            .map(Grant::calculate_vesting_schedule)
+            .collect();
+
+        let dates: BTreeSet<LocalDate> = schedules
+            .iter()
+            .flat_map(|schedule| schedule.periods.iter().map(|period| period.date))
+            .collect();
+
+        let mut periods = dates
+            .into_iter()
+            .map(|date| {
+                let latest_periods = schedules.iter().filter_map(|schedule| {
+                    schedule
+                        .periods
+                        .iter()
+                        .take_while(|period| period.date <= date)
+                        .last()
+                });
+
+                Ok(VestingPeriod::new(
+                    date,
+                    latest_periods
+                        .map(|period| period.precise_cumulative_amount)
+                        .sum(),
+                    self.total_vested_on(date)?,
+                ))
+            })
+            .collect::<Result<Vec<VestingPeriod>, VestingError>>()?;
+
+        VestingPeriod::update_vested_this_period(&mut periods);
+
+        let forfeited_amount =
+            checked_total(schedules.iter().map(|schedule| schedule.forfeited_amount))?;
+        let (Some(from_date), Some(to_date)) = (
+            schedules.iter().map(|schedule| schedule.from_date).min(),
+            schedules.iter().map(|schedule| schedule.to_date).max(),
+        ) else {
+            return Ok(None);
+        };
+
+        Ok(Some(VestingSchedule {
+            from_date,
+            to_date,
+            periods,
+            forfeited_amount,
+        }))
+    }
+
+    /// Splits the portfolio's vested and unvested shares on the given date by grant,
+    /// in the order the grants were added.
+    pub fn breakdown_on(&self, date: LocalDate) -> Vec<GrantBreakdown> {
+        self.grants
+            .iter()
+            .map(|grant| {
+                let vested_amount = grant.calculate_vested_shares(date);
+
+                GrantBreakdown {
+                    grant_date: grant.grant_date,
+                    amount: grant.amount,
+                    vested_amount,
+                    unvested_amount: grant.calculate_unvested_amount(date),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Adds up share amounts, failing instead of wrapping if the total overflows.
+fn checked_total(mut amounts: impl Iterator<Item = i64>) -> Result<i64, VestingError> {
+    amounts.try_fold(0_i64, |total, amount| {
+        total
+            .checked_add(amount)
+            .ok_or(VestingError::AmountOverflow)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "serde")]
+    use super::ImportedGrant;
+    use super::{GrantBreakdown, LocalDate, Portfolio};
+    use crate::{Grant, VestingError, VestingPeriod, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_combine_grants_into_a_portfolio() {
+        let portfolio = Portfolio::new(vec![
+            Grant::for_test(
+                1_200,
+                LocalDate::from_ymd(2021, 1, 1),
+                VestingScheduleConfiguration::monthly(0, 0.0, 12),
+            ),
+            Grant::for_test(
+                400,
+                LocalDate::from_ymd(2021, 3, 15),
+                VestingScheduleConfiguration::monthly(0, 0.0, 4),
+            ),
+        ]);
+
+        assert_eq!(
+            portfolio.total_vested_on(LocalDate::from_ymd(2021, 5, 15)),
+            Ok(600)
+        );
+
+        let combined_schedule = portfolio.combined_schedule().unwrap().unwrap();
+
+        assert_eq!(combined_schedule.from_date, LocalDate::from_ymd(2021, 1, 1));
+        assert_eq!(combined_schedule.to_date, LocalDate::from_ymd(2022, 1, 1));
+        assert_eq!(combined_schedule.periods.len(), 18);
+        assert_eq!(
+            combined_schedule.periods[3],
+            VestingPeriod {
+                date: LocalDate::from_ymd(2021, 3, 15),
+                cumulative_vested_amount: 200,
+                vested_this_period: 0,
+                precise_cumulative_amount: 200.0,
+            }
+        );
+
+        assert_eq!(
+            portfolio.breakdown_on(LocalDate::from_ymd(2021, 5, 15))[1],
+            GrantBreakdown {
+                grant_date: LocalDate::from_ymd(2021, 3, 15),
+                amount: 400,
+                vested_amount: 200,
+                unvested_amount: 200,
+            }
+        );
+        assert!(matches!(Portfolio::default().combined_schedule(), Ok(None)));
+    }
+
+    #[test]
+    fn it_errors_when_combined_amounts_overflow() {
+        let grant = |amount| {
+            Grant::for_test(
+                amount,
+                LocalDate::from_ymd(2021, 1, 1),
+                VestingScheduleConfiguration::monthly(0, 0.0, 4),
+            )
+        };
+        let large = Portfolio::new(vec![grant(2_000_000_000), grant(2_000_000_000)]);
+        let overflowing = Portfolio::new(vec![grant(i64::MAX), grant(1)]);
+
+        assert_eq!(
+            large.total_vested_on(LocalDate::from_ymd(2021, 6, 1)),
+            Ok(4_000_000_000)
+        );
+        assert_eq!(
+            overflowing.total_vested_on(LocalDate::from_ymd(2021, 6, 1)),
+            Err(VestingError::AmountOverflow)
+        );
+        assert!(matches!(
+            overflowing.combined_schedule(),
+            Err(VestingError::AmountOverflow)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn it_applies_commencement_offsets_when_importing() {
+        let grants: Vec<ImportedGrant> = serde_json::from_str(
+            r#"[
+                {
+                    "amount": 4800,
+                    "grant_date": "2021-03-06",
+                    "commencement_offset": { "months": -2 },
+                    "vesting_schedule": {
+                        "interval": "monthly",
+                        "cliff_percentage": 0.0,
+                        "cliff": 0,
+                        "length": 48
+                    }
+                },
+                {
+                    "amount": 4800,
+                    "grant_date": "2021-03-06",
+                    "commencement_offset": { "months": 2 },
+                    "vesting_schedule": {
+                        "interval": "monthly",
+                        "cliff_percentage": 0.0,
+                        "cliff": 0,
+                        "length": 48
+                    }
+                },
+                {
+                    "amount": 1200,
+                    "grant_date": "2021-03-06",
+                    "commencement_offset": { "days": -10 },
+                    "vesting_schedule": {
+                        "interval": "monthly",
+                        "cliff_percentage": 0.0,
+                        "cliff": 0,
+                        "length": 12
+                    }
+                },
+                {
+                    "amount": 1200,
+                    "grant_date": "2021-03-06",
+                    "vesting_schedule": {
+                        "interval": "monthly",
+                        "cliff_percentage": 0.0,
+                        "cliff": 0,
+                        "length": 12
+                    }
+                }
+            ]"#,
+        )
+        .unwrap();
+        let portfolio = Portfolio::import(grants);
+
+        assert_eq!(
+            portfolio.grants[0].vesting_commencement_date(),
+            LocalDate::from_ymd(2021, 1, 6)
+        );
+        assert_eq!(
+            portfolio.grants[1].vesting_commencement_date(),
+            LocalDate::from_ymd(2021, 5, 6)
+        );
+        assert_eq!(
+            portfolio.grants[1].calculate_vested_shares(LocalDate::from_ymd(2021, 4, 6)),
+            0
+        );
+        assert_eq!(
+            portfolio.grants[1].calculate_vested_shares(LocalDate::from_ymd(2021, 6, 6)),
+            100
+        );
+        assert_eq!(
+            portfolio.grants[2].vesting_commencement_date(),
+            LocalDate::from_ymd(2021, 2, 24)
+        );
+        assert_eq!(
+            portfolio.grants[2].calculate_vested_shares(LocalDate::from_ymd(2021, 4, 24)),
+            200
+        );
+        assert_eq!(
+            portfolio.grants[3].vesting_commencement_date(),
+            LocalDate::from_ymd(2021, 3, 6)
+        );
+        assert_eq!(
+            portfolio.grants[0].calculate_vesting_schedule().periods[0],
+            VestingPeriod {
+                date: LocalDate::from_ymd(2021, 3, 6),
+                cumulative_vested_amount: 200,
+                vested_this_period: 200,
+                precise_cumulative_amount: 200.0,
+            }
+        );
+    }
+}
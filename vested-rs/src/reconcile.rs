@@ -0,0 +1,200 @@
+use crate::{Grant, LocalDate, VestEvent};
+
+/// A vest event as reported by a broker or plan administrator.
+#[derive(Debug, PartialEq)]
+pub struct ReportedVestEvent {
+    pub date: LocalDate,
+    pub amount: i64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Discrepancy {
+    /// Both sides have an event on the date, but the amounts differ.
+    AmountMismatch {
+        date: LocalDate,
+        expected: i64,
+        reported: i64,
+    },
+    /// The amounts match, but the event was reported on a nearby date.
+    DateMismatch {
+        expected_date: LocalDate,
+        reported_date: LocalDate,
+        amount: i64,
+    },
+    /// The computed schedule vests on this date, but nothing was reported.
+    Missing { date: LocalDate, expected: i64 },
+    /// A reported event that doesn't correspond to anything in the computed schedule.
+    Unexpected { date: LocalDate, reported: i64 },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ReconciliationReport {
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+impl ReconciliationReport {
+    pub fn is_reconciled(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+}
+
+impl Grant {
+    /// Compares reported vest events against the computed schedule. Reported events
+    /// within `date_tolerance_days` of a computed event with the same amount are
+    /// flagged as date mismatches rather than as missing/unexpected events, pairing
+    /// each computed event with the closest one.
+    pub fn reconcile(
+        &self,
+        reported_events: &[ReportedVestEvent],
+        date_tolerance_days: i64,
+    ) -> ReconciliationReport {
+        let mut unmatched: Vec<&ReportedVestEvent> = reported_events.iter().collect();
+        let mut discrepancies = Vec::new();
+
+        for VestEvent { date, amount } in self.vest_events() {
+            if let Some(index) = unmatched.iter().position(|event| event.date == date) {
+                let reported = unmatched.remove(index).amount;
+
+                if reported != amount {
+                    discrepancies.push(Discrepancy::AmountMismatch {
+                        date,
+                        expected: amount,
+                        reported,
+                    });
+                }
+            } else if let Some(index) = unmatched
+                .iter()
+                .enumerate()
+                .map(|(index, event)| (index, event, (event.date - date).num_days().abs()))
+                .filter(|(_, event, distance)| {
+                    event.amount == amount && *distance <= date_tolerance_days
+                })
+                .min_by_key(|(_, _, distance)| *distance)
+                .map(|(index, _, _)| index)
+            {
+                discrepancies.push(Discrepancy::DateMismatch {
+                    expected_date: date,
+                    reported_date: unmatched.remove(index).date,
+                    amount,
+                });
+            } else {
+                discrepancies.push(Discrepancy::Missing {
+                    date,
+                    expected: amount,
+                });
+            }
+        }
+
+        discrepancies.extend(unmatched.into_iter().map(|event| Discrepancy::Unexpected {
+            date: event.date,
+            reported: event.amount,
+        }));
+
+        ReconciliationReport { discrepancies }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Discrepancy, LocalDate, ReportedVestEvent};
+    use crate::{Grant, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_reconcile_reported_vest_events() {
+        let grant = Grant::for_test(
+            1_200,
+            LocalDate::from_ymd(2020, 1, 15),
+            VestingScheduleConfiguration::monthly(0, 0.0, 4),
+        );
+
+        let reported_events = vec![
+            ReportedVestEvent {
+                date: LocalDate::from_ymd(2020, 2, 15),
+                amount: 300,
+            },
+            ReportedVestEvent {
+                date: LocalDate::from_ymd(2020, 3, 17),
+                amount: 300,
+            },
+            ReportedVestEvent {
+                date: LocalDate::from_ymd(2020, 4, 15),
+                amount: 250,
+            },
+            ReportedVestEvent {
+                date: LocalDate::from_ymd(2020, 9, 1),
+                amount: 50,
+            },
+        ];
+
+        let report = grant.reconcile(&reported_events, 5);
+
+        assert!(!report.is_reconciled());
+        assert_eq!(
+            report.discrepancies,
+            vec![
+                Discrepancy::DateMismatch {
+                    expected_date: LocalDate::from_ymd(2020, 3, 15),
+                    reported_date: LocalDate::from_ymd(2020, 3, 17),
+                    amount: 300,
+                },
+                Discrepancy::AmountMismatch {
+                    date: LocalDate::from_ymd(2020, 4, 15),
+                    expected: 300,
+                    reported: 250,
+                },
+                Discrepancy::Missing {
+                    date: LocalDate::from_ymd(2020, 5, 15),
+                    expected: 300,
+                },
+                Discrepancy::Unexpected {
+                    date: LocalDate::from_ymd(2020, 9, 1),
+                    reported: 50,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_matches_the_closest_reported_date() {
+        let grant = Grant::for_test(
+            1_200,
+            LocalDate::from_ymd(2020, 1, 15),
+            VestingScheduleConfiguration::monthly(0, 0.0, 4),
+        );
+        let reported = |month, day| ReportedVestEvent {
+            date: LocalDate::from_ymd(2020, month, day),
+            amount: 300,
+        };
+
+        /*
+         * 3/3 is within 20 days of the 2/15 vest, but 2/20 is closer and is the only
+         * report close enough to be the 3/15 vest.
+         */
+
+        let report = grant.reconcile(
+            &[
+                reported(3, 3),
+                reported(2, 20),
+                reported(4, 15),
+                reported(5, 15),
+            ],
+            20,
+        );
+
+        assert_eq!(
+            report.discrepancies,
+            vec![
+                Discrepancy::DateMismatch {
+                    expected_date: LocalDate::from_ymd(2020, 2, 15),
+                    reported_date: LocalDate::from_ymd(2020, 2, 20),
+                    amount: 300,
+                },
+                Discrepancy::DateMismatch {
+                    expected_date: LocalDate::from_ymd(2020, 3, 15),
+                    reported_date: LocalDate::from_ymd(2020, 3, 3),
+                    amount: 300,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{Grant, GrantType, Holder, LocalDate, VestingScheduleConfiguration};
+
+/// An employee up for a refresh grant.
+pub struct RefreshCandidate {
+    pub holder: Holder,
+    pub start_date: LocalDate,
+    /// Index into the rules' tier weights, e.g. 0 for the lowest rated.
+    pub performance_tier: usize,
+}
+
+/// How a refresh budget is split between candidates, and the terms of the grants.
+///
+/// Each candidate is weighted by their performance tier, raised for tenure and lowered
+/// for the shares they still have unvested, then given their weighted share of the
+/// budget.
+pub struct RefreshRules {
+    pub as_of: LocalDate,
+    /// Weight for each performance tier. Candidates in a tier past the end get nothing.
+    pub tier_weights: Vec<f64>,
+    /// Weight added per full year of tenure, e.g. 0.1 to weigh a 5 year employee 50%
+    /// more than a new hire.
+    pub tenure_weight_per_year: f64,
+    /// Unvested shares a candidate is considered fully retained at. Candidates are
+    /// weighted by how far short of it they are; zero disables the rule.
+    pub unvested_target: i64,
+    pub grant_type: GrantType,
+    pub vesting_schedule: VestingScheduleConfiguration,
+}
+
+/// A refresh grant proposed by the allocator.
+pub struct RefreshProposal {
+    pub holder: String,
+    pub weight: f64,
+    /// The proposed grant, dated on the rules' `as_of` date.
+    pub grant: Grant,
+}
+
+impl RefreshRules {
+    fn weight(&self, candidate: &RefreshCandidate) -> f64 {
+        let tier_weight = match self.tier_weights.get(candidate.performance_tier) {
+            Some(tier_weight) => *tier_weight,
+            None => return 0.0,
+        };
+
+        let tenure_years = ((self.as_of - candidate.start_date).num_days() / 365).max(0);
+        let tenure_factor = 1.0 + tenure_years as f64 * self.tenure_weight_per_year;
+
+        let unvested_factor = if self.unvested_target > 0 {
+            let unvested_amount: i64 = candidate
+                .holder
+                .grants
+                .iter()
+                .map(|grant| grant.calculate_unvested_amount(self.as_of))
+                .sum();
+
+            (self.unvested_target - unvested_amount).max(0) as f64 / self.unvested_target as f64
+        } else {
+            1.0
+        };
+
+        tier_weight * tenure_factor * unvested_factor
+    }
+}
+
+/// Splits a budget of shares between the candidates by their weight under the rules,
+/// rounding each grant down to whole shares. Candidates who'd get nothing are left out.
+pub fn allocate_refresh_budget(
+    candidates: &[RefreshCandidate],
+    budget: i64,
+    rules: &RefreshRules,
+) -> Vec<RefreshProposal> {
+    let weights: Vec<f64> = candidates
+        .iter()
+        .map(|candidate| rules.weight(candidate))
+        .collect();
+    let total_weight: f64 = weights.iter().sum();
+
+    if total_weight <= 0.0 {
+        return Vec::new();
+    }
+
+    candidates
+        .iter()
+        .zip(weights)
+        .filter_map(|(candidate, weight)| {
+            let amount = (budget as f64 * weight / total_weight).floor() as i64;
+
+            (amount > 0).then(|| RefreshProposal {
+                holder: candidate.holder.name.clone(),
+                weight,
+                grant: Grant {
+                    amount,
+                    grant_date: rules.as_of,
+                    vesting_schedule: rules.vesting_schedule.clone(),
+                    termination_date: None,
+                    vesting_commencement_date: None,
+                    grant_type: rules.grant_type.clone(),
+                },
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{allocate_refresh_budget, LocalDate, RefreshCandidate, RefreshRules};
+    use crate::{Grant, GrantType, Holder, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_allocate_a_refresh_budget() {
+        let candidate = |name: &str, year, performance_tier| RefreshCandidate {
+            holder: Holder {
+                name: name.to_string(),
+                grants: vec![Grant::for_test(
+                    4_800,
+                    LocalDate::from_ymd(year, 1, 1),
+                    VestingScheduleConfiguration::monthly(12, 0.25, 48),
+                )],
+            },
+            start_date: LocalDate::from_ymd(year, 1, 1),
+            performance_tier,
+        };
+
+        let rules = RefreshRules {
+            as_of: LocalDate::from_ymd(2024, 1, 1),
+            tier_weights: vec![0.0, 1.0, 2.0],
+            tenure_weight_per_year: 0.25,
+            unvested_target: 4_000,
+            grant_type: GrantType::default(),
+            vesting_schedule: VestingScheduleConfiguration::monthly(0, 0.0, 48),
+        };
+
+        /*
+         * - Alice: top tier, 4 years in and fully vested, weighs 2 * 2 = 4
+         * - Bob: middle tier, 2 years in with 2400 unvested, weighs 1 * 1.5 * 0.4 = 0.6
+         * - Carol: lowest tier, weighs nothing
+         */
+
+        let proposals = allocate_refresh_budget(
+            &[
+                candidate("Alice", 2020, 2),
+                candidate("Bob", 2022, 1),
+                candidate("Carol", 2022, 0),
+            ],
+            10_000,
+            &rules,
+        );
+
+        assert_eq!(proposals.len(), 2);
+        assert_eq!(proposals[0].holder, "Alice");
+        assert_eq!(proposals[0].grant.amount, 8_695);
+        assert_eq!(proposals[1].holder, "Bob");
+        assert_eq!(proposals[1].grant.amount, 1_304);
+        assert_eq!(
+            proposals[1].grant.grant_date,
+            LocalDate::from_ymd(2024, 1, 1)
+        );
+    }
+}
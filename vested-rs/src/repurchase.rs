@@ -0,0 +1,74 @@
+use chrono::Duration;
+
+use crate::{Grant, LocalDate, Money, Price};
+
+/// What the company needs to exercise its right to buy back unvested restricted stock,
+/// such as reverse-vested founder shares or early-exercised options.
+#[derive(Debug, PartialEq)]
+pub struct RepurchaseNotice {
+    pub termination_date: LocalDate,
+    pub vested_shares: i64,
+    pub repurchasable_shares: i64,
+    /// The price originally paid per share, which the company pays back.
+    pub price_per_share: Price,
+    pub repurchase_cost: Money,
+    /// Last day the company can exercise its repurchase right.
+    pub repurchase_deadline: LocalDate,
+}
+
+impl Grant {
+    /// Calculates the unvested shares the company may repurchase at the original price
+    /// when the holder leaves on `termination_date`.
+    pub fn repurchase_notice(
+        &self,
+        termination_date: LocalDate,
+        price_per_share: Price,
+        repurchase_window_days: i64,
+    ) -> RepurchaseNotice {
+        let vested_shares = self.calculate_vested_shares(termination_date);
+        let repurchasable_shares = self.amount - vested_shares;
+
+        RepurchaseNotice {
+            termination_date,
+            vested_shares,
+            repurchasable_shares,
+            price_per_share,
+            repurchase_cost: price_per_share * repurchasable_shares,
+            repurchase_deadline: termination_date + Duration::days(repurchase_window_days),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LocalDate, RepurchaseNotice};
+    use crate::{Currency, Grant, Money, Price, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_calculate_repurchasable_shares_on_termination() {
+        let grant = Grant::for_test(
+            4_000_000,
+            LocalDate::from_ymd(2020, 1, 1),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        );
+
+        /*
+         * A founder price of a hundredth of a cent, which would round to nothing if
+         * kept in whole cents.
+         */
+
+        let price_per_share = Price::from_major_units(0.0001, Currency::Usd);
+
+        assert_eq!(
+            grant.repurchase_notice(LocalDate::from_ymd(2022, 1, 15), price_per_share, 90),
+            RepurchaseNotice {
+                termination_date: LocalDate::from_ymd(2022, 1, 15),
+                vested_shares: 2_000_000,
+                repurchasable_shares: 2_000_000,
+                price_per_share,
+                repurchase_cost: Money::from_major_units(200.0, Currency::Usd),
+                repurchase_deadline: LocalDate::from_ymd(2022, 4, 15),
+            }
+        );
+    }
+}
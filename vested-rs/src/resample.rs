@@ -0,0 +1,198 @@
+use chrono::Duration;
+use chronoutil::{DateRule, RelativeDuration};
+
+use crate::{LocalDate, VestingSchedule};
+
+/// Spacing of the points of a resampled schedule.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Granularity {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// How the vested amount is filled in between the dates of a schedule's periods.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interpolation {
+    /// Holds the amount vested on the last period until the next one.
+    Step,
+    /// Vests evenly by day between periods.
+    Linear,
+}
+
+/// The vested amount on one date of a resampled schedule.
+#[derive(Debug, PartialEq, PartialOrd)]
+pub struct SchedulePoint {
+    pub date: LocalDate,
+    pub vested_amount: f64,
+}
+
+impl Granularity {
+    fn step(&self) -> RelativeDuration {
+        match self {
+            Granularity::Daily => RelativeDuration::from(Duration::days(1)),
+            Granularity::Weekly => RelativeDuration::from(Duration::weeks(1)),
+            Granularity::Monthly => RelativeDuration::months(1),
+        }
+    }
+}
+
+impl VestingSchedule {
+    /// Calculates the vested amount on the given date, filling in between periods.
+    pub fn vested_amount_on(&self, date: LocalDate, interpolation: Interpolation) -> f64 {
+        let index = self.periods.partition_point(|period| period.date <= date);
+
+        let previous = match index {
+            0 => return 0.0,
+            index => &self.periods[index - 1],
+        };
+        let previous_amount = previous.cumulative_vested_amount as f64;
+
+        match (interpolation, self.periods.get(index)) {
+            (Interpolation::Linear, Some(next)) => {
+                let elapsed = (date - previous.date).num_days() as f64;
+                let length = (next.date - previous.date).num_days() as f64;
+
+                previous_amount
+                    + (next.cumulative_vested_amount as f64 - previous_amount) * elapsed / length
+            }
+            _ => previous_amount,
+        }
+    }
+
+    /// Converts the schedule into evenly spaced points from its first to its last date,
+    /// e.g. for charting. Monthly points fall on the same day of each month as the
+    /// schedule's first date.
+    pub fn resample(
+        &self,
+        granularity: Granularity,
+        interpolation: Interpolation,
+    ) -> Vec<SchedulePoint> {
+        self.resample_between(self.from_date, self.to_date, granularity, interpolation)
+    }
+
+    /// Converts the schedule into evenly spaced points between the two dates, inclusive.
+    pub fn resample_between(
+        &self,
+        from_date: LocalDate,
+        to_date: LocalDate,
+        granularity: Granularity,
+        interpolation: Interpolation,
+    ) -> Vec<SchedulePoint> {
+        DateRule::new(from_date.naive_date(), granularity.step())
+            .map(LocalDate::from)
+            .take_while(|date| *date <= to_date)
+            .map(|date| SchedulePoint {
+                date,
+                vested_amount: self.vested_amount_on(date, interpolation),
+            })
+            .collect()
+    }
+
+    /// Calculates the time-weighted average vested balance from `from_date` up to but
+    /// not including `to_date`, weighing each day's balance equally. Returns `None` for
+    /// an empty window.
+    pub fn average_vested_amount(
+        &self,
+        from_date: LocalDate,
+        to_date: LocalDate,
+        interpolation: Interpolation,
+    ) -> Option<f64> {
+        if to_date <= from_date {
+            return None;
+        }
+
+        let points = self.resample_between(
+            from_date,
+            to_date - Duration::days(1),
+            Granularity::Daily,
+            interpolation,
+        );
+        let total: f64 = points.iter().map(|point| point.vested_amount).sum();
+
+        Some(total / points.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::{Granularity, Interpolation, LocalDate};
+    use crate::{Grant, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_resample_a_schedule() {
+        let grant = Grant::for_test(
+            1_200,
+            LocalDate::from_ymd(2021, 1, 1),
+            VestingScheduleConfiguration::monthly(0, 0.0, 12),
+        );
+        let vesting_schedule = grant.calculate_vesting_schedule();
+
+        let weekly = vesting_schedule.resample(Granularity::Weekly, Interpolation::Step);
+
+        assert_eq!(weekly.len(), 53);
+        assert_eq!(weekly[1].date, LocalDate::from_ymd(2021, 1, 8));
+        assert_eq!(weekly[1].vested_amount, 0.0);
+        assert_eq!(weekly[5].vested_amount, 100.0);
+
+        let daily = vesting_schedule.resample(Granularity::Daily, Interpolation::Linear);
+
+        /*
+         * 100 shares vest over the 31 days of January.
+         */
+
+        assert_eq!(daily.len(), 366);
+        assert_relative_eq!(daily[10].vested_amount, 1_000.0 / 31.0);
+        assert_eq!(daily.last().unwrap().vested_amount, 1_200.0);
+
+        let monthly = vesting_schedule.resample(Granularity::Monthly, Interpolation::Linear);
+
+        assert_eq!(monthly.len(), 13);
+        assert_eq!(monthly[6].vested_amount, 600.0);
+    }
+
+    #[test]
+    fn it_can_average_the_vested_balance_over_a_window() {
+        let grant = Grant::for_test(
+            1_200,
+            LocalDate::from_ymd(2021, 1, 1),
+            VestingScheduleConfiguration::monthly(0, 0.0, 12),
+        );
+        let vesting_schedule = grant.calculate_vesting_schedule();
+
+        /*
+         * 100 shares vested for the 28 days of February 2021, 200 for the 31 days of March.
+         */
+
+        assert_relative_eq!(
+            vesting_schedule
+                .average_vested_amount(
+                    LocalDate::from_ymd(2021, 2, 1),
+                    LocalDate::from_ymd(2021, 4, 1),
+                    Interpolation::Step
+                )
+                .unwrap(),
+            (100.0 * 28.0 + 200.0 * 31.0) / 59.0
+        );
+        assert_relative_eq!(
+            vesting_schedule
+                .average_vested_amount(
+                    LocalDate::from_ymd(2021, 1, 1),
+                    LocalDate::from_ymd(2021, 1, 3),
+                    Interpolation::Linear
+                )
+                .unwrap(),
+            50.0 / 31.0
+        );
+        assert_eq!(
+            vesting_schedule.average_vested_amount(
+                LocalDate::from_ymd(2021, 2, 1),
+                LocalDate::from_ymd(2021, 2, 1),
+                Interpolation::Step
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use chrono::Duration;
+
+use crate::{Grant, LocalDate};
+
+/// The grants held by a single employee.
+pub struct Holder {
+    pub name: String,
+    pub grants: Vec<Grant>,
+}
+
+/// What counts as a retention risk within the window following `as_of`.
+pub struct RetentionCriteria {
+    pub as_of: LocalDate,
+    pub window_days: i64,
+    /// Single vests of at least this many shares within the window are flagged.
+    pub large_tranche_amount: i64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RetentionEntry {
+    pub holder: String,
+    /// Date the holder's last grant fully vests, if that falls within the window.
+    pub fully_vested_date: Option<LocalDate>,
+    /// Largest single vest within the window, if it meets the large tranche amount.
+    pub large_tranche_amount: Option<i64>,
+    pub unvested_amount: i64,
+}
+
+/// Lists holders who fully vest or have a large tranche vesting within the window,
+/// sorted by the amount they still have unvested (largest first).
+pub fn retention_report(holders: &[Holder], criteria: &RetentionCriteria) -> Vec<RetentionEntry> {
+    let window_end = criteria.as_of + Duration::days(criteria.window_days);
+    let in_window = |date: LocalDate| date > criteria.as_of && date <= window_end;
+
+    let mut entries: Vec<RetentionEntry> = holders
+        .iter()
+        .filter_map(|holder| {
+            // Holders with a terminated grant have left and never fully vest.
+            let fully_vested_date = holder
+                .grants
+                .iter()
+                .map(Grant::fully_vested_date)
+                .collect::<Option<Vec<LocalDate>>>()
+                .and_then(|dates| dates.into_iter().max())
+                .filter(|date| in_window(*date));
+
+            let large_tranche_amount = holder
+                .grants
+                .iter()
+                .flat_map(|grant| grant.vest_events())
+                .filter(|event| in_window(event.date))
+                .map(|event| event.amount)
+                .max()
+                .filter(|amount| *amount >= criteria.large_tranche_amount);
+
+            if fully_vested_date.is_none() && large_tranche_amount.is_none() {
+                return None;
+            }
+
+            Some(RetentionEntry {
+                holder: holder.name.clone(),
+                fully_vested_date,
+                large_tranche_amount,
+                unvested_amount: holder
+                    .grants
+                    .iter()
+                    .map(|grant| grant.calculate_unvested_amount(criteria.as_of))
+                    .sum(),
+            })
+        })
+        .collect();
+
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.unvested_amount));
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{retention_report, Holder, LocalDate, RetentionCriteria, RetentionEntry};
+    use crate::{Grant, VestingScheduleConfiguration};
+
+    fn grant(amount: i64, year: i32, cliff: i32, length: i32) -> Grant {
+        Grant::for_test(
+            amount,
+            LocalDate::from_ymd(year, 2, 6),
+            VestingScheduleConfiguration::monthly(cliff, cliff as f32 / length as f32, length),
+        )
+    }
+
+    #[test]
+    fn it_can_report_upcoming_retention_risks() {
+        let holders = vec![
+            Holder {
+                name: String::from("Nearly done"),
+                grants: vec![grant(4_800, 2020, 12, 48)],
+            },
+            Holder {
+                name: String::from("Approaching cliff"),
+                grants: vec![grant(48_000, 2023, 12, 48)],
+            },
+            Holder {
+                name: String::from("Mid-schedule"),
+                grants: vec![grant(4_800, 2022, 12, 48)],
+            },
+            Holder {
+                name: String::from("Departed"),
+                grants: vec![{
+                    let mut grant = grant(4_800, 2021, 12, 48);
+                    grant.terminate(LocalDate::from_ymd(2024, 1, 31));
+                    grant
+                }],
+            },
+        ];
+
+        let report = retention_report(
+            &holders,
+            &RetentionCriteria {
+                as_of: LocalDate::from_ymd(2024, 1, 15),
+                window_days: 60,
+                large_tranche_amount: 1_000,
+            },
+        );
+
+        assert_eq!(
+            report,
+            vec![
+                RetentionEntry {
+                    holder: String::from("Approaching cliff"),
+                    fully_vested_date: None,
+                    large_tranche_amount: Some(12_000),
+                    unvested_amount: 48_000,
+                },
+                RetentionEntry {
+                    holder: String::from("Nearly done"),
+                    fully_vested_date: Some(LocalDate::from_ymd(2024, 2, 6)),
+                    large_tranche_amount: None,
+                    unvested_amount: 100,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use crate::{Holder, LocalDate};
+
+/// A holder included in a hypothetical reduction in force, along with the department
+/// their shares are reported under.
+pub struct RifEmployee {
+    pub department: String,
+    pub holder: Holder,
+}
+
+/// One department's share of a reduction in force.
+#[derive(Debug, PartialEq)]
+pub struct DepartmentImpact {
+    pub department: String,
+    pub headcount: usize,
+    /// Shares already vested on the RIF date, which the employees keep.
+    pub vested_amount: i64,
+    /// Unvested shares forfeited on the RIF date, which return to the pool.
+    pub returned_amount: i64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RifReport {
+    pub rif_date: LocalDate,
+    /// Impact on each department, sorted by department name.
+    pub departments: Vec<DepartmentImpact>,
+    pub vested_amount: i64,
+    pub returned_amount: i64,
+}
+
+/// Works out what terminating every listed employee on the RIF date would mean for the
+/// equity pool, without changing their grants. Grants already terminated before the
+/// RIF date return nothing further to the pool.
+pub fn rif_report(employees: &[RifEmployee], rif_date: LocalDate) -> RifReport {
+    let mut departments: BTreeMap<&str, DepartmentImpact> = BTreeMap::new();
+
+    for employee in employees {
+        let impact = departments
+            .entry(&employee.department)
+            .or_insert_with(|| DepartmentImpact {
+                department: employee.department.clone(),
+                headcount: 0,
+                vested_amount: 0,
+                returned_amount: 0,
+            });
+
+        impact.headcount += 1;
+
+        for grant in &employee.holder.grants {
+            impact.vested_amount += grant.calculate_vested_shares(rif_date);
+            impact.returned_amount += grant.calculate_unvested_amount(rif_date);
+        }
+    }
+
+    let departments: Vec<DepartmentImpact> = departments.into_values().collect();
+
+    RifReport {
+        rif_date,
+        vested_amount: departments.iter().map(|impact| impact.vested_amount).sum(),
+        returned_amount: departments
+            .iter()
+            .map(|impact| impact.returned_amount)
+            .sum(),
+        departments,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rif_report, DepartmentImpact, LocalDate, RifEmployee};
+    use crate::{Grant, Holder, VestingScheduleConfiguration};
+
+    fn employee(department: &str, grant_date: LocalDate, amount: i64, cliff: i32) -> RifEmployee {
+        RifEmployee {
+            department: department.to_string(),
+            holder: Holder {
+                name: String::from("Employee"),
+                grants: vec![Grant::for_test(
+                    amount,
+                    grant_date,
+                    VestingScheduleConfiguration::monthly(cliff, cliff as f32 / 48.0, 48),
+                )],
+            },
+        }
+    }
+
+    #[test]
+    fn it_can_report_the_impact_of_a_reduction_in_force() {
+        let employees = vec![
+            employee("Sales", LocalDate::from_ymd(2021, 8, 6), 4_800, 12),
+            employee("Engineering", LocalDate::from_ymd(2020, 2, 6), 4_800, 12),
+            employee("Engineering", LocalDate::from_ymd(2018, 2, 6), 1_200, 0),
+        ];
+
+        let report = rif_report(&employees, LocalDate::from_ymd(2022, 2, 15));
+
+        assert_eq!(
+            report.departments,
+            vec![
+                DepartmentImpact {
+                    department: String::from("Engineering"),
+                    headcount: 2,
+                    vested_amount: 3_600,
+                    returned_amount: 2_400,
+                },
+                DepartmentImpact {
+                    department: String::from("Sales"),
+                    headcount: 1,
+                    vested_amount: 0,
+                    returned_amount: 4_800,
+                },
+            ]
+        );
+        assert_eq!(report.vested_amount, 3_600);
+        assert_eq!(report.returned_amount, 7_200);
+    }
+}
@@ -0,0 +1,148 @@
+use std::time::Duration as StdDuration;
+
+use chrono::{Duration, TimeZone, Utc};
+
+use crate::{DeliveryError, Holder, LocalDate, NotificationSink};
+
+/// Sends each holder a reminder a fixed number of days before each of their vests,
+/// checking once a day.
+pub struct ReminderScheduler<Tz: TimeZone> {
+    holders: Vec<Holder>,
+    sinks: Vec<Box<dyn NotificationSink + Send>>,
+    lead_days: i64,
+    timezone: Tz,
+}
+
+impl<Tz: TimeZone> ReminderScheduler<Tz> {
+    /// Makes a scheduler that checks for vests at the start of each day in the
+    /// timezone, reminding holders the given number of days ahead.
+    pub fn new(holders: Vec<Holder>, lead_days: i64, timezone: Tz) -> ReminderScheduler<Tz> {
+        ReminderScheduler {
+            holders,
+            sinks: Vec::new(),
+            lead_days,
+            timezone,
+        }
+    }
+
+    pub fn add_sink<S: NotificationSink + Send + 'static>(&mut self, sink: S) {
+        self.sinks.push(Box::new(sink));
+    }
+
+    /// Delivers reminders for the vests `lead_days` after the given date to every sink,
+    /// returning the deliveries that failed. A failing sink doesn't stop the others.
+    pub fn run_once(&mut self, today: LocalDate) -> Vec<DeliveryError> {
+        let vest_date = today + Duration::days(self.lead_days);
+        let mut notifications: Vec<_> = self
+            .holders
+            .iter()
+            .flat_map(|holder| holder.upcoming_vest_notifications(vest_date, 0))
+            .collect();
+        notifications.sort_by_key(|notification| notification.vest_date);
+
+        let mut errors = Vec::new();
+
+        for sink in self.sinks.iter_mut() {
+            for notification in &notifications {
+                if let Err(error) = sink.deliver(notification) {
+                    errors.push(error);
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Time left until the start of the next day in the timezone.
+    fn until_tomorrow(&self) -> StdDuration {
+        let tomorrow = LocalDate::today_in(&self.timezone) + Duration::days(1);
+
+        tomorrow
+            .start_of_day_in(&self.timezone)
+            .and_then(|start| (start.with_timezone(&Utc) - Utc::now()).to_std().ok())
+            .unwrap_or(StdDuration::from_secs(60 * 60))
+    }
+
+    /// Runs forever, delivering the day's reminders now and then at the start of
+    /// every following day. Failed deliveries are passed to `on_error`, e.g. to log
+    /// them, and aren't retried.
+    pub async fn run<F: FnMut(DeliveryError)>(mut self, mut on_error: F) {
+        loop {
+            let today = LocalDate::today_in(&self.timezone);
+
+            for error in self.run_once(today) {
+                on_error(error);
+            }
+
+            tokio::time::sleep(self.until_tomorrow()).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    use chrono::Utc;
+
+    use super::{LocalDate, ReminderScheduler};
+    use crate::{
+        DeliveryError, Grant, Holder, Notification, NotificationSink, VestingScheduleConfiguration,
+        WriterSink,
+    };
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    struct FailingSink;
+
+    impl NotificationSink for FailingSink {
+        fn deliver(&mut self, _notification: &Notification) -> Result<(), DeliveryError> {
+            Err(DeliveryError::Io(io::ErrorKind::BrokenPipe.into()))
+        }
+    }
+
+    #[test]
+    fn it_can_deliver_reminders_ahead_of_vests() {
+        let holder = Holder {
+            name: "Jane Doe".to_string(),
+            grants: vec![Grant::for_test(
+                4_800,
+                LocalDate::from_ymd(2021, 1, 15),
+                VestingScheduleConfiguration::monthly(12, 0.25, 48),
+            )],
+        };
+        let buffer = SharedBuffer::default();
+        let mut scheduler = ReminderScheduler::new(vec![holder], 3, Utc);
+        scheduler.add_sink(FailingSink);
+        scheduler.add_sink(WriterSink::new(buffer.clone()));
+
+        assert_eq!(
+            scheduler.run_once(LocalDate::from_ymd(2022, 1, 11)).len(),
+            0
+        );
+        assert_eq!(
+            scheduler.run_once(LocalDate::from_ymd(2022, 1, 12)).len(),
+            1
+        );
+        assert_eq!(
+            scheduler.run_once(LocalDate::from_ymd(2022, 1, 13)).len(),
+            0
+        );
+        assert_eq!(
+            String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap(),
+            "Jane Doe: 1,200 shares vest on 2022-01-15\n"
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use chrono::Duration;
+
+use crate::{Grant, HolidayCalendar, LocalDate};
+
+/// A trading window closure, e.g. the weeks around an earnings release. Both dates are inclusive.
+pub struct BlackoutWindow {
+    pub start: LocalDate,
+    pub end: LocalDate,
+}
+
+/// Everything that stops vested shares from being sold on a given day.
+#[derive(Default)]
+pub struct SaleRestrictions {
+    /// Market holidays, in addition to weekends.
+    pub holidays: Vec<LocalDate>,
+    pub blackout_windows: Vec<BlackoutWindow>,
+    /// Sales are restricted before this date, e.g. an IPO lockup.
+    pub lockup_until: Option<LocalDate>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SellableVest {
+    pub vest_date: LocalDate,
+    /// First day the vested shares can actually be sold.
+    pub sellable_date: LocalDate,
+    pub amount: i64,
+}
+
+impl SaleRestrictions {
+    /// Checks if the market is open on the given date.
+    pub fn is_trading_day(&self, date: LocalDate) -> bool {
+        self.is_business_day(date)
+    }
+
+    fn is_blacked_out(&self, date: LocalDate) -> bool {
+        self.blackout_windows
+            .iter()
+            .any(|window| date >= window.start && date <= window.end)
+    }
+
+    /// Returns the first date on or after the given date that shares can be sold.
+    pub fn sellable_on(&self, date: LocalDate) -> LocalDate {
+        let mut sellable_date = match self.lockup_until {
+            Some(lockup_until) if lockup_until > date => lockup_until,
+            _ => date,
+        };
+
+        while !self.is_trading_day(sellable_date) || self.is_blacked_out(sellable_date) {
+            sellable_date = sellable_date + Duration::days(1);
+        }
+
+        sellable_date
+    }
+}
+
+impl Grant {
+    /// Lists each vest along with the first date its shares can be sold.
+    pub fn sellable_vests(&self, restrictions: &SaleRestrictions) -> Vec<SellableVest> {
+        self.vest_events()
+            .into_iter()
+            .map(|event| SellableVest {
+                vest_date: event.date,
+                sellable_date: restrictions.sellable_on(event.date),
+                amount: event.amount,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BlackoutWindow, LocalDate, SaleRestrictions, SellableVest};
+    use crate::{Grant, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_calculate_when_vested_shares_are_sellable() {
+        let grant = Grant::for_test(
+            300,
+            LocalDate::from_ymd(2021, 9, 4),
+            VestingScheduleConfiguration::monthly(0, 0.0, 3),
+        );
+
+        let restrictions = SaleRestrictions {
+            holidays: vec![LocalDate::from_ymd(2021, 10, 4)],
+            blackout_windows: vec![BlackoutWindow {
+                start: LocalDate::from_ymd(2021, 11, 1),
+                end: LocalDate::from_ymd(2021, 11, 10),
+            }],
+            lockup_until: None,
+        };
+
+        /*
+         * - 2021/10/4: Monday holiday, sellable on Tuesday 10/5
+         * - 2021/11/4: inside the blackout window, sellable on Thursday 11/11
+         * - 2021/12/4: Saturday, sellable on Monday 12/6
+         */
+
+        assert_eq!(
+            grant.sellable_vests(&restrictions),
+            vec![
+                SellableVest {
+                    vest_date: LocalDate::from_ymd(2021, 10, 4),
+                    sellable_date: LocalDate::from_ymd(2021, 10, 5),
+                    amount: 100,
+                },
+                SellableVest {
+                    vest_date: LocalDate::from_ymd(2021, 11, 4),
+                    sellable_date: LocalDate::from_ymd(2021, 11, 11),
+                    amount: 100,
+                },
+                SellableVest {
+                    vest_date: LocalDate::from_ymd(2021, 12, 4),
+                    sellable_date: LocalDate::from_ymd(2021, 12, 6),
+                    amount: 100,
+                },
+            ]
+        );
+
+        let locked_up = SaleRestrictions {
+            lockup_until: Some(LocalDate::from_ymd(2022, 3, 1)),
+            ..SaleRestrictions::default()
+        };
+
+        assert_eq!(
+            locked_up.sellable_on(LocalDate::from_ymd(2021, 12, 4)),
+            LocalDate::from_ymd(2022, 3, 1)
+        );
+    }
+}
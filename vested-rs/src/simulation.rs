@@ -0,0 +1,197 @@
+use chronoutil::DateRule;
+
+use crate::{Grant, LocalDate, Money, Price};
+
+/// Assumptions driving a Monte Carlo simulation of a portfolio's value.
+pub struct SimulationParameters {
+    /// Share price on the first simulated date. Values are reported in its currency.
+    pub starting_price: Price,
+    /// Expected annual return of the share price, e.g. 0.1 for 10%.
+    pub annual_drift: f32,
+    /// Annualized standard deviation of the share price's return.
+    pub annual_volatility: f32,
+    /// Chance (0.0 - 1.0) that the company exits in any given month. The share price
+    /// is locked in at the exit price from then on.
+    pub monthly_exit_probability: f32,
+    /// Flat tax rate (0.0 - 1.0) applied to the value of vested shares.
+    pub tax_rate: f32,
+    pub trials: usize,
+    /// Seeds the random number generator, so the same parameters always produce the
+    /// same bands.
+    pub seed: u64,
+}
+
+/// Spread of simulated after-tax values of the vested shares on a single date.
+#[derive(Debug, PartialEq, PartialOrd)]
+pub struct PercentileBand {
+    pub date: LocalDate,
+    pub p10: Money,
+    pub p50: Money,
+    pub p90: Money,
+}
+
+/// SplitMix64, which is small and good enough for sampling price paths and
+/// anonymizing datasets.
+pub(crate) struct Rng(pub(crate) u64);
+
+impl Rng {
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniformly distributed in (0.0, 1.0].
+    pub(crate) fn next_uniform(&mut self) -> f64 {
+        ((self.next_u64() >> 11) + 1) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal sample using the Box-Muller transform.
+    fn next_normal(&mut self) -> f64 {
+        let radius = (-2.0 * self.next_uniform().ln()).sqrt();
+        radius * (2.0 * std::f64::consts::PI * self.next_uniform()).cos()
+    }
+}
+
+/// Returns the value at the given percentile of already sorted values, using the
+/// nearest-rank method.
+fn percentile(sorted_values: &[Money], percentile: f32) -> Money {
+    let rank = (percentile * sorted_values.len() as f32).ceil() as usize;
+    sorted_values[rank.clamp(1, sorted_values.len()) - 1]
+}
+
+/// Simulates the after-tax value of the portfolio's vested shares at each of the next
+/// `months` monthly dates, sampling the share price as a geometric Brownian motion
+/// along with the month the company exits, if at all.
+pub fn simulate_outcomes(
+    grants: &[Grant],
+    from_date: LocalDate,
+    months: i32,
+    parameters: &SimulationParameters,
+) -> Vec<PercentileBand> {
+    let dates: Vec<LocalDate> = DateRule::monthly(from_date.naive_date())
+        .with_count(months.max(0) as usize + 1)
+        .map(LocalDate::from)
+        .collect();
+
+    let vested_shares: Vec<f64> = dates
+        .iter()
+        .map(|date| {
+            grants
+                .iter()
+                .map(|grant| grant.calculate_vested_shares(*date) as f64)
+                .sum()
+        })
+        .collect();
+
+    let time_step = 1.0 / 12.0;
+    let drift = parameters.annual_drift as f64;
+    let volatility = parameters.annual_volatility as f64;
+    let after_tax = 1.0 - parameters.tax_rate as f64;
+    let currency = parameters.starting_price.currency();
+
+    let mut rng = Rng(parameters.seed);
+    let mut values = vec![Vec::with_capacity(parameters.trials); dates.len()];
+
+    for _ in 0..parameters.trials {
+        let mut price = parameters.starting_price.to_major_units();
+        let mut exited = false;
+
+        for (month, shares) in vested_shares.iter().enumerate() {
+            if month > 0 && !exited {
+                price *= ((drift - volatility * volatility / 2.0) * time_step
+                    + volatility * time_step.sqrt() * rng.next_normal())
+                .exp();
+                exited = rng.next_uniform() <= parameters.monthly_exit_probability as f64;
+            }
+
+            values[month].push(Money::from_major_units(
+                shares * price * after_tax,
+                currency,
+            ));
+        }
+    }
+
+    dates
+        .into_iter()
+        .zip(values)
+        .map(|(date, mut values)| {
+            values.sort_by_key(Money::minor_units);
+
+            if values.is_empty() {
+                return PercentileBand {
+                    date,
+                    p10: Money::zero(currency),
+                    p50: Money::zero(currency),
+                    p90: Money::zero(currency),
+                };
+            }
+
+            PercentileBand {
+                date,
+                p10: percentile(&values, 0.1),
+                p50: percentile(&values, 0.5),
+                p90: percentile(&values, 0.9),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::{simulate_outcomes, LocalDate, Money, PercentileBand, SimulationParameters};
+    use crate::{Currency, Grant, Price, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_simulate_percentile_bands_of_portfolio_value() {
+        let grants = vec![Grant::for_test(
+            1_200,
+            LocalDate::from_ymd(2021, 1, 1),
+            VestingScheduleConfiguration::monthly(0, 0.0, 12),
+        )];
+
+        let flat = SimulationParameters {
+            starting_price: Price::from_major_units(10.0, Currency::Usd),
+            annual_drift: 0.0,
+            annual_volatility: 0.0,
+            monthly_exit_probability: 0.0,
+            tax_rate: 0.25,
+            trials: 100,
+            seed: 7,
+        };
+
+        let bands = simulate_outcomes(&grants, LocalDate::from_ymd(2021, 1, 1), 12, &flat);
+
+        assert_eq!(bands.len(), 13);
+        assert_eq!(
+            bands[6],
+            PercentileBand {
+                date: LocalDate::from_ymd(2021, 7, 1),
+                p10: Money::from_major_units(4500.0, Currency::Usd),
+                p50: Money::from_major_units(4500.0, Currency::Usd),
+                p90: Money::from_major_units(4500.0, Currency::Usd),
+            }
+        );
+
+        let volatile = SimulationParameters {
+            annual_volatility: 0.6,
+            monthly_exit_probability: 0.05,
+            ..flat
+        };
+
+        let bands = simulate_outcomes(&grants, LocalDate::from_ymd(2021, 1, 1), 12, &volatile);
+        let last = bands.last().unwrap();
+
+        assert!(last.p10 < last.p50 && last.p50 < last.p90);
+        assert_relative_eq!(last.p50.to_major_units(), 9000.0, max_relative = 0.5);
+        assert_eq!(
+            bands,
+            simulate_outcomes(&grants, LocalDate::from_ymd(2021, 1, 1), 12, &volatile)
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use crate::VestingSchedule;
+
+impl VestingSchedule {
+    /// Plots the cumulative shares vested over time as a step line, with x from 0 to
+    /// `width` spanning the first to the last period and y from `height` (nothing
+    /// vested) to 0 (the whole grant vested, including forfeited shares).
+    fn sparkline_points(&self, width: f64, height: f64) -> Vec<(f64, f64)> {
+        let (first, last) = match (self.periods.first(), self.periods.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return vec![(0.0, height), (width, height)],
+        };
+
+        let span_days = (last.date - first.date).num_days().max(1) as f64;
+        let total = (last.cumulative_vested_amount + self.forfeited_amount).max(1) as f64;
+        let mut points = vec![(0.0, height)];
+
+        for period in &self.periods {
+            let x = (period.date - first.date).num_days() as f64 / span_days * width;
+            let y = height - period.cumulative_vested_amount as f64 / total * height;
+            let previous_y = points.last().map_or(height, |&(_, y)| y);
+
+            if y != previous_y {
+                points.push((x, previous_y));
+                points.push((x, y));
+            }
+        }
+
+        let &(last_x, last_y) = points.last().unwrap();
+
+        if last_x < width {
+            points.push((width, last_y));
+        }
+
+        points
+    }
+
+    /// Renders the cumulative vesting curve as a small standalone SVG, for embedding
+    /// in HTML reports. The line is drawn in `currentColor`, so it takes the color of
+    /// the surrounding text.
+    pub fn to_sparkline_svg(&self, width: u32, height: u32) -> String {
+        let points: Vec<String> = self
+            .sparkline_points(width as f64, height as f64)
+            .into_iter()
+            .map(|(x, y)| format!("{:.1},{:.1}", x, y))
+            .collect();
+
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\">\
+             <polyline fill=\"none\" stroke=\"currentColor\" stroke-width=\"1\" points=\"{}\"/>\
+             </svg>",
+            points.join(" ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Grant, LocalDate, VestingInterval, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_render_a_sparkline() {
+        let grant = Grant::for_test(
+            400,
+            LocalDate::from_ymd(2021, 1, 1),
+            VestingScheduleConfiguration::without_cliff(VestingInterval::Annual, 4),
+        );
+
+        /*
+         * 2021/1/1 to 2025/1/1 is 1,461 days, with 2024 a leap year.
+         */
+
+        assert_eq!(
+            grant.calculate_vesting_schedule().to_sparkline_svg(100, 20),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\" height=\"20\" \
+             viewBox=\"0 0 100 20\">\
+             <polyline fill=\"none\" stroke=\"currentColor\" stroke-width=\"1\" \
+             points=\"0.0,20.0 25.0,20.0 25.0,15.0 50.0,15.0 50.0,10.0 74.9,10.0 74.9,5.0 \
+             100.0,5.0 100.0,0.0\"/>\
+             </svg>"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt;
+
+use crate::money::group_thousands;
+use crate::VestingSchedule;
+
+const HEADERS: [&str; 4] = ["Date", "Vested this period", "Cumulative", "% vested"];
+
+impl VestingSchedule {
+    /// Formats each period as table cells: the date, the shares vesting on it, the
+    /// cumulative shares vested and the percentage of the grant vested, counting
+    /// forfeited shares as never vesting.
+    fn table_rows(&self) -> Vec<[String; 4]> {
+        let total = self
+            .periods
+            .last()
+            .map_or(0, |period| period.cumulative_vested_amount)
+            + self.forfeited_amount;
+
+        self.periods
+            .iter()
+            .map(|period| {
+                let percent_vested = if total == 0 {
+                    0.0
+                } else {
+                    period.cumulative_vested_amount as f64 / total as f64 * 100.0
+                };
+
+                [
+                    period.date.to_string(),
+                    group_thousands(period.vested_this_period),
+                    group_thousands(period.cumulative_vested_amount),
+                    format!("{:.1}%", percent_vested),
+                ]
+            })
+            .collect()
+    }
+
+    fn column_widths(rows: &[[String; 4]]) -> [usize; 4] {
+        let mut widths = HEADERS.map(str::len);
+
+        for row in rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+
+        widths
+    }
+
+    /// Renders the schedule as a Markdown table with aligned columns, readable both
+    /// rendered and as plain text.
+    pub fn to_markdown_table(&self) -> String {
+        let rows = self.table_rows();
+        let [date, this_period, cumulative, percent] = VestingSchedule::column_widths(&rows);
+
+        let mut table = format!(
+            "| {:<date$} | {:>this_period$} | {:>cumulative$} | {:>percent$} |\n",
+            HEADERS[0], HEADERS[1], HEADERS[2], HEADERS[3]
+        );
+        table += &format!(
+            "| {} | {}: | {}: | {}: |\n",
+            "-".repeat(date),
+            "-".repeat(this_period - 1),
+            "-".repeat(cumulative - 1),
+            "-".repeat(percent - 1)
+        );
+
+        for row in rows {
+            table += &format!(
+                "| {:<date$} | {:>this_period$} | {:>cumulative$} | {:>percent$} |\n",
+                row[0], row[1], row[2], row[3]
+            );
+        }
+
+        table
+    }
+}
+
+impl fmt::Display for VestingSchedule {
+    /// Formats the schedule as a plain text table, one row per period.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows = self.table_rows();
+        let [date, this_period, cumulative, percent] = VestingSchedule::column_widths(&rows);
+
+        writeln!(
+            f,
+            "{:<date$}  {:>this_period$}  {:>cumulative$}  {:>percent$}",
+            HEADERS[0], HEADERS[1], HEADERS[2], HEADERS[3]
+        )?;
+
+        for row in rows {
+            writeln!(
+                f,
+                "{:<date$}  {:>this_period$}  {:>cumulative$}  {:>percent$}",
+                row[0], row[1], row[2], row[3]
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Grant, LocalDate, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_render_a_schedule_as_a_table() {
+        let grant = Grant::for_test(
+            3_000,
+            LocalDate::from_ymd(2021, 1, 15),
+            VestingScheduleConfiguration::monthly(0, 0.0, 3),
+        );
+        let schedule = grant.calculate_vesting_schedule();
+
+        assert_eq!(
+            schedule.to_markdown_table(),
+            "| Date       | Vested this period | Cumulative | % vested |\n\
+             | ---------- | -----------------: | ---------: | -------: |\n\
+             | 2021-01-15 |                  0 |          0 |     0.0% |\n\
+             | 2021-02-15 |              1,000 |      1,000 |    33.3% |\n\
+             | 2021-03-15 |              1,000 |      2,000 |    66.7% |\n\
+             | 2021-04-15 |              1,000 |      3,000 |   100.0% |\n"
+        );
+        assert_eq!(
+            schedule.to_string(),
+            "Date        Vested this period  Cumulative  % vested\n\
+             2021-01-15                   0           0      0.0%\n\
+             2021-02-15               1,000       1,000     33.3%\n\
+             2021-03-15               1,000       2,000     66.7%\n\
+             2021-04-15               1,000       3,000    100.0%\n"
+        );
+    }
+}
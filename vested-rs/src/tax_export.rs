@@ -0,0 +1,323 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{
+    EightyThreeBElection, GrantKind, GrantLedger, GrantType, IncomeCharacter, IncomeEventKind,
+    LocalDate, Money, Price, ValuationProvider,
+};
+
+/// Shares acquired by a vest or exercise, along with what a later sale of them is
+/// taxed against.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaxLot {
+    pub grant_kind: GrantKind,
+    pub event: IncomeEventKind,
+    /// Date the holding period starts: the vest date, or the exercise date for options
+    /// and shares covered by an 83(b) election.
+    pub date_acquired: LocalDate,
+    pub shares: i64,
+    /// Fair market value per share on the acquisition date.
+    pub fair_market_value: Price,
+    /// Price paid per share, the strike price or restricted stock purchase price.
+    pub price_paid: Price,
+    /// `SupplementalWages` or `AmtPreference`, the income recognized on acquisition.
+    pub character: IncomeCharacter,
+}
+
+/// A column of a tax software export.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TaxLotField {
+    /// E.g. `100 sh RSU vest`.
+    Description,
+    DateAcquired,
+    Shares,
+    FairMarketValue,
+    PricePaid,
+    OrdinaryIncome,
+    AmtAdjustment,
+    CostBasis,
+    AmtCostBasis,
+    /// ISO 4217 code of the lot's currency.
+    Currency,
+    /// The same value on every row, e.g. a broker name expected by the import.
+    Constant(String),
+}
+
+/// Column order, headers and formatting of a tax software export.
+pub struct TaxExportLayout {
+    /// Header and field of each column, in order.
+    pub columns: Vec<(String, TaxLotField)>,
+    pub delimiter: char,
+    /// strftime-style format used for dates.
+    pub date_format: String,
+    pub include_header: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TaxExportError {
+    /// No fair market value is available for a vest or exercise date.
+    MissingPrice(LocalDate),
+}
+
+impl fmt::Display for TaxExportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TaxExportError::MissingPrice(date) => {
+                write!(f, "no fair market value for {}", date)
+            }
+        }
+    }
+}
+
+impl Error for TaxExportError {}
+
+impl TaxLot {
+    fn spread(&self) -> Money {
+        let spread = (self.fair_market_value - self.price_paid) * self.shares;
+
+        if spread.minor_units() > 0 {
+            spread
+        } else {
+            Money::zero(spread.currency())
+        }
+    }
+
+    /// Income reported as wages on acquisition, the spread over the price paid.
+    pub fn ordinary_income(&self) -> Money {
+        match self.character {
+            IncomeCharacter::SupplementalWages => self.spread(),
+            _ => Money::zero(self.price_paid.currency()),
+        }
+    }
+
+    /// The spread on an ISO exercise added to income for the AMT.
+    pub fn amt_adjustment(&self) -> Money {
+        match self.character {
+            IncomeCharacter::AmtPreference => self.spread(),
+            _ => Money::zero(self.price_paid.currency()),
+        }
+    }
+
+    /// Regular tax basis: the price paid plus the income already taxed as wages.
+    pub fn cost_basis(&self) -> Money {
+        self.price_paid * self.shares + self.ordinary_income()
+    }
+
+    /// Basis for the AMT, which also includes the ISO spread.
+    pub fn amt_cost_basis(&self) -> Money {
+        self.cost_basis() + self.amt_adjustment()
+    }
+
+    pub fn description(&self) -> String {
+        let kind = match self.grant_kind {
+            GrantKind::Iso => "ISO",
+            GrantKind::Nso => "NSO",
+            GrantKind::Sar => "SAR",
+            GrantKind::Rsu => "RSU",
+            GrantKind::Rsa => "RSA",
+        };
+        let event = match self.event {
+            IncomeEventKind::Vest => "vest",
+            IncomeEventKind::Exercise => "exercise",
+        };
+
+        format!("{} sh {} {}", self.shares, kind, event)
+    }
+}
+
+impl GrantLedger {
+    /// Lists the lots acquired through the grant's taxable vests and exercises, valuing
+    /// each at the fair market value on its date. Restricted stock covered by an 83(b)
+    /// election is a single lot acquired on the grant date at the election's value.
+    pub fn calculate_tax_lots<V: ValuationProvider>(
+        &self,
+        elections: &[EightyThreeBElection],
+        provider: &V,
+    ) -> Result<Vec<TaxLot>, TaxExportError> {
+        let grant_kind = self.grant.grant_type.kind();
+        let mut lots = Vec::new();
+
+        if let GrantType::Rsa { purchase_price } = self.grant.grant_type {
+            if let Some(election) = elections.iter().find(|election| {
+                election.transfer_date == self.grant.grant_date && election.is_effective()
+            }) {
+                lots.push(TaxLot {
+                    grant_kind,
+                    event: IncomeEventKind::Vest,
+                    date_acquired: election.transfer_date,
+                    shares: election.shares,
+                    fair_market_value: election.fair_market_value,
+                    price_paid: purchase_price,
+                    character: IncomeCharacter::SupplementalWages,
+                });
+            }
+        }
+
+        for event in self.calculate_income_events(elections) {
+            let character = match event.character {
+                Some(IncomeCharacter::CapitalGain) | None => continue,
+                Some(character) => character,
+            };
+            let fair_market_value = provider
+                .fmv_on(event.date)
+                .ok_or(TaxExportError::MissingPrice(event.date))?;
+            let price_paid = match self.grant.grant_type {
+                GrantType::Iso { strike_price }
+                | GrantType::Nso { strike_price }
+                | GrantType::Sar { strike_price } => strike_price,
+                GrantType::Rsa { purchase_price } => purchase_price,
+                GrantType::Rsu { .. } => Price::zero(fair_market_value.currency()),
+            };
+
+            lots.push(TaxLot {
+                grant_kind,
+                event: event.kind,
+                date_acquired: event.date,
+                shares: event.shares,
+                fair_market_value,
+                price_paid,
+                character,
+            });
+        }
+
+        Ok(lots)
+    }
+}
+
+impl Default for TaxExportLayout {
+    /// The columns most tax software asks for when importing equity compensation, with
+    /// amounts in major units.
+    fn default() -> TaxExportLayout {
+        TaxExportLayout {
+            columns: vec![
+                ("Description".to_string(), TaxLotField::Description),
+                ("Date Acquired".to_string(), TaxLotField::DateAcquired),
+                ("Quantity".to_string(), TaxLotField::Shares),
+                ("Ordinary Income".to_string(), TaxLotField::OrdinaryIncome),
+                ("AMT Adjustment".to_string(), TaxLotField::AmtAdjustment),
+                ("Cost Basis".to_string(), TaxLotField::CostBasis),
+                ("AMT Cost Basis".to_string(), TaxLotField::AmtCostBasis),
+            ],
+            delimiter: ',',
+            date_format: "%m/%d/%Y".to_string(),
+            include_header: true,
+        }
+    }
+}
+
+impl TaxExportLayout {
+    /// Quotes the value if it contains the delimiter, a quote or a line break.
+    fn escape(&self, value: &str) -> String {
+        if value.contains([self.delimiter, '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+
+    fn format_money(amount: Money) -> String {
+        format!(
+            "{:.*}",
+            amount.currency().decimal_places() as usize,
+            amount.to_major_units()
+        )
+    }
+
+    fn format_field(&self, field: &TaxLotField, lot: &TaxLot) -> String {
+        match field {
+            TaxLotField::Description => lot.description(),
+            TaxLotField::DateAcquired => lot.date_acquired.format(&self.date_format).to_string(),
+            TaxLotField::Shares => lot.shares.to_string(),
+            TaxLotField::FairMarketValue => lot.fair_market_value.to_decimal_string(),
+            TaxLotField::PricePaid => lot.price_paid.to_decimal_string(),
+            TaxLotField::OrdinaryIncome => Self::format_money(lot.ordinary_income()),
+            TaxLotField::AmtAdjustment => Self::format_money(lot.amt_adjustment()),
+            TaxLotField::CostBasis => Self::format_money(lot.cost_basis()),
+            TaxLotField::AmtCostBasis => Self::format_money(lot.amt_cost_basis()),
+            TaxLotField::Currency => lot.price_paid.currency().code().to_string(),
+            TaxLotField::Constant(value) => value.clone(),
+        }
+    }
+
+    /// Writes the lots as CSV, one line per lot.
+    pub fn to_csv(&self, lots: &[TaxLot]) -> String {
+        let delimiter = self.delimiter.to_string();
+        let mut lines = Vec::with_capacity(lots.len() + 1);
+
+        if self.include_header {
+            lines.push(
+                self.columns
+                    .iter()
+                    .map(|(header, _)| self.escape(header))
+                    .collect::<Vec<_>>()
+                    .join(&delimiter),
+            );
+        }
+
+        for lot in lots {
+            lines.push(
+                self.columns
+                    .iter()
+                    .map(|(_, field)| self.escape(&self.format_field(field, lot)))
+                    .collect::<Vec<_>>()
+                    .join(&delimiter),
+            );
+        }
+
+        lines.into_iter().map(|line| line + "\n").collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LocalDate, TaxExportError, TaxExportLayout};
+    use crate::{
+        usd, usd_price, Grant, GrantLedger, GrantType, PriceTable, VestingScheduleConfiguration,
+    };
+
+    #[test]
+    fn it_can_export_lots_for_tax_software() {
+        let grant = Grant::for_test(
+            300,
+            LocalDate::from_ymd(2022, 1, 1),
+            VestingScheduleConfiguration::monthly(0, 0.0, 3),
+        );
+        let prices = PriceTable::new(vec![
+            (LocalDate::from_ymd(2022, 1, 1), usd_price(10.0)),
+            (LocalDate::from_ymd(2022, 3, 1), usd_price(12.0)),
+        ]);
+
+        let rsu_lots = GrantLedger::new(grant.clone())
+            .calculate_tax_lots(&[], &prices)
+            .unwrap();
+
+        let mut iso = GrantLedger::new(Grant {
+            grant_type: GrantType::Iso {
+                strike_price: usd_price(2.0),
+            },
+            ..grant
+        });
+        iso.record_exercise(LocalDate::from_ymd(2022, 3, 15), 50)
+            .unwrap();
+
+        let iso_lots = iso.calculate_tax_lots(&[], &prices).unwrap();
+
+        assert_eq!(rsu_lots.len(), 3);
+        assert_eq!(iso_lots.len(), 1);
+        assert_eq!(iso_lots[0].cost_basis(), usd(100.0));
+        assert_eq!(iso_lots[0].amt_cost_basis(), usd(600.0));
+        assert_eq!(
+            TaxExportLayout::default().to_csv(&[&rsu_lots[..1], &iso_lots[..]].concat()),
+            "Description,Date Acquired,Quantity,Ordinary Income,AMT Adjustment,Cost Basis,AMT Cost Basis\n\
+             100 sh RSU vest,02/01/2022,100,1000.00,0.00,1000.00,1000.00\n\
+             50 sh ISO exercise,03/15/2022,50,0.00,500.00,100.00,600.00\n"
+        );
+
+        assert_eq!(
+            iso.calculate_tax_lots(&[], &PriceTable::default()),
+            Err(TaxExportError::MissingPrice(LocalDate::from_ymd(
+                2022, 3, 15
+            )))
+        );
+    }
+}
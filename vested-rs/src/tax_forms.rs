@@ -0,0 +1,177 @@
+use chrono::Datelike;
+
+use crate::{LocalDate, Price};
+
+/// Shares acquired by exercising an incentive stock option.
+pub struct IsoExercise {
+    pub participant: String,
+    pub grant_date: LocalDate,
+    pub exercise_date: LocalDate,
+    pub exercise_price_per_share: Price,
+    /// Fair market value per share on the exercise date.
+    pub fair_market_value_per_share: Price,
+    pub shares: i64,
+}
+
+/// Shares purchased under an employee stock purchase plan and transferred to the
+/// participant.
+pub struct EsppTransfer {
+    pub participant: String,
+    /// First day of the offering period.
+    pub grant_date: LocalDate,
+    /// Purchase date.
+    pub exercise_date: LocalDate,
+    pub fair_market_value_on_grant_date: Price,
+    pub fair_market_value_on_exercise_date: Price,
+    pub price_paid_per_share: Price,
+    /// Only needed when the exercise price wasn't fixed on the grant date, e.g. a
+    /// lookback, in which case it's the price had the option been exercised then.
+    pub price_if_exercised_on_grant_date: Option<Price>,
+    pub shares: i64,
+    pub transfer_date: LocalDate,
+}
+
+/// The data reported on one IRS Form 3921.
+#[derive(Debug, PartialEq)]
+pub struct Form3921Record {
+    pub participant: String,
+    pub tax_year: i32,
+    /// Box 1.
+    pub grant_date: LocalDate,
+    /// Box 2.
+    pub exercise_date: LocalDate,
+    /// Box 3.
+    pub exercise_price_per_share: Price,
+    /// Box 4.
+    pub fair_market_value_per_share: Price,
+    /// Box 5.
+    pub shares_transferred: i64,
+}
+
+/// The data reported on one IRS Form 3922.
+#[derive(Debug, PartialEq)]
+pub struct Form3922Record {
+    pub participant: String,
+    pub tax_year: i32,
+    /// Box 1.
+    pub grant_date: LocalDate,
+    /// Box 2.
+    pub exercise_date: LocalDate,
+    /// Box 3.
+    pub fair_market_value_on_grant_date: Price,
+    /// Box 4.
+    pub fair_market_value_on_exercise_date: Price,
+    /// Box 5.
+    pub price_paid_per_share: Price,
+    /// Box 6.
+    pub shares_transferred: i64,
+    /// Box 7.
+    pub transfer_date: LocalDate,
+    /// Box 8.
+    pub price_if_exercised_on_grant_date: Option<Price>,
+}
+
+/// Builds the Form 3921 data for each exercise, ordered by participant, then tax year
+/// and exercise date. The tax year is the year of exercise.
+pub fn form_3921_records(exercises: &[IsoExercise]) -> Vec<Form3921Record> {
+    let mut records: Vec<Form3921Record> = exercises
+        .iter()
+        .map(|exercise| Form3921Record {
+            participant: exercise.participant.clone(),
+            tax_year: exercise.exercise_date.year(),
+            grant_date: exercise.grant_date,
+            exercise_date: exercise.exercise_date,
+            exercise_price_per_share: exercise.exercise_price_per_share,
+            fair_market_value_per_share: exercise.fair_market_value_per_share,
+            shares_transferred: exercise.shares,
+        })
+        .collect();
+
+    records.sort_by(|a, b| {
+        (&a.participant, a.tax_year, a.exercise_date).cmp(&(
+            &b.participant,
+            b.tax_year,
+            b.exercise_date,
+        ))
+    });
+
+    records
+}
+
+/// Builds the Form 3922 data for each transfer, ordered by participant, then tax year
+/// and transfer date. The tax year is the year legal title was transferred.
+pub fn form_3922_records(transfers: &[EsppTransfer]) -> Vec<Form3922Record> {
+    let mut records: Vec<Form3922Record> = transfers
+        .iter()
+        .map(|transfer| Form3922Record {
+            participant: transfer.participant.clone(),
+            tax_year: transfer.transfer_date.year(),
+            grant_date: transfer.grant_date,
+            exercise_date: transfer.exercise_date,
+            fair_market_value_on_grant_date: transfer.fair_market_value_on_grant_date,
+            fair_market_value_on_exercise_date: transfer.fair_market_value_on_exercise_date,
+            price_paid_per_share: transfer.price_paid_per_share,
+            shares_transferred: transfer.shares,
+            transfer_date: transfer.transfer_date,
+            price_if_exercised_on_grant_date: transfer.price_if_exercised_on_grant_date,
+        })
+        .collect();
+
+    records.sort_by(|a, b| {
+        (&a.participant, a.tax_year, a.transfer_date).cmp(&(
+            &b.participant,
+            b.tax_year,
+            b.transfer_date,
+        ))
+    });
+
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{form_3921_records, form_3922_records, EsppTransfer, IsoExercise, LocalDate};
+    use crate::usd_price;
+
+    #[test]
+    fn it_can_extract_form_3921_and_3922_data() {
+        let exercise = |participant: &str, exercise_date| IsoExercise {
+            participant: participant.to_string(),
+            grant_date: LocalDate::from_ymd(2019, 3, 1),
+            exercise_date,
+            exercise_price_per_share: usd_price(1.5),
+            fair_market_value_per_share: usd_price(9.0),
+            shares: 1_000,
+        };
+
+        let records = form_3921_records(&[
+            exercise("Sam", LocalDate::from_ymd(2022, 6, 1)),
+            exercise("Alex", LocalDate::from_ymd(2022, 9, 1)),
+            exercise("Sam", LocalDate::from_ymd(2021, 11, 1)),
+        ]);
+
+        assert_eq!(
+            records
+                .iter()
+                .map(|record| (record.participant.as_str(), record.tax_year))
+                .collect::<Vec<_>>(),
+            vec![("Alex", 2022), ("Sam", 2021), ("Sam", 2022)]
+        );
+        assert_eq!(records[0].exercise_price_per_share, usd_price(1.5));
+
+        let records = form_3922_records(&[EsppTransfer {
+            participant: "Sam".to_string(),
+            grant_date: LocalDate::from_ymd(2022, 1, 1),
+            exercise_date: LocalDate::from_ymd(2022, 6, 30),
+            fair_market_value_on_grant_date: usd_price(20.0),
+            fair_market_value_on_exercise_date: usd_price(25.0),
+            price_paid_per_share: usd_price(17.0),
+            price_if_exercised_on_grant_date: Some(usd_price(17.0)),
+            shares: 50,
+            transfer_date: LocalDate::from_ymd(2023, 1, 3),
+        }]);
+
+        assert_eq!(records[0].tax_year, 2023);
+        assert_eq!(records[0].shares_transferred, 50);
+    }
+}
@@ -0,0 +1,205 @@
+use std::fmt;
+
+use crate::{Grant, LocalDate};
+
+#[derive(Debug, PartialEq)]
+pub enum GrantEventKind {
+    Granted {
+        amount: i64,
+    },
+    CliffReached {
+        vested_amount: i64,
+    },
+    FullyVested {
+        amount: i64,
+    },
+    /// The grant was terminated before fully vesting, forfeiting the unvested shares.
+    Terminated {
+        vested_amount: i64,
+        forfeited_amount: i64,
+    },
+    /// Free-form note attached to the grant, e.g. a board approval or an amendment.
+    Note {
+        text: String,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GrantEvent {
+    pub date: LocalDate,
+    pub kind: GrantEventKind,
+}
+
+/// Chronologically ordered events for a single grant.
+#[derive(Debug, PartialEq)]
+pub struct Timeline {
+    pub events: Vec<GrantEvent>,
+}
+
+impl fmt::Display for GrantEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: ", self.date.format("%Y-%m-%d"))?;
+
+        match &self.kind {
+            GrantEventKind::Granted { amount } => write!(f, "Granted {} shares", amount),
+            GrantEventKind::CliffReached { vested_amount } => {
+                write!(f, "Cliff reached, {} shares vested", vested_amount)
+            }
+            GrantEventKind::FullyVested { amount } => {
+                write!(f, "Fully vested, {} shares vested", amount)
+            }
+            GrantEventKind::Terminated {
+                vested_amount,
+                forfeited_amount,
+            } => write!(
+                f,
+                "Terminated, {} shares vested and {} forfeited",
+                vested_amount, forfeited_amount
+            ),
+            GrantEventKind::Note { text } => write!(f, "{}", text),
+        }
+    }
+}
+
+impl fmt::Display for Timeline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for event in &self.events {
+            writeln!(f, "{}", event)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Timeline {
+    /// Builds a timeline from events in any order. Events on the same date keep
+    /// their relative order.
+    pub fn from_events(mut events: Vec<GrantEvent>) -> Timeline {
+        events.sort_by_key(|event| event.date);
+
+        Timeline { events }
+    }
+
+    /// Adds an event, keeping the timeline in chronological order.
+    pub fn push(&mut self, event: GrantEvent) {
+        let index = self
+            .events
+            .partition_point(|existing| existing.date <= event.date);
+
+        self.events.insert(index, event);
+    }
+}
+
+impl Grant {
+    /// Lists the grant, cliff and full-vest events implied by the grant's terms. A cliff
+    /// or full vest falling before the grant date is reached on it instead. A grant
+    /// terminated before fully vesting ends with its termination instead.
+    pub fn timeline(&self) -> Timeline {
+        let mut events = vec![GrantEvent {
+            date: self.grant_date,
+            kind: GrantEventKind::Granted {
+                amount: self.amount,
+            },
+        }];
+
+        if let Some(cliff) = self.vesting_schedule.cliff() {
+            let cliff_date = self
+                .vesting_schedule
+                .interval_date(self.vesting_commencement_date(), cliff.intervals)
+                .max(self.grant_date);
+
+            if self
+                .termination_date
+                .is_some_and(|termination_date| termination_date < cliff_date)
+            {
+                return self.terminated_timeline(events);
+            }
+
+            events.push(GrantEvent {
+                date: cliff_date,
+                kind: GrantEventKind::CliffReached {
+                    vested_amount: self.calculate_vested_shares(cliff_date),
+                },
+            });
+        }
+
+        match self.fully_vested_date() {
+            Some(fully_vested_date) => events.push(GrantEvent {
+                date: fully_vested_date,
+                kind: GrantEventKind::FullyVested {
+                    amount: self.amount,
+                },
+            }),
+            None => return self.terminated_timeline(events),
+        }
+
+        Timeline::from_events(events)
+    }
+
+    /// Ends the timeline with the grant's termination.
+    fn terminated_timeline(&self, mut events: Vec<GrantEvent>) -> Timeline {
+        if let Some(termination_date) = self.termination_date {
+            let vested_amount = self.calculate_vested_shares(termination_date);
+
+            events.push(GrantEvent {
+                date: termination_date,
+                kind: GrantEventKind::Terminated {
+                    vested_amount,
+                    forfeited_amount: self.amount - vested_amount,
+                },
+            });
+        }
+
+        Timeline::from_events(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GrantEvent, GrantEventKind, LocalDate};
+    use crate::{Grant, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_render_a_grant_timeline() {
+        let grant = Grant::for_test(
+            10_000,
+            LocalDate::from_ymd(2020, 2, 6),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        );
+
+        let mut timeline = grant.timeline();
+
+        timeline.push(GrantEvent {
+            date: LocalDate::from_ymd(2020, 3, 1),
+            kind: GrantEventKind::Note {
+                text: String::from("Board approved grant"),
+            },
+        });
+
+        assert_eq!(
+            timeline.to_string(),
+            "2020-02-06: Granted 10000 shares\n\
+             2020-03-01: Board approved grant\n\
+             2021-02-06: Cliff reached, 2500 shares vested\n\
+             2024-02-06: Fully vested, 10000 shares vested\n"
+        );
+        let mut terminated = grant.clone();
+
+        terminated.terminate(LocalDate::from_ymd(2022, 2, 20));
+
+        assert_eq!(
+            terminated.timeline().to_string(),
+            "2020-02-06: Granted 10000 shares\n\
+             2021-02-06: Cliff reached, 2500 shares vested\n\
+             2022-02-20: Terminated, 5000 shares vested and 5000 forfeited\n"
+        );
+
+        terminated.terminate(LocalDate::from_ymd(2020, 12, 20));
+
+        assert_eq!(
+            terminated.timeline().to_string(),
+            "2020-02-06: Granted 10000 shares\n\
+             2020-12-20: Terminated, 0 shares vested and 10000 forfeited\n"
+        );
+    }
+}
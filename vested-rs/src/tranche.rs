@@ -0,0 +1,179 @@
+use std::fmt;
+
+use chrono::Datelike;
+
+use crate::{CapTable, Grant, LocalDate, Money, Price};
+
+/// The shares vesting on one date, valued at an exit price.
+#[derive(Debug, PartialEq)]
+pub struct TrancheValue {
+    pub date: LocalDate,
+    pub vested_this_period: i64,
+    pub value: Money,
+    /// Fraction of the grant's total value the tranche carries.
+    pub share_of_value: f64,
+    /// Year of the schedule the tranche vests in, starting at 1 for the 12 months
+    /// following the vesting commencement date.
+    pub schedule_year: i32,
+}
+
+/// A grant's tranches valued at an exit price, in vesting order.
+pub struct TrancheValueReport {
+    pub price_per_share: Price,
+    pub tranches: Vec<TrancheValue>,
+}
+
+/// Counts the whole months from one date to a later one.
+fn months_between(from: LocalDate, to: LocalDate) -> i32 {
+    let months = (to.year() - from.year()) * 12 + to.month() as i32 - from.month() as i32;
+
+    if to.day() < from.day() {
+        months - 1
+    } else {
+        months
+    }
+}
+
+impl Grant {
+    /// Values each tranche of the grant's schedule at the exit price per share.
+    pub fn calculate_tranche_values(&self, price_per_share: Price) -> TrancheValueReport {
+        let total_value = price_per_share * self.amount;
+
+        let tranches = self
+            .vest_events()
+            .into_iter()
+            .map(|event| {
+                let value = price_per_share * event.amount;
+
+                TrancheValue {
+                    date: event.date,
+                    vested_this_period: event.amount,
+                    value,
+                    share_of_value: if total_value.minor_units() != 0 {
+                        value.minor_units() as f64 / total_value.minor_units() as f64
+                    } else {
+                        0.0
+                    },
+                    schedule_year: (months_between(self.vesting_commencement_date(), event.date)
+                        + 11)
+                        / 12,
+                }
+            })
+            .collect();
+
+        TrancheValueReport {
+            price_per_share,
+            tranches,
+        }
+    }
+
+    /// Values each tranche at the price per share implied by an exit at the given
+    /// company valuation, spread over the fully diluted shares on the exit date.
+    pub fn calculate_tranche_values_at_valuation(
+        &self,
+        valuation: Money,
+        exit_date: LocalDate,
+        cap_table: &CapTable,
+    ) -> Option<TrancheValueReport> {
+        let fully_diluted_shares = cap_table.fully_diluted_shares_on(exit_date)?;
+
+        if fully_diluted_shares <= 0 {
+            return None;
+        }
+
+        Some(self.calculate_tranche_values(Price::per_share(valuation, fully_diluted_shares)))
+    }
+}
+
+impl TrancheValueReport {
+    /// Totals the value vesting in each year of the schedule, starting with year 1.
+    pub fn value_by_schedule_year(&self) -> Vec<Money> {
+        let years = self
+            .tranches
+            .iter()
+            .map(|tranche| tranche.schedule_year)
+            .max()
+            .unwrap_or(0);
+
+        (1..=years)
+            .map(|year| {
+                Money::sum(
+                    self.price_per_share.currency(),
+                    self.tranches
+                        .iter()
+                        .filter(|tranche| tranche.schedule_year == year)
+                        .map(|tranche| tranche.value),
+                )
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for TrancheValueReport {
+    /// Formats the report as a plain text table, one row per tranche.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<10}  {:>4}  {:>10}  {:>16}  {:>7}",
+            "Date", "Year", "Vested", "Value", "Share"
+        )?;
+
+        for tranche in &self.tranches {
+            writeln!(
+                f,
+                "{:<10}  {:>4}  {:>10}  {:>16}  {:>6.2}%",
+                tranche.date.to_string(),
+                tranche.schedule_year,
+                tranche.vested_this_period,
+                tranche.value.to_string(),
+                tranche.share_of_value * 100.0
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LocalDate;
+    use crate::{usd, usd_price, CapTable, Grant, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_value_each_tranche_at_an_exit() {
+        let grant = Grant::for_test(
+            4_800,
+            LocalDate::from_ymd(2021, 1, 15),
+            VestingScheduleConfiguration::monthly(12, 0.25, 48),
+        );
+        let cap_table = CapTable::new(vec![(LocalDate::from_ymd(2021, 1, 1), 10_000_000)]);
+
+        let report = grant
+            .calculate_tranche_values_at_valuation(
+                usd(50_000_000.0),
+                LocalDate::from_ymd(2025, 1, 1),
+                &cap_table,
+            )
+            .unwrap();
+
+        /*
+         * The cliff carries a quarter of the value in year 1, the monthly tranches
+         * after it the rest.
+         */
+
+        assert_eq!(report.price_per_share, usd_price(5.0));
+        assert_eq!(report.tranches.len(), 37);
+        assert_eq!(report.tranches[0].value, usd(6_000.0));
+        assert_eq!(report.tranches[0].share_of_value, 0.25);
+        assert_eq!(report.tranches[0].schedule_year, 1);
+        assert_eq!(report.tranches[1].schedule_year, 2);
+        assert_eq!(
+            report.value_by_schedule_year(),
+            vec![usd(6_000.0), usd(6_000.0), usd(6_000.0), usd(6_000.0)]
+        );
+        assert_eq!(
+            report.to_string().lines().nth(1),
+            Some("2022-01-15     1        1200         $6,000.00   25.00%")
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{Currency, Grant, LocalDate, Money, Price};
+
+/// A source of fair market values per share.
+pub trait ValuationProvider {
+    /// Returns the fair market value per share on the given date, if known.
+    fn fmv_on(&self, date: LocalDate) -> Option<Price>;
+}
+
+/// Fair market values that hold from their effective date until the next one, like a
+/// series of 409A valuations.
+#[derive(Debug, Default, PartialEq)]
+pub struct PriceTable {
+    prices: Vec<(LocalDate, Price)>,
+}
+
+impl PriceTable {
+    pub fn new(mut prices: Vec<(LocalDate, Price)>) -> PriceTable {
+        prices.sort_by_key(|(date, _)| *date);
+        PriceTable { prices }
+    }
+
+    /// Parses one `date,price` line per valuation. Blank lines and lines starting
+    /// with `#` are ignored, as is a leading `date,price` header. Prices are given in
+    /// major units of the currency, e.g. dollars.
+    pub fn parse_csv(source: &str, currency: Currency) -> Option<PriceTable> {
+        let mut prices = Vec::new();
+
+        for (index, line) in source.lines().map(str::trim).enumerate() {
+            if line.is_empty() || line.starts_with('#') || (index == 0 && line == "date,price") {
+                continue;
+            }
+
+            let (date, price) = line.split_once(',')?;
+            prices.push((
+                date.trim().parse().ok()?,
+                Price::from_major_units(price.trim().parse().ok()?, currency),
+            ));
+        }
+
+        Some(PriceTable::new(prices))
+    }
+
+    /// Reads a CSV file in the format accepted by `parse_csv`.
+    pub fn from_csv_file<P: AsRef<Path>>(path: P, currency: Currency) -> io::Result<PriceTable> {
+        PriceTable::parse_csv(&fs::read_to_string(path)?, currency)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed price table"))
+    }
+}
+
+impl ValuationProvider for PriceTable {
+    fn fmv_on(&self, date: LocalDate) -> Option<Price> {
+        self.prices
+            .iter()
+            .take_while(|(effective_date, _)| *effective_date <= date)
+            .last()
+            .map(|(_, price)| *price)
+    }
+}
+
+impl Grant {
+    /// Calculates the value of the shares vested on the given date, or `None` if the
+    /// provider has no price for it.
+    pub fn calculate_vested_value<V: ValuationProvider>(
+        &self,
+        date: LocalDate,
+        provider: &V,
+    ) -> Option<Money> {
+        Some(provider.fmv_on(date)? * self.calculate_vested_shares(date))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Currency, LocalDate, PriceTable, ValuationProvider};
+    use crate::{usd, usd_price, Grant, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_can_look_up_prices_from_a_table() {
+        let table = PriceTable::parse_csv(
+            "date,price\n\
+             2021-06-01,4.50\n\
+             # annual 409A\n\
+             2021-01-01,3.00\n",
+            Currency::Usd,
+        )
+        .unwrap();
+
+        assert_eq!(table.fmv_on(LocalDate::from_ymd(2020, 12, 31)), None);
+        assert_eq!(
+            table.fmv_on(LocalDate::from_ymd(2021, 3, 1)),
+            Some(usd_price(3.0))
+        );
+        assert_eq!(
+            table.fmv_on(LocalDate::from_ymd(2021, 6, 1)),
+            Some(usd_price(4.5))
+        );
+        assert_eq!(
+            PriceTable::parse_csv("2021-01-01,three", Currency::Usd),
+            None
+        );
+    }
+
+    #[test]
+    fn it_can_value_vested_shares_from_a_table() {
+        let table = PriceTable::parse_csv(
+            "date,price\n2021-01-01,3.00\n2021-06-01,4.50\n",
+            Currency::Usd,
+        )
+        .unwrap();
+        let grant = Grant::for_test(
+            1_200,
+            LocalDate::from_ymd(2021, 1, 1),
+            VestingScheduleConfiguration::monthly(0, 0.0, 12),
+        );
+
+        assert_eq!(
+            grant.calculate_vested_value(LocalDate::from_ymd(2021, 7, 1), &table),
+            Some(usd(2700.0))
+        );
+    }
+}
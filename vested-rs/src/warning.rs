@@ -0,0 +1,124 @@
+use chrono::{Datelike, Weekday};
+
+use crate::{Grant, LocalDate, VestingSchedule};
+
+/// How far the cliff percentage may stray from the cliff's share of the vesting length.
+const CLIFF_PERCENTAGE_TOLERANCE: f32 = 0.05;
+
+/// Longest schedule, in months, that isn't considered unusual.
+const MAX_EXPECTED_LENGTH: i32 = 120;
+
+/// Inputs that are legal but likely to be a mistake.
+#[derive(Debug, PartialEq)]
+pub enum VestingWarning {
+    /// The cliff vests a noticeably different share than the time it covers, e.g. 50% at a
+    /// 12 month cliff on a 48 month schedule.
+    DisproportionateCliff {
+        cliff_percentage: f32,
+        expected_percentage: f32,
+    },
+    /// Shares vest on a Saturday or Sunday.
+    WeekendVestDate(LocalDate),
+    /// The schedule runs longer than ten years. The length is in vesting intervals.
+    LongSchedule { length: i32 },
+    /// The grant is dated before the board approved it.
+    GrantDatePrecedesBoardApproval {
+        grant_date: LocalDate,
+        board_approval_date: LocalDate,
+    },
+    /// The grant is dated before the 409A valuation its price is based on.
+    GrantDatePrecedesValuation {
+        grant_date: LocalDate,
+        valuation_date: LocalDate,
+    },
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Warnings {
+    pub warnings: Vec<VestingWarning>,
+}
+
+impl Warnings {
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+impl Grant {
+    /// Checks the grant for suspicious-but-legal terms.
+    pub fn warnings(&self) -> Warnings {
+        let configuration = &self.vesting_schedule;
+        let mut warnings = Vec::new();
+
+        if let Some(cliff) = configuration
+            .cliff()
+            .filter(|_| configuration.total_intervals > 0)
+        {
+            let expected_percentage = cliff.intervals as f32 / configuration.total_intervals as f32;
+
+            if (cliff.percentage - expected_percentage).abs() > CLIFF_PERCENTAGE_TOLERANCE {
+                warnings.push(VestingWarning::DisproportionateCliff {
+                    cliff_percentage: cliff.percentage,
+                    expected_percentage,
+                });
+            }
+        }
+
+        if configuration.total_vesting_months() > MAX_EXPECTED_LENGTH {
+            warnings.push(VestingWarning::LongSchedule {
+                length: configuration.total_intervals,
+            });
+        }
+
+        warnings.extend(
+            self.vest_events()
+                .into_iter()
+                .filter(|event| matches!(event.date.weekday(), Weekday::Sat | Weekday::Sun))
+                .map(|event| VestingWarning::WeekendVestDate(event.date)),
+        );
+
+        Warnings { warnings }
+    }
+
+    /// Calculates the full vesting schedule along with any warnings about the grant's terms.
+    pub fn calculate_vesting_schedule_with_warnings(&self) -> (VestingSchedule, Warnings) {
+        (self.calculate_vesting_schedule(), self.warnings())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LocalDate, VestingWarning};
+    use crate::{Grant, VestingScheduleConfiguration};
+
+    #[test]
+    fn it_warns_about_suspicious_grant_terms() {
+        let grant = Grant::for_test(
+            10_000,
+            LocalDate::from_ymd(2020, 2, 1),
+            VestingScheduleConfiguration::monthly(2, 0.5, 4),
+        );
+
+        assert!(grant.warnings().is_empty());
+
+        let grant = Grant::for_test(
+            10_000,
+            LocalDate::from_ymd(2020, 2, 1),
+            VestingScheduleConfiguration::monthly(1, 0.5, 4),
+        );
+
+        let (vesting_schedule, warnings) = grant.calculate_vesting_schedule_with_warnings();
+
+        assert_eq!(vesting_schedule.periods.len(), 5);
+        assert_eq!(
+            warnings.warnings,
+            vec![
+                VestingWarning::DisproportionateCliff {
+                    cliff_percentage: 0.5,
+                    expected_percentage: 0.25,
+                },
+                VestingWarning::WeekendVestDate(LocalDate::from_ymd(2020, 3, 1)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{Grant, LocalDate, VestingInterval};
+
+/// A grant, exposed to JavaScript as `Grant`.
+#[wasm_bindgen(js_name = Grant)]
+pub struct WasmGrant {
+    grant: Grant,
+}
+
+/// A vesting period, exposed to JavaScript as `VestingPeriod`.
+#[wasm_bindgen(js_name = VestingPeriod, getter_with_clone)]
+pub struct WasmVestingPeriod {
+    /// Date as `YYYY-MM-DD`.
+    pub date: String,
+    #[wasm_bindgen(js_name = vestedThisPeriod)]
+    pub vested_this_period: i64,
+    #[wasm_bindgen(js_name = cumulativeVestedAmount)]
+    pub cumulative_vested_amount: i64,
+    /// Amount vested by the date before rounding to whole shares.
+    #[wasm_bindgen(js_name = preciseCumulativeAmount)]
+    pub precise_cumulative_amount: f64,
+}
+
+fn parse_date(value: &str) -> Result<LocalDate, JsError> {
+    value
+        .parse()
+        .map_err(|_| JsError::new(&format!("invalid date \"{}\"", value)))
+}
+
+fn parse_interval(value: &str) -> Result<VestingInterval, JsError> {
+    match value {
+        "weekly" => Ok(VestingInterval::Weekly),
+        "bi-weekly" | "biweekly" => Ok(VestingInterval::BiWeekly),
+        "monthly" => Ok(VestingInterval::Monthly),
+        "quarterly" => Ok(VestingInterval::Quarterly),
+        "annual" | "yearly" => Ok(VestingInterval::Annual),
+        _ => Err(JsError::new(&format!("unknown interval \"{}\"", value))),
+    }
+}
+
+#[wasm_bindgen(js_class = Grant)]
+impl WasmGrant {
+    /// Makes a grant from its terms, with dates as `YYYY-MM-DD` and the interval one of
+    /// `weekly`, `bi-weekly`, `monthly`, `quarterly` or `annual`. Throws if the terms
+    /// don't produce a meaningful schedule.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        amount: i64,
+        grant_date: &str,
+        interval: &str,
+        length_months: i32,
+        cliff_months: i32,
+        cliff_percentage: f32,
+    ) -> Result<WasmGrant, JsError> {
+        let grant = Grant::builder()
+            .amount(amount)
+            .grant_date(parse_date(grant_date)?)
+            .interval(parse_interval(interval)?)
+            .length_months(length_months)
+            .cliff_months(cliff_months)
+            .cliff_percentage(cliff_percentage)
+            .build()?;
+
+        Ok(WasmGrant { grant })
+    }
+
+    /// Stops vesting on the date, forfeiting the unvested shares.
+    pub fn terminate(&mut self, date: &str) -> Result<(), JsError> {
+        self.grant.terminate(parse_date(date)?);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = vestedSharesOn)]
+    pub fn vested_shares_on(&self, date: &str) -> Result<i64, JsError> {
+        Ok(self.grant.calculate_vested_shares(parse_date(date)?))
+    }
+
+    pub fn schedule(&self) -> Vec<WasmVestingPeriod> {
+        self.grant
+            .calculate_vesting_schedule()
+            .periods
+            .into_iter()
+            .map(|period| WasmVestingPeriod {
+                date: period.date.to_string(),
+                vested_this_period: period.vested_this_period,
+                cumulative_vested_amount: period.cumulative_vested_amount,
+                precise_cumulative_amount: period.precise_cumulative_amount,
+            })
+            .collect()
+    }
+
+    /// Describes the schedule in plain language.
+    pub fn describe(&self) -> String {
+        self.grant.describe_vesting()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WasmGrant;
+
+    #[test]
+    fn it_can_calculate_schedules_for_javascript() {
+        let grant = WasmGrant::new(300, "2021-01-15", "monthly", 3, 0, 0.0).unwrap_or_else(|_| {
+            panic!("invalid grant");
+        });
+        let schedule = grant.schedule();
+
+        assert_eq!(schedule.len(), 4);
+        assert_eq!(schedule[1].date, "2021-02-15");
+        assert_eq!(schedule[3].cumulative_vested_amount, 300);
+    }
+}